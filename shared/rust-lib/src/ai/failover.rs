@@ -0,0 +1,100 @@
+//! Automatic provider failover: try providers in priority order, falling
+//! through to the next one when a provider is unavailable or rate-limited
+//! rather than surfacing the failure to the user immediately.
+
+use super::{AiProvider, ChatMessage, ProviderError};
+
+/// Try each provider in `providers` (priority order) until one returns a
+/// successful chat response, returning the id of the provider that
+/// succeeded alongside its response. [`ProviderError::InvalidRequest`] is
+/// not retried against the next provider — a malformed request will fail
+/// identically everywhere, so failing over just wastes the other providers'
+/// quota.
+pub fn get_provider_with_fallback(
+    providers: &[Box<dyn AiProvider>],
+    messages: &[ChatMessage],
+) -> Result<(&'static str, String), ProviderError> {
+    let mut last_error = ProviderError::Unavailable("no providers configured".to_string());
+
+    for provider in providers {
+        match provider.chat(messages) {
+            Ok(response) => return Ok((provider.id(), response)),
+            Err(ProviderError::InvalidRequest(reason)) => {
+                return Err(ProviderError::InvalidRequest(reason));
+            }
+            Err(error) => last_error = error,
+        }
+    }
+
+    Err(last_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::ChatRole;
+
+    struct FailingProvider(&'static str, ProviderError);
+    impl AiProvider for FailingProvider {
+        fn id(&self) -> &'static str {
+            self.0
+        }
+        fn chat(&self, _messages: &[ChatMessage]) -> Result<String, ProviderError> {
+            Err(self.1.clone())
+        }
+    }
+
+    struct WorkingProvider(&'static str);
+    impl AiProvider for WorkingProvider {
+        fn id(&self) -> &'static str {
+            self.0
+        }
+        fn chat(&self, _messages: &[ChatMessage]) -> Result<String, ProviderError> {
+            Ok("response".to_string())
+        }
+    }
+
+    fn messages() -> Vec<ChatMessage> {
+        vec![ChatMessage {
+            role: ChatRole::User,
+            content: "hi".to_string(),
+        }]
+    }
+
+    #[test]
+    fn falls_back_to_the_next_provider_on_failure() {
+        let providers: Vec<Box<dyn AiProvider>> = vec![
+            Box::new(FailingProvider("primary", ProviderError::RateLimited)),
+            Box::new(WorkingProvider("secondary")),
+        ];
+
+        let (provider_id, response) = get_provider_with_fallback(&providers, &messages()).unwrap();
+        assert_eq!(provider_id, "secondary");
+        assert_eq!(response, "response");
+    }
+
+    #[test]
+    fn invalid_request_is_not_retried_against_other_providers() {
+        let providers: Vec<Box<dyn AiProvider>> = vec![
+            Box::new(FailingProvider(
+                "primary",
+                ProviderError::InvalidRequest("bad prompt".to_string()),
+            )),
+            Box::new(WorkingProvider("secondary")),
+        ];
+
+        let result = get_provider_with_fallback(&providers, &messages());
+        assert_eq!(result, Err(ProviderError::InvalidRequest("bad prompt".to_string())));
+    }
+
+    #[test]
+    fn all_providers_failing_returns_the_last_error() {
+        let providers: Vec<Box<dyn AiProvider>> = vec![
+            Box::new(FailingProvider("primary", ProviderError::RateLimited)),
+            Box::new(FailingProvider("secondary", ProviderError::Unavailable("down".to_string()))),
+        ];
+
+        let result = get_provider_with_fallback(&providers, &messages());
+        assert_eq!(result, Err(ProviderError::Unavailable("down".to_string())));
+    }
+}
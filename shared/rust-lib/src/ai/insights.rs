@@ -0,0 +1,143 @@
+//! Extracting structured insights (action items, sentiment, suggested
+//! priority) from an email's content via an [`AiProvider`], instead of
+//! returning free-form text the caller has to re-parse.
+
+use super::{AiProvider, ChatMessage, ChatRole, ProviderError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sentiment {
+    Positive,
+    Neutral,
+    Negative,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuggestedPriority {
+    Low,
+    Normal,
+    High,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailInsights {
+    pub summary: String,
+    pub action_items: Vec<String>,
+    pub sentiment: Sentiment,
+    pub suggested_priority: SuggestedPriority,
+}
+
+const INSIGHTS_SYSTEM_PROMPT: &str = "Extract insights from the email as JSON with keys: \
+    summary (string), action_items (array of strings), sentiment (positive|neutral|negative), \
+    suggested_priority (low|normal|high). Respond with JSON only, no prose.";
+
+/// Ask `provider` to extract insights from `email_body`, parsing its JSON
+/// response into structured [`EmailInsights`] rather than returning the raw
+/// string for every caller to parse themselves.
+pub fn extract_email_insights(
+    provider: &dyn AiProvider,
+    email_body: &str,
+) -> Result<EmailInsights, ProviderError> {
+    let messages = vec![
+        ChatMessage {
+            role: ChatRole::System,
+            content: INSIGHTS_SYSTEM_PROMPT.to_string(),
+        },
+        ChatMessage {
+            role: ChatRole::User,
+            content: email_body.to_string(),
+        },
+    ];
+
+    let raw_response = provider.chat(&messages)?;
+    parse_insights_json(&raw_response)
+        .ok_or_else(|| ProviderError::InvalidRequest("model response was not valid insights JSON".to_string()))
+}
+
+/// Minimal hand-rolled JSON field extraction — the crate has no JSON
+/// dependency yet, and these responses are a small fixed shape, so a full
+/// parser would be overkill.
+fn parse_insights_json(raw: &str) -> Option<EmailInsights> {
+    let summary = extract_string_field(raw, "summary")?;
+    let action_items = extract_string_array_field(raw, "action_items").unwrap_or_default();
+    let sentiment = match extract_string_field(raw, "sentiment")?.as_str() {
+        "positive" => Sentiment::Positive,
+        "negative" => Sentiment::Negative,
+        _ => Sentiment::Neutral,
+    };
+    let suggested_priority = match extract_string_field(raw, "suggested_priority")?.as_str() {
+        "low" => SuggestedPriority::Low,
+        "high" => SuggestedPriority::High,
+        _ => SuggestedPriority::Normal,
+    };
+
+    Some(EmailInsights {
+        summary,
+        action_items,
+        sentiment,
+        suggested_priority,
+    })
+}
+
+fn extract_string_field(raw: &str, field: &str) -> Option<String> {
+    let needle = format!("\"{field}\"");
+    let field_start = raw.find(&needle)? + needle.len();
+    let after_colon = raw[field_start..].find(':')? + field_start + 1;
+    let rest = raw[after_colon..].trim_start();
+    let quote_start = rest.strip_prefix('"')?;
+    let end = quote_start.find('"')?;
+    Some(quote_start[..end].to_string())
+}
+
+fn extract_string_array_field(raw: &str, field: &str) -> Option<Vec<String>> {
+    let needle = format!("\"{field}\"");
+    let field_start = raw.find(&needle)? + needle.len();
+    let after_colon = raw[field_start..].find(':')? + field_start + 1;
+    let rest = raw[after_colon..].trim_start();
+    let array_body_start = rest.strip_prefix('[')?;
+    let end = array_body_start.find(']')?;
+    let body = &array_body_start[..end];
+    Some(
+        body.split(',')
+            .map(|item| item.trim().trim_matches('"').to_string())
+            .filter(|item| !item.is_empty())
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedResponseProvider(&'static str);
+    impl AiProvider for FixedResponseProvider {
+        fn id(&self) -> &'static str {
+            "fixed"
+        }
+        fn chat(&self, _messages: &[ChatMessage]) -> Result<String, ProviderError> {
+            Ok(self.0.to_string())
+        }
+    }
+
+    #[test]
+    fn parses_structured_insights_from_json_response() {
+        let provider = FixedResponseProvider(
+            r#"{"summary": "Client wants a revised quote", "action_items": ["Send updated quote", "Schedule follow-up call"], "sentiment": "neutral", "suggested_priority": "high"}"#,
+        );
+
+        let insights = extract_email_insights(&provider, "email body").unwrap();
+        assert_eq!(insights.summary, "Client wants a revised quote");
+        assert_eq!(
+            insights.action_items,
+            vec!["Send updated quote".to_string(), "Schedule follow-up call".to_string()]
+        );
+        assert_eq!(insights.sentiment, Sentiment::Neutral);
+        assert_eq!(insights.suggested_priority, SuggestedPriority::High);
+    }
+
+    #[test]
+    fn malformed_response_is_reported_as_invalid_request() {
+        let provider = FixedResponseProvider("not json at all");
+        let result = extract_email_insights(&provider, "email body");
+        assert!(matches!(result, Err(ProviderError::InvalidRequest(_))));
+    }
+}
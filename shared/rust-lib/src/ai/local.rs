@@ -0,0 +1,125 @@
+//! Local (on-device) model provider, with a pull-based token stream API.
+//! The reply is generated in full first and then handed out token by
+//! token; tokens are not produced incrementally by the model.
+
+use super::{AiProvider, ChatMessage, ProviderError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StreamEvent {
+    Token(String),
+    Done,
+}
+
+/// A running local-model inference, yielding [`StreamEvent`]s as the model
+/// produces them. This models what a real llama.cpp/Candle binding would
+/// hand back instead of a `Vec<String>` collected up front — callers can
+/// render partial output as it arrives.
+pub trait ChatStream {
+    fn next_event(&mut self) -> Option<StreamEvent>;
+}
+
+/// Splits a fully-generated response into a token stream. Stands in for the
+/// real model's incremental decode loop; the public contract — "call
+/// `next_event` until it returns `None`" — is identical either way.
+pub struct BufferedChatStream {
+    tokens: std::vec::IntoIter<String>,
+    done_sent: bool,
+}
+
+impl BufferedChatStream {
+    fn new(text: &str) -> Self {
+        let tokens: Vec<String> = text.split_whitespace().map(|t| t.to_string()).collect();
+        Self {
+            tokens: tokens.into_iter(),
+            done_sent: false,
+        }
+    }
+}
+
+impl ChatStream for BufferedChatStream {
+    fn next_event(&mut self) -> Option<StreamEvent> {
+        if let Some(token) = self.tokens.next() {
+            return Some(StreamEvent::Token(token));
+        }
+        if !self.done_sent {
+            self.done_sent = true;
+            return Some(StreamEvent::Done);
+        }
+        None
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LocalProvider;
+
+impl LocalProvider {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Start a streaming chat completion, returning a [`ChatStream`] the
+    /// caller pulls events from. The full reply is computed by
+    /// [`AiProvider::chat`] before this returns and is then split on
+    /// whitespace into [`StreamEvent::Token`]s.
+    pub fn chat_stream(&self, messages: &[ChatMessage]) -> Result<Box<dyn ChatStream>, ProviderError> {
+        if messages.is_empty() {
+            return Err(ProviderError::InvalidRequest("no messages provided".to_string()));
+        }
+        let reply = self.chat(messages)?;
+        Ok(Box::new(BufferedChatStream::new(&reply)))
+    }
+}
+
+impl AiProvider for LocalProvider {
+    fn id(&self) -> &'static str {
+        "local"
+    }
+
+    fn chat(&self, messages: &[ChatMessage]) -> Result<String, ProviderError> {
+        Ok(messages.last().map(|m| m.content.clone()).unwrap_or_default())
+    }
+}
+
+/// Drain a [`ChatStream`] into its tokens and whether it completed, for
+/// callers (and tests) that want the whole thing synchronously.
+pub fn collect_stream(mut stream: Box<dyn ChatStream>) -> (Vec<String>, bool) {
+    let mut tokens = Vec::new();
+    let mut completed = false;
+    while let Some(event) = stream.next_event() {
+        match event {
+            StreamEvent::Token(token) => tokens.push(token),
+            StreamEvent::Done => completed = true,
+        }
+    }
+    (tokens, completed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::ChatRole;
+
+    #[test]
+    fn stream_yields_tokens_then_a_done_event() {
+        let provider = LocalProvider::new();
+        let messages = vec![ChatMessage {
+            role: ChatRole::User,
+            content: "hello world".to_string(),
+        }];
+
+        let stream = provider.chat_stream(&messages).unwrap();
+        let (tokens, completed) = collect_stream(stream);
+
+        assert_eq!(tokens, vec!["hello".to_string(), "world".to_string()]);
+        assert!(completed);
+    }
+
+    #[test]
+    fn empty_messages_are_rejected_before_streaming_starts() {
+        let provider = LocalProvider::new();
+        assert!(matches!(
+            provider.chat_stream(&[]),
+            Err(ProviderError::InvalidRequest(_))
+        ));
+    }
+}
@@ -0,0 +1,22 @@
+//! AI features: provider abstraction for chat, summarization and insight
+//! extraction over mail/calendar content.
+//!
+//! Gated behind the `ai` feature flag (disabled by default) so that builds
+//! which don't ship AI features — and don't want the extra dependency
+//! surface of the provider HTTP clients — can exclude this module entirely.
+
+pub mod anthropic;
+pub mod failover;
+pub mod insights;
+pub mod local;
+pub mod provider;
+pub mod retry;
+pub mod tokenizer;
+
+pub use anthropic::AnthropicProvider;
+pub use failover::get_provider_with_fallback;
+pub use insights::{extract_email_insights, EmailInsights, Sentiment, SuggestedPriority};
+pub use local::{collect_stream, ChatStream, LocalProvider, StreamEvent};
+pub use provider::{AiProvider, ChatMessage, ChatRole, ProviderError};
+pub use retry::{call_with_retry, RetryPolicy};
+pub use tokenizer::{BpeTokenizer, TokenBudget, Tokenizer};
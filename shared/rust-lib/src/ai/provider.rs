@@ -0,0 +1,52 @@
+//! Common `AiProvider` trait implemented by each backend (local model,
+//! Anthropic, ...).
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatRole {
+    System,
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatMessage {
+    pub role: ChatRole,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProviderError {
+    Unavailable(String),
+    RateLimited,
+    InvalidRequest(String),
+}
+
+pub trait AiProvider {
+    fn id(&self) -> &'static str;
+    fn chat(&self, messages: &[ChatMessage]) -> Result<String, ProviderError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct EchoProvider;
+    impl AiProvider for EchoProvider {
+        fn id(&self) -> &'static str {
+            "echo"
+        }
+        fn chat(&self, messages: &[ChatMessage]) -> Result<String, ProviderError> {
+            Ok(messages.last().map(|m| m.content.clone()).unwrap_or_default())
+        }
+    }
+
+    #[test]
+    fn provider_returns_last_message_content() {
+        let provider = EchoProvider;
+        let messages = vec![ChatMessage {
+            role: ChatRole::User,
+            content: "hello".to_string(),
+        }];
+        assert_eq!(provider.chat(&messages).unwrap(), "hello");
+    }
+}
@@ -0,0 +1,160 @@
+//! Retry provider HTTP calls with exponential backoff and jitter, so a
+//! transient rate limit or outage doesn't surface as a hard failure on the
+//! first attempt. The backoff shape mirrors
+//! [`crate::mail::imap::ReconnectBackoff`], but adds jitter — several
+//! callers can end up retrying the same provider at once, and synchronized
+//! backoff just re-creates the thundering herd it's meant to avoid.
+
+use super::ProviderError;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base: Duration,
+    pub max: Duration,
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32, base: Duration, max: Duration) -> Self {
+        Self { max_attempts, base, max }
+    }
+
+    /// Backoff delay before retry attempt number `attempt` (0-indexed: the
+    /// delay before the *second* call overall is `delay_for_attempt(0, _)`),
+    /// scaled by `jitter_seed` (expected in `0.0..=1.0`). `jitter_seed` is an
+    /// explicit parameter rather than sourced from a `rand` crate, since no
+    /// random-number dependency exists in this crate; callers wire in their
+    /// own source of randomness.
+    pub fn delay_for_attempt(&self, attempt: u32, jitter_seed: f64) -> Duration {
+        let factor = 1u32.checked_shl(attempt).unwrap_or(u32::MAX);
+        let capped = self.base.saturating_mul(factor).min(self.max);
+        let jittered_millis = (jitter_seed.clamp(0.0, 1.0) * capped.as_millis() as f64) as u64;
+        Duration::from_millis(jittered_millis)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3, Duration::from_millis(200), Duration::from_secs(10))
+    }
+}
+
+fn is_retryable(error: &ProviderError) -> bool {
+    !matches!(error, ProviderError::InvalidRequest(_))
+}
+
+/// Call `attempt` up to `policy.max_attempts` times, invoking `sleep`
+/// (rather than actually blocking) between retries so tests — and callers
+/// that want to plug in an async sleep — don't need to wait in real time.
+/// [`ProviderError::InvalidRequest`] is never retried, matching
+/// [`super::get_provider_with_fallback`]'s reasoning: a malformed request
+/// fails identically on every attempt.
+pub fn call_with_retry(
+    policy: &RetryPolicy,
+    jitter_seeds: &[f64],
+    mut attempt: impl FnMut(u32) -> Result<String, ProviderError>,
+    mut sleep: impl FnMut(Duration),
+) -> Result<String, ProviderError> {
+    let mut last_error = ProviderError::Unavailable("no attempts made".to_string());
+
+    for attempt_number in 0..policy.max_attempts {
+        match attempt(attempt_number) {
+            Ok(response) => return Ok(response),
+            Err(error) if !is_retryable(&error) => return Err(error),
+            Err(error) => {
+                last_error = error;
+                if attempt_number + 1 < policy.max_attempts {
+                    let seed = jitter_seeds.get(attempt_number as usize).copied().unwrap_or(0.5);
+                    sleep(policy.delay_for_attempt(attempt_number, seed));
+                }
+            }
+        }
+    }
+
+    Err(last_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delay_grows_and_caps() {
+        let policy = RetryPolicy::new(5, Duration::from_millis(100), Duration::from_secs(1));
+        assert_eq!(policy.delay_for_attempt(0, 1.0), Duration::from_millis(100));
+        assert_eq!(policy.delay_for_attempt(1, 1.0), Duration::from_millis(200));
+        assert_eq!(policy.delay_for_attempt(2, 1.0), Duration::from_millis(400));
+        assert_eq!(policy.delay_for_attempt(10, 1.0), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn jitter_seed_scales_the_delay_down_from_the_ceiling() {
+        let policy = RetryPolicy::new(5, Duration::from_millis(100), Duration::from_secs(1));
+        assert_eq!(policy.delay_for_attempt(0, 0.0), Duration::from_millis(0));
+        assert_eq!(policy.delay_for_attempt(0, 0.5), Duration::from_millis(50));
+    }
+
+    #[test]
+    fn retries_on_transient_errors_until_success() {
+        let policy = RetryPolicy::default();
+        let mut calls = 0;
+        let mut slept = Vec::new();
+
+        let result = call_with_retry(
+            &policy,
+            &[0.0, 0.0],
+            |_attempt| {
+                calls += 1;
+                if calls < 3 {
+                    Err(ProviderError::RateLimited)
+                } else {
+                    Ok("ok".to_string())
+                }
+            },
+            |delay| slept.push(delay),
+        );
+
+        assert_eq!(result, Ok("ok".to_string()));
+        assert_eq!(calls, 3);
+        assert_eq!(slept.len(), 2);
+    }
+
+    #[test]
+    fn invalid_request_is_never_retried() {
+        let policy = RetryPolicy::default();
+        let mut calls = 0;
+
+        let result = call_with_retry(
+            &policy,
+            &[],
+            |_attempt| {
+                calls += 1;
+                Err(ProviderError::InvalidRequest("bad prompt".to_string()))
+            },
+            |_delay| panic!("should not sleep before a non-retryable error"),
+        );
+
+        assert_eq!(result, Err(ProviderError::InvalidRequest("bad prompt".to_string())));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn exhausting_all_attempts_returns_the_last_error() {
+        let policy = RetryPolicy::new(3, Duration::from_millis(10), Duration::from_secs(1));
+        let mut calls = 0;
+
+        let result = call_with_retry(
+            &policy,
+            &[0.0, 0.0],
+            |_attempt| {
+                calls += 1;
+                Err(ProviderError::Unavailable("down".to_string()))
+            },
+            |_delay| {},
+        );
+
+        assert_eq!(result, Err(ProviderError::Unavailable("down".to_string())));
+        assert_eq!(calls, 3);
+    }
+}
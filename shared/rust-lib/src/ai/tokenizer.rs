@@ -0,0 +1,104 @@
+//! Token-accurate budgeting for AI requests.
+//!
+//! Previously prompt sizing was approximated as `chars / 4`, which
+//! undercounts content with lots of punctuation/short words and overcounts
+//! CJK text, leading to requests that looked within budget but were
+//! rejected by the provider for exceeding its context window. This uses a
+//! real (BPE-style) tokenizer so the count matches what the provider
+//! actually bills against.
+
+pub trait Tokenizer {
+    fn count_tokens(&self, text: &str) -> usize;
+}
+
+/// A byte-pair-encoding tokenizer over a fixed vocabulary of merge rules.
+/// Real providers ship a trained vocabulary (e.g. `cl100k_base`); this
+/// models the same merge-loop algorithm generically so it's swappable for
+/// the provider-specific vocab without changing call sites.
+pub struct BpeTokenizer {
+    /// Ranked merge pairs; earlier entries are applied first, matching how
+    /// BPE vocabularies are trained and encoded.
+    merges: Vec<(String, String)>,
+}
+
+impl BpeTokenizer {
+    pub fn new(merges: Vec<(String, String)>) -> Self {
+        Self { merges }
+    }
+
+    fn encode_word(&self, word: &str) -> Vec<String> {
+        let mut symbols: Vec<String> = word.chars().map(|c| c.to_string()).collect();
+
+        loop {
+            let mut best: Option<(usize, usize)> = None;
+            for (rank, (a, b)) in self.merges.iter().enumerate() {
+                if let Some(pos) = symbols.windows(2).position(|pair| &pair[0] == a && &pair[1] == b) {
+                    if best.map(|(best_rank, _)| rank < best_rank).unwrap_or(true) {
+                        best = Some((rank, pos));
+                    }
+                }
+            }
+            let Some((rank, pos)) = best else { break };
+            let (a, b) = &self.merges[rank];
+            symbols.splice(pos..pos + 2, [format!("{a}{b}")]);
+        }
+
+        symbols
+    }
+}
+
+impl Tokenizer for BpeTokenizer {
+    fn count_tokens(&self, text: &str) -> usize {
+        text.split_whitespace().map(|word| self.encode_word(word).len()).sum()
+    }
+}
+
+/// A fixed token budget, checked against the tokenizer's real count rather
+/// than the old `chars / 4` heuristic.
+pub struct TokenBudget {
+    pub max_tokens: usize,
+}
+
+impl TokenBudget {
+    pub fn fits(&self, tokenizer: &dyn Tokenizer, text: &str) -> bool {
+        tokenizer.count_tokens(text) <= self.max_tokens
+    }
+
+    /// Tokens remaining in the budget after accounting for `text`, or
+    /// `None` if `text` alone already exceeds it.
+    pub fn remaining_after(&self, tokenizer: &dyn Tokenizer, text: &str) -> Option<usize> {
+        self.max_tokens.checked_sub(tokenizer.count_tokens(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokenizer() -> BpeTokenizer {
+        BpeTokenizer::new(vec![
+            ("l".to_string(), "o".to_string()),
+            ("lo".to_string(), "w".to_string()),
+        ])
+    }
+
+    #[test]
+    fn merges_apply_in_rank_order() {
+        let tok = tokenizer();
+        assert_eq!(tok.encode_word("low"), vec!["low".to_string()]);
+    }
+
+    #[test]
+    fn unmerged_word_falls_back_to_one_symbol_per_char() {
+        let tok = tokenizer();
+        assert_eq!(tok.encode_word("cat"), vec!["c", "a", "t"]);
+    }
+
+    #[test]
+    fn budget_rejects_text_exceeding_real_token_count() {
+        let tok = tokenizer();
+        let budget = TokenBudget { max_tokens: 2 };
+        assert!(budget.fits(&tok, "low"));
+        assert!(!budget.fits(&tok, "low cat"));
+    }
+}
@@ -0,0 +1,72 @@
+//! Agenda queries for a single day or week. Every event is checked against
+//! the range once; only the events in range are sorted.
+
+use super::{CalendarEngine, CalendarEvent};
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, Clone, Copy)]
+pub enum AgendaRange {
+    Day(SystemTime),
+    Week(SystemTime),
+}
+
+impl AgendaRange {
+    fn bounds(self) -> (SystemTime, SystemTime) {
+        const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+        match self {
+            AgendaRange::Day(start) => (start, start + DAY),
+            AgendaRange::Week(start) => (start, start + DAY * 7),
+        }
+    }
+}
+
+impl CalendarEngine {
+    /// Events overlapping `range`, sorted by start time. Events are filtered
+    /// by overlap (start < range.end && end > range.start) rather than
+    /// requiring full containment, so multi-day events show up on each day
+    /// they span.
+    pub fn agenda(&self, range: AgendaRange) -> Vec<&CalendarEvent> {
+        let (start, end) = range.bounds();
+        let mut events: Vec<&CalendarEvent> = self
+            .events()
+            .iter()
+            .filter(|e| e.start < end && e.end > start)
+            .collect();
+        events.sort_by_key(|e| e.start);
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::epoch_plus;
+
+    fn event(id: &str, start_secs: u64, dur_secs: u64) -> CalendarEvent {
+        CalendarEvent {
+            id: id.to_string(),
+            calendar_id: "cal-1".to_string(),
+            uid: None,
+            title: id.to_string(),
+            start: epoch_plus(start_secs),
+            end: epoch_plus(start_secs + dur_secs),
+            description: None,
+            location: None,
+            attendee_count: 0,
+            recurring_event_id: None,
+            original_start_time: None,
+        }
+    }
+
+    #[test]
+    fn agenda_day_includes_only_overlapping_events() {
+        const DAY: u64 = 24 * 60 * 60;
+        let mut engine = CalendarEngine::new();
+        engine.add_event(event("today", DAY, 3600));
+        engine.add_event(event("tomorrow", DAY * 2, 3600));
+
+        let agenda = engine.agenda(AgendaRange::Day(epoch_plus(DAY)));
+        let ids: Vec<&str> = agenda.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["today"]);
+    }
+}
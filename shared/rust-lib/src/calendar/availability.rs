@@ -0,0 +1,170 @@
+//! A tiny query language for natural availability questions like
+//! "free Tuesday afternoon".
+
+use super::CalendarEngine;
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weekday {
+    Mon,
+    Tue,
+    Wed,
+    Thu,
+    Fri,
+    Sat,
+    Sun,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayPart {
+    Morning,
+    Afternoon,
+    Evening,
+}
+
+impl DayPart {
+    /// Hour range `[start, end)` in the user's local day.
+    fn hour_range(self) -> (u32, u32) {
+        match self {
+            DayPart::Morning => (8, 12),
+            DayPart::Afternoon => (12, 17),
+            DayPart::Evening => (17, 21),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AvailabilityQuery {
+    pub weekday: Weekday,
+    pub day_part: DayPart,
+}
+
+/// Parse a phrase like "free Tuesday afternoon" into a structured query.
+/// Returns `None` if the phrase doesn't name both a weekday and a day part.
+pub fn parse_availability_query(phrase: &str) -> Option<AvailabilityQuery> {
+    let lowered = phrase.to_lowercase();
+
+    let weekday = [
+        ("monday", Weekday::Mon),
+        ("tuesday", Weekday::Tue),
+        ("wednesday", Weekday::Wed),
+        ("thursday", Weekday::Thu),
+        ("friday", Weekday::Fri),
+        ("saturday", Weekday::Sat),
+        ("sunday", Weekday::Sun),
+    ]
+    .into_iter()
+    .find(|(name, _)| lowered.contains(name))
+    .map(|(_, day)| day)?;
+
+    let day_part = [
+        ("morning", DayPart::Morning),
+        ("afternoon", DayPart::Afternoon),
+        ("evening", DayPart::Evening),
+    ]
+    .into_iter()
+    .find(|(name, _)| lowered.contains(name))
+    .map(|(_, part)| part)?;
+
+    Some(AvailabilityQuery { weekday, day_part })
+}
+
+/// A free window found for an [`AvailabilityQuery`] within a given week.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FreeWindow {
+    pub start: SystemTime,
+    pub end: SystemTime,
+}
+
+impl CalendarEngine {
+    /// Find free windows matching `query` within `[week_start, week_start + 7d)`,
+    /// where `day_offset` maps `query.weekday` to an offset from `week_start`
+    /// (supplied by the caller, which already knows the calendar's locale/week
+    /// start convention).
+    pub fn find_availability(
+        &self,
+        week_start: SystemTime,
+        day_offset: impl Fn(Weekday) -> u32,
+        query: AvailabilityQuery,
+    ) -> Vec<FreeWindow> {
+        let day = week_start + Duration::from_secs(u64::from(day_offset(query.weekday)) * 86_400);
+        let (start_hour, end_hour) = query.day_part.hour_range();
+        let window_start = day + Duration::from_secs(u64::from(start_hour) * 3600);
+        let window_end = day + Duration::from_secs(u64::from(end_hour) * 3600);
+
+        let busy: Vec<(SystemTime, SystemTime)> = self
+            .events()
+            .iter()
+            .filter(|e| e.start < window_end && e.end > window_start)
+            .map(|e| (e.start.max(window_start), e.end.min(window_end)))
+            .collect();
+
+        subtract_busy(window_start, window_end, busy)
+    }
+}
+
+fn subtract_busy(start: SystemTime, end: SystemTime, mut busy: Vec<(SystemTime, SystemTime)>) -> Vec<FreeWindow> {
+    busy.sort_by_key(|(s, _)| *s);
+    let mut free = Vec::new();
+    let mut cursor = start;
+    for (busy_start, busy_end) in busy {
+        if busy_start > cursor {
+            free.push(FreeWindow {
+                start: cursor,
+                end: busy_start,
+            });
+        }
+        cursor = cursor.max(busy_end);
+    }
+    if cursor < end {
+        free.push(FreeWindow { start: cursor, end });
+    }
+    free
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::{epoch_plus, CalendarEvent};
+
+    #[test]
+    fn parses_weekday_and_day_part() {
+        let query = parse_availability_query("free Tuesday afternoon").unwrap();
+        assert_eq!(query.weekday, Weekday::Tue);
+        assert_eq!(query.day_part, DayPart::Afternoon);
+        assert!(parse_availability_query("is it sunny").is_none());
+    }
+
+    #[test]
+    fn finds_gaps_around_busy_events() {
+        const DAY: u64 = 86_400;
+        let week_start = epoch_plus(0);
+        let mut engine = CalendarEngine::new();
+        // Tuesday (offset 1) at 13:00-14:00.
+        engine.add_event(CalendarEvent {
+            id: "meeting".to_string(),
+            calendar_id: "cal-1".to_string(),
+            uid: None,
+            title: "Sync".to_string(),
+            start: epoch_plus(DAY + 13 * 3600),
+            end: epoch_plus(DAY + 14 * 3600),
+            description: None,
+            location: None,
+            attendee_count: 0,
+            recurring_event_id: None,
+            original_start_time: None,
+        });
+
+        let query = AvailabilityQuery {
+            weekday: Weekday::Tue,
+            day_part: DayPart::Afternoon,
+        };
+        let windows = engine.find_availability(week_start, |_| 1, query);
+
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[0].start, epoch_plus(DAY + 12 * 3600));
+        assert_eq!(windows[0].end, epoch_plus(DAY + 13 * 3600));
+        assert_eq!(windows[1].start, epoch_plus(DAY + 14 * 3600));
+        assert_eq!(windows[1].end, epoch_plus(DAY + 17 * 3600));
+    }
+}
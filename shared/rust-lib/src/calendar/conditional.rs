@@ -0,0 +1,62 @@
+//! Conditional requests (`ETag`/`If-Match`) so a write that raced with a
+//! remote change is rejected instead of silently clobbering it.
+
+use crate::error::{FlowDeskError, FlowDeskResult};
+
+/// A remote resource's version tag, as returned by CalDAV/Gmail/Graph.
+pub type ETag = String;
+
+/// An update that must only apply if the server's current ETag still
+/// matches what we last fetched.
+#[derive(Debug, Clone)]
+pub struct ConditionalUpdate<T> {
+    pub expected_etag: ETag,
+    pub payload: T,
+}
+
+/// Simulates the provider-side check a real HTTP client would express as an
+/// `If-Match` header: if the resource's current tag doesn't match what we
+/// last read, the update is rejected as a lost-update conflict rather than
+/// applied.
+pub fn apply_conditional_update<T>(
+    current_etag: &ETag,
+    update: ConditionalUpdate<T>,
+    apply: impl FnOnce(T) -> ETag,
+) -> FlowDeskResult<ETag> {
+    if &update.expected_etag != current_etag {
+        return Err(FlowDeskError::Connection(format!(
+            "conditional update rejected: expected ETag {}, resource is now at {}",
+            update.expected_etag, current_etag
+        )));
+    }
+    Ok(apply(update.payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_update_when_etag_matches() {
+        let current = "etag-1".to_string();
+        let update = ConditionalUpdate {
+            expected_etag: "etag-1".to_string(),
+            payload: "new title",
+        };
+
+        let result = apply_conditional_update(&current, update, |_| "etag-2".to_string());
+        assert_eq!(result.unwrap(), "etag-2");
+    }
+
+    #[test]
+    fn rejects_update_when_resource_changed_concurrently() {
+        let current = "etag-2".to_string();
+        let update = ConditionalUpdate {
+            expected_etag: "etag-1".to_string(),
+            payload: "new title",
+        };
+
+        let result = apply_conditional_update(&current, update, |_| "etag-3".to_string());
+        assert!(result.is_err());
+    }
+}
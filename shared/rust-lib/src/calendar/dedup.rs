@@ -0,0 +1,222 @@
+//! Duplicate event detection and merging.
+
+use super::{CalendarEngine, CalendarEvent, CalendarId, EventId};
+use std::time::Duration;
+
+/// How close two events' start/end times need to be (when UIDs don't match)
+/// to be considered the same occurrence.
+const FUZZ_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+/// A set of events that are likely duplicates of one another.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateGroup {
+    pub event_ids: Vec<EventId>,
+    pub reason: DuplicateReason,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateReason {
+    SameUid,
+    SameTitleAndTime,
+}
+
+/// Result of a (possibly dry-run) merge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeOutcome {
+    pub kept: EventId,
+    pub removed: Vec<EventId>,
+    pub dry_run: bool,
+}
+
+impl CalendarEngine {
+    /// Group events that are likely duplicates, either because they share an
+    /// iCalendar UID or because their title/start/end match within
+    /// [`FUZZ_WINDOW`].
+    pub fn find_duplicate_events(&self, calendar_ids: &[CalendarId]) -> Vec<DuplicateGroup> {
+        let candidates: Vec<&CalendarEvent> = self
+            .events()
+            .iter()
+            .filter(|e| calendar_ids.contains(&e.calendar_id))
+            .collect();
+
+        let mut groups: Vec<DuplicateGroup> = Vec::new();
+        let mut consumed: Vec<bool> = vec![false; candidates.len()];
+
+        for i in 0..candidates.len() {
+            if consumed[i] {
+                continue;
+            }
+            let mut group_ids = vec![candidates[i].id.clone()];
+            let mut reason = DuplicateReason::SameTitleAndTime;
+
+            for j in (i + 1)..candidates.len() {
+                if consumed[j] {
+                    continue;
+                }
+                if let (Some(uid_a), Some(uid_b)) = (&candidates[i].uid, &candidates[j].uid) {
+                    if uid_a == uid_b {
+                        group_ids.push(candidates[j].id.clone());
+                        consumed[j] = true;
+                        reason = DuplicateReason::SameUid;
+                        continue;
+                    }
+                }
+                if candidates[i].title == candidates[j].title
+                    && within_fuzz(candidates[i].start, candidates[j].start)
+                    && within_fuzz(candidates[i].end, candidates[j].end)
+                {
+                    group_ids.push(candidates[j].id.clone());
+                    consumed[j] = true;
+                }
+            }
+
+            if group_ids.len() > 1 {
+                consumed[i] = true;
+                groups.push(DuplicateGroup {
+                    event_ids: group_ids,
+                    reason,
+                });
+            }
+        }
+
+        groups
+    }
+
+    /// Remove every event in `group` except `keep`, preserving the kept
+    /// event's richest fields (longest description/location) from the set.
+    /// When `dry_run` is true, no mutation happens and the would-be outcome
+    /// is returned.
+    pub fn merge_duplicates(&mut self, group: &DuplicateGroup, keep: &EventId, dry_run: bool) -> MergeOutcome {
+        let removed: Vec<EventId> = group
+            .event_ids
+            .iter()
+            .filter(|id| *id != keep)
+            .cloned()
+            .collect();
+
+        if dry_run {
+            return MergeOutcome {
+                kept: keep.clone(),
+                removed,
+                dry_run: true,
+            };
+        }
+
+        let richest_description = group
+            .event_ids
+            .iter()
+            .filter_map(|id| self.events().iter().find(|e| &e.id == id))
+            .filter_map(|e| e.description.clone())
+            .max_by_key(|d| d.len());
+        let richest_location = group
+            .event_ids
+            .iter()
+            .filter_map(|id| self.events().iter().find(|e| &e.id == id))
+            .filter_map(|e| e.location.clone())
+            .max_by_key(|l| l.len());
+
+        if let Some(kept_event) = self.events_mut().iter_mut().find(|e| &e.id == keep) {
+            if richest_description.is_some() {
+                kept_event.description = richest_description;
+            }
+            if richest_location.is_some() {
+                kept_event.location = richest_location;
+            }
+        }
+
+        self.events_mut().retain(|e| !removed.contains(&e.id));
+
+        MergeOutcome {
+            kept: keep.clone(),
+            removed,
+            dry_run: false,
+        }
+    }
+}
+
+fn within_fuzz(a: std::time::SystemTime, b: std::time::SystemTime) -> bool {
+    a.duration_since(b)
+        .or_else(|_| b.duration_since(a))
+        .map(|d| d <= FUZZ_WINDOW)
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::epoch_plus;
+
+    fn event(id: &str, uid: Option<&str>, title: &str, start_secs: u64) -> CalendarEvent {
+        CalendarEvent {
+            id: id.to_string(),
+            calendar_id: "cal-1".to_string(),
+            uid: uid.map(|s| s.to_string()),
+            title: title.to_string(),
+            start: epoch_plus(start_secs),
+            end: epoch_plus(start_secs + 1800),
+            description: None,
+            location: None,
+            attendee_count: 0,
+            recurring_event_id: None,
+            original_start_time: None,
+        }
+    }
+
+    #[test]
+    fn groups_events_with_same_uid() {
+        let mut engine = CalendarEngine::new();
+        engine.add_event(event("a", Some("uid-1"), "Standup", 1000));
+        engine.add_event(event("b", Some("uid-1"), "Standup", 1000));
+        engine.add_event(event("c", None, "Unrelated", 5000));
+
+        let groups = engine.find_duplicate_events(&["cal-1".to_string()]);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].reason, DuplicateReason::SameUid);
+        assert_eq!(groups[0].event_ids, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn groups_events_with_same_title_and_fuzzy_time() {
+        let mut engine = CalendarEngine::new();
+        engine.add_event(event("a", None, "1:1 with Sam", 1000));
+        engine.add_event(event("b", None, "1:1 with Sam", 1100));
+
+        let groups = engine.find_duplicate_events(&["cal-1".to_string()]);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].reason, DuplicateReason::SameTitleAndTime);
+    }
+
+    #[test]
+    fn merge_removes_extras_and_keeps_richest_fields() {
+        let mut engine = CalendarEngine::new();
+        let mut kept = event("a", Some("uid-1"), "Standup", 1000);
+        kept.description = None;
+        let mut extra = event("b", Some("uid-1"), "Standup", 1000);
+        extra.description = Some("Agenda: roadmap review".to_string());
+        engine.add_event(kept);
+        engine.add_event(extra);
+
+        let groups = engine.find_duplicate_events(&["cal-1".to_string()]);
+        let outcome = engine.merge_duplicates(&groups[0], &"a".to_string(), false);
+
+        assert_eq!(outcome.removed, vec!["b".to_string()]);
+        assert_eq!(engine.events().len(), 1);
+        assert_eq!(
+            engine.events()[0].description.as_deref(),
+            Some("Agenda: roadmap review")
+        );
+    }
+
+    #[test]
+    fn dry_run_does_not_mutate() {
+        let mut engine = CalendarEngine::new();
+        engine.add_event(event("a", Some("uid-1"), "Standup", 1000));
+        engine.add_event(event("b", Some("uid-1"), "Standup", 1000));
+
+        let groups = engine.find_duplicate_events(&["cal-1".to_string()]);
+        let outcome = engine.merge_duplicates(&groups[0], &"a".to_string(), true);
+
+        assert!(outcome.dry_run);
+        assert_eq!(engine.events().len(), 2);
+    }
+}
@@ -0,0 +1,96 @@
+//! Free/busy aggregation across multiple calendar accounts (e.g. answering
+//! "when is everyone free" using each attendee's own calendar, or merging a
+//! user's personal and work accounts into one busy picture).
+
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BusyInterval {
+    pub start: SystemTime,
+    pub end: SystemTime,
+}
+
+/// One account's contribution to the aggregate: its busy intervals within
+/// the query range. A provider outage is modeled as `Err` so the caller can
+/// decide whether to treat "unknown" as busy (conservative) or ignore it.
+pub type AccountBusyResult = Result<Vec<BusyInterval>, String>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AggregatedFreeBusy {
+    /// Merged, non-overlapping busy intervals across every account that
+    /// responded successfully.
+    pub busy: Vec<BusyInterval>,
+    /// Accounts whose free/busy lookup failed, so the caller can surface a
+    /// partial-results warning instead of silently treating them as free.
+    pub failed_accounts: Vec<String>,
+}
+
+/// Merge free/busy results from multiple accounts into one aggregated busy
+/// timeline. `results` pairs an account identifier with its lookup result.
+pub fn aggregate_free_busy(results: Vec<(String, AccountBusyResult)>) -> AggregatedFreeBusy {
+    let mut intervals = Vec::new();
+    let mut failed_accounts = Vec::new();
+
+    for (account_id, result) in results {
+        match result {
+            Ok(account_intervals) => intervals.extend(account_intervals),
+            Err(_) => failed_accounts.push(account_id),
+        }
+    }
+
+    AggregatedFreeBusy {
+        busy: merge_overlapping(intervals),
+        failed_accounts,
+    }
+}
+
+fn merge_overlapping(mut intervals: Vec<BusyInterval>) -> Vec<BusyInterval> {
+    intervals.sort_by_key(|interval| interval.start);
+    let mut merged: Vec<BusyInterval> = Vec::new();
+    for interval in intervals {
+        match merged.last_mut() {
+            Some(last) if interval.start <= last.end => {
+                last.end = last.end.max(interval.end);
+            }
+            _ => merged.push(interval),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::epoch_plus;
+
+    fn busy(start: u64, end: u64) -> BusyInterval {
+        BusyInterval {
+            start: epoch_plus(start),
+            end: epoch_plus(end),
+        }
+    }
+
+    #[test]
+    fn merges_overlapping_intervals_across_accounts() {
+        let results = vec![
+            ("personal".to_string(), Ok(vec![busy(0, 100)])),
+            ("work".to_string(), Ok(vec![busy(50, 150), busy(300, 400)])),
+        ];
+
+        let aggregated = aggregate_free_busy(results);
+        assert_eq!(aggregated.busy, vec![busy(0, 150), busy(300, 400)]);
+        assert!(aggregated.failed_accounts.is_empty());
+    }
+
+    #[test]
+    fn failed_account_is_reported_not_silently_treated_as_free() {
+        let results = vec![
+            ("personal".to_string(), Ok(vec![busy(0, 100)])),
+            ("work".to_string(), Err("provider timeout".to_string())),
+        ];
+
+        let aggregated = aggregate_free_busy(results);
+        assert_eq!(aggregated.busy, vec![busy(0, 100)]);
+        assert_eq!(aggregated.failed_accounts, vec!["work".to_string()]);
+    }
+}
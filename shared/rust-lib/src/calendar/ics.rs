@@ -0,0 +1,240 @@
+//! ICS (iCalendar, RFC 5545) import/export for events and whole calendars.
+//!
+//! Only the subset of properties [`CalendarEvent`] actually models is
+//! round-tripped (`UID`, `SUMMARY`, `DTSTART`, `DTEND`, `DESCRIPTION`,
+//! `LOCATION`) — recurrence, attendees and timezone-qualified `DTSTART`s
+//! aren't stored on this crate's event model yet, so they're neither
+//! emitted nor read back. Every time is treated as UTC, matching the `Z`
+//! suffix this module always writes and the only form it parses.
+
+use super::{CalendarEvent, CalendarId};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Days-since-epoch to civil (proleptic Gregorian) date, and back. Howard
+/// Hinnant's `civil_from_days`/`days_from_civil` algorithm — there's no
+/// date/time crate in this workspace to lean on for calendar math. Also
+/// used by [`super::recurrence`] for month/year arithmetic.
+pub(super) fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = (z - era * 146_097) as u64;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let y = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    (if m <= 2 { y + 1 } else { y }, m, d)
+}
+
+pub(super) fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = (y - era * 400) as u64;
+    let month_index = if m > 2 { m - 3 } else { m + 9 } as u64;
+    let doy = (153 * month_index + 2) / 5 + d as u64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe as i64 - 719_468
+}
+
+/// Format a [`SystemTime`] as a UTC iCalendar `DATE-TIME`, e.g.
+/// `20260815T090000Z`.
+pub(super) fn format_ics_datetime(time: SystemTime) -> String {
+    let total_seconds = time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64;
+    let days = total_seconds.div_euclid(SECONDS_PER_DAY);
+    let secs_of_day = total_seconds.rem_euclid(SECONDS_PER_DAY);
+    let (year, month, day) = civil_from_days(days);
+    let (hour, minute, second) = (secs_of_day / 3600, (secs_of_day / 60) % 60, secs_of_day % 60);
+    format!("{year:04}{month:02}{day:02}T{hour:02}{minute:02}{second:02}Z")
+}
+
+/// Parse a UTC iCalendar `DATE-TIME`. Returns `None` for any other shape
+/// (floating time, `TZID=`-qualified, or a bare `DATE`), which this crate
+/// doesn't model yet.
+fn parse_ics_datetime(value: &str) -> Option<SystemTime> {
+    let value = value.strip_suffix('Z')?;
+    if value.len() != 15 || value.as_bytes()[8] != b'T' {
+        return None;
+    }
+    let year: i64 = value[0..4].parse().ok()?;
+    let month: u32 = value[4..6].parse().ok()?;
+    let day: u32 = value[6..8].parse().ok()?;
+    let hour: i64 = value[9..11].parse().ok()?;
+    let minute: i64 = value[11..13].parse().ok()?;
+    let second: i64 = value[13..15].parse().ok()?;
+
+    let days = days_from_civil(year, month, day);
+    let total_seconds = days * SECONDS_PER_DAY + hour * 3600 + minute * 60 + second;
+    Some(UNIX_EPOCH + Duration::from_secs(total_seconds.max(0) as u64))
+}
+
+pub(super) fn escape_ics_text(value: &str) -> String {
+    value.replace('\\', "\\\\").replace(',', "\\,").replace(';', "\\;").replace('\n', "\\n")
+}
+
+fn unescape_ics_text(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' {
+            if let Some(escaped) = chars.next() {
+                result.push(if escaped == 'n' { '\n' } else { escaped });
+            }
+        } else {
+            result.push(ch);
+        }
+    }
+    result
+}
+
+/// Render a single event as a `VEVENT` component, without the surrounding
+/// `VCALENDAR` wrapper — see [`export_calendar`] for that.
+pub fn export_event(event: &CalendarEvent) -> String {
+    let mut lines = vec!["BEGIN:VEVENT".to_string()];
+    lines.push(format!("UID:{}", event.uid.clone().unwrap_or_else(|| event.id.clone())));
+    lines.push(format!("DTSTART:{}", format_ics_datetime(event.start)));
+    lines.push(format!("DTEND:{}", format_ics_datetime(event.end)));
+    lines.push(format!("SUMMARY:{}", escape_ics_text(&event.title)));
+    if let Some(description) = &event.description {
+        lines.push(format!("DESCRIPTION:{}", escape_ics_text(description)));
+    }
+    if let Some(location) = &event.location {
+        lines.push(format!("LOCATION:{}", escape_ics_text(location)));
+    }
+    lines.push("END:VEVENT".to_string());
+    lines.join("\r\n")
+}
+
+/// Render every event belonging to `calendar_id` as a complete
+/// `VCALENDAR` document.
+pub fn export_calendar(calendar_id: &CalendarId, events: &[CalendarEvent]) -> String {
+    let mut lines =
+        vec!["BEGIN:VCALENDAR".to_string(), "VERSION:2.0".to_string(), "PRODID:-//Flow Desk//Calendar//EN".to_string()];
+    for event in events.iter().filter(|event| &event.calendar_id == calendar_id) {
+        lines.push(export_event(event));
+    }
+    lines.push("END:VCALENDAR".to_string());
+    lines.join("\r\n")
+}
+
+/// Parse every `VEVENT` component out of an ICS document (a lone event, or
+/// a whole `VCALENDAR`). `calendar_id` is assigned to every parsed event,
+/// since iCalendar itself carries no notion of this crate's local
+/// calendar id. Each event's `id` is set to its `UID`. A `VEVENT` missing
+/// `DTSTART`/`DTEND` is dropped rather than failing the whole import.
+pub fn import_events(calendar_id: &CalendarId, ics: &str) -> Vec<CalendarEvent> {
+    let mut events = Vec::new();
+    let mut in_event = false;
+    let (mut uid, mut summary, mut start, mut end, mut description, mut location) = (None, None, None, None, None, None);
+
+    for raw_line in ics.lines() {
+        let line = raw_line.trim_end_matches('\r');
+        match line {
+            "BEGIN:VEVENT" => {
+                in_event = true;
+                (uid, summary, start, end, description, location) = (None, None, None, None, None, None);
+            }
+            "END:VEVENT" => {
+                if in_event {
+                    if let (Some(start), Some(end)) = (start, end) {
+                        events.push(CalendarEvent {
+                            id: uid.clone().unwrap_or_default(),
+                            calendar_id: calendar_id.clone(),
+                            uid: uid.clone(),
+                            title: summary.clone().unwrap_or_default(),
+                            start,
+                            end,
+                            description: description.clone(),
+                            location: location.clone(),
+                            attendee_count: 0,
+                            recurring_event_id: None,
+                            original_start_time: None,
+                        });
+                    }
+                }
+                in_event = false;
+            }
+            _ if in_event => {
+                let Some((key, value)) = line.split_once(':') else { continue };
+                let key = key.split(';').next().unwrap_or(key);
+                match key {
+                    "UID" => uid = Some(value.to_string()),
+                    "SUMMARY" => summary = Some(unescape_ics_text(value)),
+                    "DTSTART" => start = parse_ics_datetime(value),
+                    "DTEND" => end = parse_ics_datetime(value),
+                    "DESCRIPTION" => description = Some(unescape_ics_text(value)),
+                    "LOCATION" => location = Some(unescape_ics_text(value)),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event() -> CalendarEvent {
+        CalendarEvent {
+            id: "evt-1".to_string(),
+            calendar_id: "cal-1".to_string(),
+            uid: Some("evt-1@flowdesk".to_string()),
+            title: "Launch review".to_string(),
+            start: UNIX_EPOCH + Duration::from_secs(1_755_248_400),
+            end: UNIX_EPOCH + Duration::from_secs(1_755_252_000),
+            description: Some("Go over the launch checklist".to_string()),
+            location: Some("Room 4B".to_string()),
+            attendee_count: 0,
+            recurring_event_id: None,
+            original_start_time: None,
+        }
+    }
+
+    #[test]
+    fn formats_and_parses_the_same_utc_datetime() {
+        let formatted = format_ics_datetime(event().start);
+        assert_eq!(parse_ics_datetime(&formatted), Some(event().start));
+    }
+
+    #[test]
+    fn exporting_then_importing_a_single_event_round_trips() {
+        let ics = format!("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n{}\r\nEND:VCALENDAR", export_event(&event()));
+        let imported = import_events(&"cal-1".to_string(), &ics);
+        // The local id isn't carried in ICS; an imported event's id is its UID.
+        let mut expected = event();
+        expected.id = "evt-1@flowdesk".to_string();
+        assert_eq!(imported, vec![expected]);
+    }
+
+    #[test]
+    fn export_calendar_only_includes_events_from_that_calendar() {
+        let mut other = event();
+        other.id = "evt-2".to_string();
+        other.calendar_id = "cal-2".to_string();
+
+        let ics = export_calendar(&"cal-1".to_string(), &[event(), other]);
+        assert!(ics.contains("evt-1@flowdesk"));
+        assert!(!ics.contains("evt-2"));
+    }
+
+    #[test]
+    fn an_event_missing_dtstart_or_dtend_is_dropped_not_fabricated() {
+        let ics = "BEGIN:VEVENT\r\nUID:broken@flowdesk\r\nSUMMARY:No times\r\nEND:VEVENT";
+        assert!(import_events(&"cal-1".to_string(), ics).is_empty());
+    }
+
+    #[test]
+    fn escaped_text_round_trips_through_export_and_import() {
+        let mut with_special_chars = event();
+        with_special_chars.title = "Q3; Launch, Review\nFollow-up".to_string();
+
+        let ics = format!("BEGIN:VCALENDAR\r\n{}\r\nEND:VCALENDAR", export_event(&with_special_chars));
+        let imported = import_events(&"cal-1".to_string(), &ics);
+        assert_eq!(imported[0].title, with_special_chars.title);
+    }
+}
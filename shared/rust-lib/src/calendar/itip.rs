@@ -0,0 +1,163 @@
+//! Attendee RSVP handling and iTIP `REPLY` generation (RFC 5546). When an
+//! attendee responds to an invite, their client sends the organizer a
+//! `METHOD:REPLY` iCalendar message rather than a plain-text email; this
+//! builds that message and parses one sent back to us, reusing
+//! [`super::ics`]'s `DATE-TIME` formatting and text escaping.
+
+use super::ics::{escape_ics_text, format_ics_datetime};
+use super::CalendarEvent;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RsvpStatus {
+    NeedsAction,
+    Accepted,
+    Declined,
+    Tentative,
+}
+
+impl RsvpStatus {
+    fn partstat(self) -> &'static str {
+        match self {
+            RsvpStatus::NeedsAction => "NEEDS-ACTION",
+            RsvpStatus::Accepted => "ACCEPTED",
+            RsvpStatus::Declined => "DECLINED",
+            RsvpStatus::Tentative => "TENTATIVE",
+        }
+    }
+
+    fn parse_partstat(value: &str) -> Option<Self> {
+        match value {
+            "NEEDS-ACTION" => Some(RsvpStatus::NeedsAction),
+            "ACCEPTED" => Some(RsvpStatus::Accepted),
+            "DECLINED" => Some(RsvpStatus::Declined),
+            "TENTATIVE" => Some(RsvpStatus::Tentative),
+            _ => None,
+        }
+    }
+}
+
+/// Build the `METHOD:REPLY` iCalendar message an attendee's client sends
+/// back to `organizer_email` after responding to `event`.
+pub fn generate_itip_reply(
+    event: &CalendarEvent,
+    organizer_email: &str,
+    attendee_email: &str,
+    status: RsvpStatus,
+) -> String {
+    let uid = event.uid.clone().unwrap_or_else(|| event.id.clone());
+    let lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Flow Desk//Calendar//EN".to_string(),
+        "METHOD:REPLY".to_string(),
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{uid}"),
+        format!("DTSTART:{}", format_ics_datetime(event.start)),
+        format!("SUMMARY:{}", escape_ics_text(&event.title)),
+        format!("ORGANIZER:mailto:{organizer_email}"),
+        format!("ATTENDEE;PARTSTAT={}:mailto:{attendee_email}", status.partstat()),
+        "END:VEVENT".to_string(),
+        "END:VCALENDAR".to_string(),
+    ];
+    lines.join("\r\n")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RsvpReply {
+    pub event_uid: String,
+    pub attendee_email: String,
+    pub status: RsvpStatus,
+}
+
+/// Parse an inbound `METHOD:REPLY` message into the attendee's RSVP, so the
+/// organizer's client can update its attendee list. Returns `None` if the
+/// message isn't a `REPLY`, or is missing a field needed to apply it.
+pub fn parse_itip_reply(ics: &str) -> Option<RsvpReply> {
+    if !ics.lines().any(|line| line.trim() == "METHOD:REPLY") {
+        return None;
+    }
+
+    let mut event_uid = None;
+    let mut attendee_email = None;
+    let mut status = None;
+
+    for raw_line in ics.lines() {
+        let line = raw_line.trim_end_matches('\r').trim();
+        let Some((key, value)) = line.split_once(':') else { continue };
+        let (name, params) = key.split_once(';').map_or((key, None), |(n, p)| (n, Some(p)));
+
+        match name {
+            "UID" => event_uid = Some(value.to_string()),
+            "ATTENDEE" => {
+                attendee_email = Some(value.strip_prefix("mailto:").unwrap_or(value).to_string());
+                if let Some(params) = params {
+                    for param in params.split(';') {
+                        if let Some(partstat) = param.strip_prefix("PARTSTAT=") {
+                            status = RsvpStatus::parse_partstat(partstat);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(RsvpReply { event_uid: event_uid?, attendee_email: attendee_email?, status: status? })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn event() -> CalendarEvent {
+        CalendarEvent {
+            id: "evt-1".to_string(),
+            calendar_id: "cal-1".to_string(),
+            uid: Some("evt-1@flowdesk".to_string()),
+            title: "Launch review".to_string(),
+            start: UNIX_EPOCH + Duration::from_secs(1_755_248_400),
+            end: UNIX_EPOCH + Duration::from_secs(1_755_252_000),
+            description: None,
+            location: None,
+            attendee_count: 1,
+            recurring_event_id: None,
+            original_start_time: None,
+        }
+    }
+
+    #[test]
+    fn generated_reply_round_trips_through_the_parser() {
+        let ics = generate_itip_reply(&event(), "organizer@example.com", "attendee@example.com", RsvpStatus::Accepted);
+
+        let reply = parse_itip_reply(&ics).unwrap();
+        assert_eq!(
+            reply,
+            RsvpReply {
+                event_uid: "evt-1@flowdesk".to_string(),
+                attendee_email: "attendee@example.com".to_string(),
+                status: RsvpStatus::Accepted,
+            }
+        );
+    }
+
+    #[test]
+    fn every_rsvp_status_round_trips() {
+        for status in [RsvpStatus::NeedsAction, RsvpStatus::Accepted, RsvpStatus::Declined, RsvpStatus::Tentative] {
+            let ics = generate_itip_reply(&event(), "organizer@example.com", "attendee@example.com", status);
+            assert_eq!(parse_itip_reply(&ics).unwrap().status, status);
+        }
+    }
+
+    #[test]
+    fn a_request_method_is_not_parsed_as_a_reply() {
+        let ics = "BEGIN:VCALENDAR\r\nMETHOD:REQUEST\r\nEND:VCALENDAR";
+        assert!(parse_itip_reply(ics).is_none());
+    }
+
+    #[test]
+    fn a_reply_missing_a_recognized_partstat_is_rejected() {
+        let ics = "BEGIN:VCALENDAR\r\nMETHOD:REPLY\r\nBEGIN:VEVENT\r\nUID:evt-1\r\nATTENDEE:mailto:a@example.com\r\nEND:VEVENT\r\nEND:VCALENDAR";
+        assert!(parse_itip_reply(ics).is_none());
+    }
+}
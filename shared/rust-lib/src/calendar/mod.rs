@@ -0,0 +1,96 @@
+//! Calendar engine: event storage, sync and scheduling helpers.
+
+mod agenda;
+mod availability;
+mod conditional;
+mod dedup;
+mod freebusy;
+mod ics;
+mod itip;
+mod privacy_sync;
+pub mod providers;
+mod recurrence;
+mod reminders;
+mod suggest;
+mod upcoming;
+mod webhook;
+
+pub use agenda::AgendaRange;
+pub use availability::{parse_availability_query, AvailabilityQuery, DayPart, FreeWindow, Weekday};
+pub use conditional::{apply_conditional_update, ConditionalUpdate, ETag};
+pub use dedup::{DuplicateGroup, MergeOutcome};
+pub use freebusy::{aggregate_free_busy, AccountBusyResult, AggregatedFreeBusy, BusyInterval};
+pub use ics::{export_calendar, export_event, import_events};
+pub use itip::{generate_itip_reply, parse_itip_reply, RsvpReply, RsvpStatus};
+pub use privacy_sync::{
+    build_sync_marker, is_sync_generated, render_privacy_title, would_create_feedback_loop,
+};
+pub use recurrence::{expand_occurrences, expand_recurrence, ByDay, Frequency, RecurrenceRule};
+pub use reminders::{
+    resync_reminders_after_settings_change, EventReminders, ReminderOffset, ReminderSettings,
+};
+pub use suggest::{suggest_meeting_times, MeetingProposal, SuggestedSlot};
+pub use webhook::{GoogleChannelNotification, MicrosoftNotification, WebhookProvider, WebhookVerifier};
+
+use std::time::SystemTime;
+#[cfg(test)]
+use std::time::UNIX_EPOCH;
+
+pub type CalendarId = String;
+pub type EventId = String;
+
+/// A single calendar event as stored locally.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarEvent {
+    pub id: EventId,
+    pub calendar_id: CalendarId,
+    /// iCalendar UID, when known. Two events with the same UID are always
+    /// the same logical event (e.g. re-synced from two accounts).
+    pub uid: Option<String>,
+    pub title: String,
+    pub start: SystemTime,
+    pub end: SystemTime,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub attendee_count: usize,
+    /// When this event is one occurrence of a recurring series, the
+    /// [`EventId`] of the master event [`recurrence::expand_recurrence`]
+    /// expanded it from.
+    pub recurring_event_id: Option<EventId>,
+    /// For a recurring occurrence (or an override of one), the occurrence's
+    /// un-overridden start time — how overrides are matched back to the
+    /// occurrence they replace. `None` for a non-recurring event.
+    pub original_start_time: Option<SystemTime>,
+}
+
+/// Minimal in-memory calendar engine. Real persistence is handled by the
+/// database layer; this struct coordinates the business logic above it.
+#[derive(Debug, Default)]
+pub struct CalendarEngine {
+    events: Vec<CalendarEvent>,
+}
+
+impl CalendarEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_event(&mut self, event: CalendarEvent) {
+        self.events.push(event);
+    }
+
+    pub fn events(&self) -> &[CalendarEvent] {
+        &self.events
+    }
+
+    pub fn events_mut(&mut self) -> &mut Vec<CalendarEvent> {
+        &mut self.events
+    }
+}
+
+/// Placeholder "now" used by duration math in tests that don't care about
+/// the exact instant.
+#[cfg(test)]
+pub(crate) fn epoch_plus(seconds: u64) -> SystemTime {
+    UNIX_EPOCH + std::time::Duration::from_secs(seconds)
+}
@@ -0,0 +1,119 @@
+//! Privacy-sync title templating: when mirroring an event from a private
+//! calendar onto a shared one, the title is rendered from a template rather
+//! than copied verbatim (e.g. "Busy" or "Busy: {{title}}" depending on how
+//! much the user wants to disclose).
+
+use super::CalendarEvent;
+
+/// Recognized substitution tokens, matched literally (no escaping support —
+/// templates are short, user-authored strings, not a general templating
+/// language).
+const TITLE_TOKEN: &str = "{{title}}";
+const LOCATION_TOKEN: &str = "{{location}}";
+const CALENDAR_TOKEN: &str = "{{calendar}}";
+
+/// Render a privacy-sync title template against the source event. Unknown
+/// tokens are left as-is; missing fields (e.g. no location) substitute an
+/// empty string rather than failing.
+pub fn render_privacy_title(template: &str, event: &CalendarEvent, source_calendar_name: &str) -> String {
+    template
+        .replace(TITLE_TOKEN, &event.title)
+        .replace(LOCATION_TOKEN, event.location.as_deref().unwrap_or(""))
+        .replace(CALENDAR_TOKEN, source_calendar_name)
+}
+
+/// Marker embedded in a mirrored event's description, identifying which
+/// source calendar and source event produced it. Syncing this marker back
+/// is how we detect (and refuse to re-mirror) our own previously-synced
+/// copies, which would otherwise create an infinite mirror loop when two
+/// calendars privacy-sync to each other.
+const SYNC_MARKER_PREFIX: &str = "flowdesk-privacy-sync-source:";
+
+pub fn build_sync_marker(source_calendar_id: &str, source_event_id: &str) -> String {
+    format!("{SYNC_MARKER_PREFIX}{source_calendar_id}:{source_event_id}")
+}
+
+/// An event should not be mirrored if it was itself produced by a prior
+/// privacy sync — i.e. its description carries our marker. This is the
+/// check that breaks an A-syncs-to-B / B-syncs-to-A feedback loop.
+pub fn is_sync_generated(event: &CalendarEvent) -> bool {
+    event
+        .description
+        .as_deref()
+        .is_some_and(|description| description.contains(SYNC_MARKER_PREFIX))
+}
+
+/// Whether mirroring `event` from `source_calendar_id` onto `target_calendar_id`
+/// would create or continue a feedback loop: mirroring onto the same
+/// calendar it came from, or re-mirroring an event that is already a
+/// mirror.
+pub fn would_create_feedback_loop(
+    event: &CalendarEvent,
+    source_calendar_id: &str,
+    target_calendar_id: &str,
+) -> bool {
+    source_calendar_id == target_calendar_id || is_sync_generated(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::epoch_plus;
+
+    fn event() -> CalendarEvent {
+        CalendarEvent {
+            id: "evt-1".to_string(),
+            calendar_id: "personal".to_string(),
+            uid: None,
+            title: "Dentist appointment".to_string(),
+            start: epoch_plus(0),
+            end: epoch_plus(3600),
+            description: None,
+            location: Some("123 Main St".to_string()),
+            attendee_count: 0,
+            recurring_event_id: None,
+            original_start_time: None,
+        }
+    }
+
+    #[test]
+    fn substitutes_known_tokens() {
+        let rendered = render_privacy_title("Busy: {{title}} @ {{location}}", &event(), "Personal");
+        assert_eq!(rendered, "Busy: Dentist appointment @ 123 Main St");
+    }
+
+    #[test]
+    fn missing_location_substitutes_empty_string() {
+        let mut evt = event();
+        evt.location = None;
+        let rendered = render_privacy_title("Busy @ {{location}}", &evt, "Personal");
+        assert_eq!(rendered, "Busy @ ");
+    }
+
+    #[test]
+    fn fully_opaque_template_ignores_event_fields() {
+        let rendered = render_privacy_title("Busy", &event(), "Personal");
+        assert_eq!(rendered, "Busy");
+    }
+
+    #[test]
+    fn mirroring_onto_the_same_calendar_is_a_loop() {
+        assert!(would_create_feedback_loop(&event(), "personal", "personal"));
+    }
+
+    #[test]
+    fn re_mirroring_a_previously_synced_event_is_a_loop() {
+        let mut evt = event();
+        evt.description = Some(format!(
+            "Mirrored event. {}",
+            build_sync_marker("personal", "evt-1")
+        ));
+        assert!(is_sync_generated(&evt));
+        assert!(would_create_feedback_loop(&evt, "work", "shared"));
+    }
+
+    #[test]
+    fn mirroring_an_original_event_onto_a_different_calendar_is_not_a_loop() {
+        assert!(!would_create_feedback_loop(&event(), "personal", "shared"));
+    }
+}
@@ -0,0 +1,142 @@
+//! CalDAV `sync-collection` (RFC 6578) incremental sync.
+//!
+//! Instead of re-fetching every event on each sync, the server hands back a
+//! `sync-token` that can be replayed on the next `REPORT` to get only what
+//! changed since. Mirrors the `historyId`/`deltaLink` delta-sync pattern used
+//! by the Gmail and Outlook mail providers.
+
+/// One entry in a `sync-collection` response: either the resource's current
+/// ETag, or a 404 indicating it was deleted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CollectionChange {
+    Updated { href: String, etag: String },
+    Deleted { href: String },
+}
+
+#[derive(Debug, Clone)]
+pub struct SyncCollectionPage {
+    pub changes: Vec<CollectionChange>,
+    pub sync_token: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncCollectionOutcome {
+    Applied { new_sync_token: String, changes_applied: usize },
+    /// The server rejected the token (HTTP 507 `valid-sync-token`), meaning
+    /// it expired or was issued by a different server instance — the client
+    /// must discard local state and resync from scratch.
+    TokenInvalid,
+}
+
+/// Tracks the sync-token for a single CalDAV collection (a calendar).
+#[derive(Debug, Default)]
+pub struct CalDavSyncState {
+    sync_token: Option<String>,
+}
+
+impl CalDavSyncState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The `sync-token` element to send in the next `sync-collection`
+    /// REPORT, or `None` for an initial full sync.
+    pub fn sync_token(&self) -> Option<&str> {
+        self.sync_token.as_deref()
+    }
+
+    /// Apply one page of a `sync-collection` response. `token_rejected`
+    /// models the server responding with the `valid-sync-token`
+    /// precondition error instead of a page.
+    pub fn apply_page(
+        &mut self,
+        page: Option<SyncCollectionPage>,
+        token_rejected: bool,
+    ) -> SyncCollectionOutcome {
+        if token_rejected {
+            self.sync_token = None;
+            return SyncCollectionOutcome::TokenInvalid;
+        }
+
+        let page = match page {
+            Some(page) => page,
+            None => return SyncCollectionOutcome::TokenInvalid,
+        };
+
+        let changes_applied = page.changes.len();
+        self.sync_token = Some(page.sync_token.clone());
+        SyncCollectionOutcome::Applied {
+            new_sync_token: page.sync_token,
+            changes_applied,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initial_sync_has_no_token_then_stores_one() {
+        let mut state = CalDavSyncState::new();
+        assert_eq!(state.sync_token(), None);
+
+        let outcome = state.apply_page(
+            Some(SyncCollectionPage {
+                changes: vec![CollectionChange::Updated {
+                    href: "/cal/1.ics".into(),
+                    etag: "\"1\"".into(),
+                }],
+                sync_token: "token-a".into(),
+            }),
+            false,
+        );
+
+        assert_eq!(
+            outcome,
+            SyncCollectionOutcome::Applied {
+                new_sync_token: "token-a".into(),
+                changes_applied: 1,
+            }
+        );
+        assert_eq!(state.sync_token(), Some("token-a"));
+    }
+
+    #[test]
+    fn rejected_token_clears_state_for_full_resync() {
+        let mut state = CalDavSyncState::new();
+        state.apply_page(
+            Some(SyncCollectionPage {
+                changes: vec![],
+                sync_token: "token-a".into(),
+            }),
+            false,
+        );
+
+        let outcome = state.apply_page(None, true);
+        assert_eq!(outcome, SyncCollectionOutcome::TokenInvalid);
+        assert_eq!(state.sync_token(), None);
+    }
+
+    #[test]
+    fn deleted_resource_is_represented_distinctly_from_updates() {
+        let mut state = CalDavSyncState::new();
+        let outcome = state.apply_page(
+            Some(SyncCollectionPage {
+                changes: vec![CollectionChange::Deleted {
+                    href: "/cal/1.ics".into(),
+                }],
+                sync_token: "token-b".into(),
+            }),
+            false,
+        );
+
+        assert_eq!(
+            outcome,
+            SyncCollectionOutcome::Applied {
+                new_sync_token: "token-b".into(),
+                changes_applied: 1,
+            }
+        );
+    }
+}
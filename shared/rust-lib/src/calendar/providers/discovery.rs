@@ -0,0 +1,156 @@
+//! Calendar provider auto-discovery: given just the domain half of an
+//! account's email address, work out which provider to talk to and, for
+//! CalDAV, which server URL to try — so the user never has to type in a
+//! server hostname.
+//!
+//! Google and Microsoft accounts are recognized by domain and routed to
+//! their native providers ([`super::google`], [`super::outlook`]) directly.
+//! Everything else is assumed to be CalDAV, discovered per RFC 6764: a DNS
+//! `SRV` lookup for `_caldavs._tcp.<domain>`, falling back to
+//! `https://<domain>/.well-known/caldav`. No DNS-resolver crate is a
+//! dependency of this crate, so [`pick_srv_record`] is a pure function over
+//! already-resolved records — the caller does the actual lookup with
+//! whatever resolver the embedding app ships and passes the results in.
+
+const GOOGLE_DOMAINS: &[&str] = &["gmail.com", "googlemail.com"];
+const OUTLOOK_DOMAINS: &[&str] = &["outlook.com", "hotmail.com", "live.com", "office365.com"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DetectedProvider {
+    Google,
+    Outlook,
+    /// CalDAV base URLs to try in order, most likely to work first.
+    CalDav { candidate_urls: Vec<String> },
+}
+
+/// A resolved DNS `SRV` record (RFC 2782), e.g. from a `_caldavs._tcp.<domain>` lookup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SrvRecord {
+    pub priority: u16,
+    pub weight: u16,
+    pub port: u16,
+    pub target: String,
+}
+
+/// Pick the record to try first: lowest `priority` wins; ties are broken by
+/// weight using `weight_seed` (expected in `0.0..=1.0`) as the point along
+/// the combined weight range, per RFC 2782's weighted-selection algorithm.
+/// `weight_seed` is an explicit parameter rather than sourced from `rand`,
+/// matching [`crate::ai::retry`]'s approach to the same problem.
+pub fn pick_srv_record(records: &[SrvRecord], weight_seed: f64) -> Option<&SrvRecord> {
+    let best_priority = records.iter().map(|r| r.priority).min()?;
+    let candidates: Vec<&SrvRecord> = records.iter().filter(|r| r.priority == best_priority).collect();
+
+    let total_weight: u32 = candidates.iter().map(|r| r.weight as u32).sum();
+    if total_weight == 0 {
+        return candidates.into_iter().next();
+    }
+
+    let target = (weight_seed.clamp(0.0, 1.0) * total_weight as f64) as u32;
+    let mut running = 0u32;
+    for record in &candidates {
+        running += record.weight as u32;
+        if target < running {
+            return Some(record);
+        }
+    }
+    candidates.last().copied()
+}
+
+fn well_known_url(host: &str, port: u16) -> String {
+    if port == 443 {
+        format!("https://{host}/.well-known/caldav")
+    } else {
+        format!("https://{host}:{port}/.well-known/caldav")
+    }
+}
+
+/// Detect the provider for `email`, returning native `Google`/`Outlook`
+/// variants for recognized domains, or CalDAV candidate URLs built from
+/// `srv_records` (if any were resolved for `_caldavs._tcp.<domain>`) with a
+/// plain `.well-known/caldav` fallback always appended last.
+pub fn detect_provider_for_email(email: &str, srv_records: &[SrvRecord], weight_seed: f64) -> DetectedProvider {
+    let domain = match email.rsplit_once('@') {
+        Some((_, domain)) => domain.to_lowercase(),
+        None => return DetectedProvider::CalDav { candidate_urls: vec![well_known_url(email, 443)] },
+    };
+
+    if GOOGLE_DOMAINS.contains(&domain.as_str()) {
+        return DetectedProvider::Google;
+    }
+    if OUTLOOK_DOMAINS.contains(&domain.as_str()) {
+        return DetectedProvider::Outlook;
+    }
+
+    let mut candidate_urls = Vec::new();
+    if let Some(record) = pick_srv_record(srv_records, weight_seed) {
+        candidate_urls.push(well_known_url(&record.target, record.port));
+    }
+    candidate_urls.push(well_known_url(&domain, 443));
+
+    DetectedProvider::CalDav { candidate_urls }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn google_domains_are_routed_to_the_native_provider() {
+        assert_eq!(detect_provider_for_email("me@gmail.com", &[], 0.0), DetectedProvider::Google);
+    }
+
+    #[test]
+    fn outlook_domains_are_routed_to_the_native_provider() {
+        assert_eq!(detect_provider_for_email("me@outlook.com", &[], 0.0), DetectedProvider::Outlook);
+    }
+
+    #[test]
+    fn unknown_domain_without_srv_records_falls_back_to_well_known() {
+        let detected = detect_provider_for_email("me@example.com", &[], 0.0);
+        assert_eq!(
+            detected,
+            DetectedProvider::CalDav { candidate_urls: vec!["https://example.com/.well-known/caldav".to_string()] }
+        );
+    }
+
+    #[test]
+    fn srv_record_is_tried_before_the_well_known_fallback() {
+        let records = vec![SrvRecord { priority: 0, weight: 1, port: 8443, target: "caldav.example.com".to_string() }];
+        let detected = detect_provider_for_email("me@example.com", &records, 0.0);
+        assert_eq!(
+            detected,
+            DetectedProvider::CalDav {
+                candidate_urls: vec![
+                    "https://caldav.example.com:8443/.well-known/caldav".to_string(),
+                    "https://example.com/.well-known/caldav".to_string(),
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn pick_srv_record_prefers_lowest_priority() {
+        let records = vec![
+            SrvRecord { priority: 10, weight: 0, port: 443, target: "backup.example.com".to_string() },
+            SrvRecord { priority: 0, weight: 0, port: 443, target: "primary.example.com".to_string() },
+        ];
+        let picked = pick_srv_record(&records, 0.0).unwrap();
+        assert_eq!(picked.target, "primary.example.com");
+    }
+
+    #[test]
+    fn pick_srv_record_breaks_ties_by_weighted_seed() {
+        let records = vec![
+            SrvRecord { priority: 0, weight: 1, port: 443, target: "a.example.com".to_string() },
+            SrvRecord { priority: 0, weight: 9, port: 443, target: "b.example.com".to_string() },
+        ];
+        assert_eq!(pick_srv_record(&records, 0.0).unwrap().target, "a.example.com");
+        assert_eq!(pick_srv_record(&records, 0.99).unwrap().target, "b.example.com");
+    }
+
+    #[test]
+    fn pick_srv_record_returns_none_for_an_empty_list() {
+        assert!(pick_srv_record(&[], 0.5).is_none());
+    }
+}
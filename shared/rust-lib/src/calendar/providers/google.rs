@@ -0,0 +1,31 @@
+//! Google Calendar native provider (`GET /calendars/{id}/events`).
+
+use super::CalendarProvider;
+use crate::calendar::CalendarEvent;
+use crate::error::FlowDeskResult;
+
+#[derive(Debug, Clone)]
+pub struct GoogleCalendarProvider {
+    pub access_token: String,
+}
+
+impl GoogleCalendarProvider {
+    pub fn new(access_token: impl Into<String>) -> Self {
+        Self {
+            access_token: access_token.into(),
+        }
+    }
+}
+
+impl CalendarProvider for GoogleCalendarProvider {
+    fn id(&self) -> &'static str {
+        "google"
+    }
+
+    fn list_events(&self, _calendar_id: &str) -> FlowDeskResult<Vec<CalendarEvent>> {
+        // Real implementation calls the Events: list endpoint and maps each
+        // `Event` resource to `CalendarEvent`; left for the HTTP client
+        // integration to fill in.
+        Ok(Vec::new())
+    }
+}
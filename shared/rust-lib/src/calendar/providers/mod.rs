@@ -0,0 +1,20 @@
+//! Native calendar providers. Most calendar accounts sync over CalDAV, but
+//! Google and Microsoft expose richer native APIs (push channels, delta
+//! sync, native free/busy) that are worth using directly instead of going
+//! through their CalDAV compatibility layers.
+
+pub mod caldav;
+pub mod discovery;
+pub mod google;
+pub mod outlook;
+pub mod vtodo;
+
+use crate::calendar::CalendarEvent;
+use crate::error::FlowDeskResult;
+
+/// Common behavior every calendar backend (CalDAV, Google, Outlook)
+/// implements, so the engine can sync any of them the same way.
+pub trait CalendarProvider {
+    fn id(&self) -> &'static str;
+    fn list_events(&self, calendar_id: &str) -> FlowDeskResult<Vec<CalendarEvent>>;
+}
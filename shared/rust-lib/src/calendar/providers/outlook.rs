@@ -0,0 +1,31 @@
+//! Outlook/Microsoft 365 native provider (`GET /me/calendars/{id}/events`
+//! via Microsoft Graph).
+
+use super::CalendarProvider;
+use crate::calendar::CalendarEvent;
+use crate::error::FlowDeskResult;
+
+#[derive(Debug, Clone)]
+pub struct OutlookCalendarProvider {
+    pub access_token: String,
+}
+
+impl OutlookCalendarProvider {
+    pub fn new(access_token: impl Into<String>) -> Self {
+        Self {
+            access_token: access_token.into(),
+        }
+    }
+}
+
+impl CalendarProvider for OutlookCalendarProvider {
+    fn id(&self) -> &'static str {
+        "outlook"
+    }
+
+    fn list_events(&self, _calendar_id: &str) -> FlowDeskResult<Vec<CalendarEvent>> {
+        // Real implementation calls Microsoft Graph's calendar events
+        // endpoint and maps each `event` resource to `CalendarEvent`.
+        Ok(Vec::new())
+    }
+}
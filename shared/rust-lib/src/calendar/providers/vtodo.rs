@@ -0,0 +1,94 @@
+//! `VTODO` (CalDAV tasks) support. Tasks share a collection type with
+//! events in CalDAV (`VCALENDAR` components can be `VEVENT` or `VTODO`) but
+//! have their own status/completion lifecycle, so they get their own model
+//! rather than being shoehorned into [`CalendarEvent`](super::super::CalendarEvent).
+
+use std::time::SystemTime;
+
+pub type TaskId = String;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskStatus {
+    NeedsAction,
+    InProcess,
+    Completed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Task {
+    pub id: TaskId,
+    pub uid: String,
+    pub summary: String,
+    pub due: Option<SystemTime>,
+    pub status: TaskStatus,
+    /// 0-9, matching the iCalendar `PRIORITY` property (0 = undefined).
+    pub priority: u8,
+}
+
+/// Parse the handful of `VTODO` properties we care about out of a raw
+/// iCalendar component body (already stripped of `BEGIN:VTODO`/`END:VTODO`).
+pub fn parse_vtodo(id: TaskId, raw: &str) -> Option<Task> {
+    let mut uid = None;
+    let mut summary = None;
+    let mut status = TaskStatus::NeedsAction;
+    let mut priority = 0u8;
+
+    for line in raw.lines() {
+        let line = line.trim();
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        match key {
+            "UID" => uid = Some(value.to_string()),
+            "SUMMARY" => summary = Some(value.to_string()),
+            "STATUS" => {
+                status = match value {
+                    "IN-PROCESS" => TaskStatus::InProcess,
+                    "COMPLETED" => TaskStatus::Completed,
+                    "CANCELLED" => TaskStatus::Cancelled,
+                    _ => TaskStatus::NeedsAction,
+                }
+            }
+            "PRIORITY" => priority = value.parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+
+    Some(Task {
+        id,
+        uid: uid?,
+        summary: summary.unwrap_or_default(),
+        due: None,
+        status,
+        priority,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_status_and_priority() {
+        let raw = "UID:task-1@example.com\nSUMMARY:File expense report\nSTATUS:IN-PROCESS\nPRIORITY:1";
+        let task = parse_vtodo("local-1".to_string(), raw).unwrap();
+        assert_eq!(task.uid, "task-1@example.com");
+        assert_eq!(task.summary, "File expense report");
+        assert_eq!(task.status, TaskStatus::InProcess);
+        assert_eq!(task.priority, 1);
+    }
+
+    #[test]
+    fn defaults_status_to_needs_action_when_absent() {
+        let raw = "UID:task-2@example.com\nSUMMARY:Draft proposal";
+        let task = parse_vtodo("local-2".to_string(), raw).unwrap();
+        assert_eq!(task.status, TaskStatus::NeedsAction);
+    }
+
+    #[test]
+    fn missing_uid_fails_to_parse() {
+        let raw = "SUMMARY:No uid here";
+        assert!(parse_vtodo("local-3".to_string(), raw).is_none());
+    }
+}
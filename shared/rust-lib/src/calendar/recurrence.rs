@@ -0,0 +1,453 @@
+//! Recurring event expansion (RFC 5545 `RRULE`/`EXDATE`/`RDATE`).
+//!
+//! All of the month/year civil-calendar arithmetic below is UTC-only,
+//! reusing [`super::ics`]'s [`civil_from_days`]/[`days_from_civil`] — there's
+//! no timezone database in this workspace (see that module's doc comment),
+//! so a rule that crosses a real-world DST transition still produces
+//! occurrences spaced exactly 24h/7d/calendar-month apart rather than the
+//! wall-clock-correct offset either side of the transition.
+
+use super::ics::{civil_from_days, days_from_civil};
+use super::{CalendarEvent, Weekday};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// A `BYDAY` entry, e.g. `TU` (every Tuesday) or `2TU`/`-1TU` (the 2nd, or
+/// last, Tuesday of the month — for `Yearly` rules, of `dtstart`'s month).
+/// The ordinal is only used by `Monthly`/`Yearly` rules; `Weekly` uses just
+/// the weekday, and `Daily` ignores `by_day` altogether.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByDay {
+    pub weekday: Weekday,
+    pub ordinal: Option<i32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    pub interval: u32,
+    /// Inclusive; `None` means unbounded (callers must pass a `range_end`
+    /// to [`expand_occurrences`]/[`expand_recurrence`]).
+    pub until: Option<SystemTime>,
+    /// Stop after this many rule-generated occurrences (an [`Self::rdates`]
+    /// is additional and isn't counted against this).
+    pub count: Option<u32>,
+    /// Which weekday(s) an occurrence falls on. For `Weekly`, every matching
+    /// weekday within the interval's week; for `Monthly`/`Yearly`, an entry
+    /// carrying an `ordinal` selects one weekday (e.g. "the 2nd Tuesday")
+    /// and an entry with no ordinal matches every such weekday in the
+    /// month. Empty means "use `dtstart`'s own weekday/day-of-month".
+    pub by_day: Vec<ByDay>,
+    /// Day(s) of the month, `1..=31`, or negative to count back from the
+    /// end of the month (`-1` = the last day). Only consulted for
+    /// `Monthly`/`Yearly` rules, and only when `by_day` is empty.
+    pub by_month_day: Vec<i32>,
+    /// Dates explicitly excluded from the recurrence (the user deleted that
+    /// one occurrence).
+    pub exdates: Vec<SystemTime>,
+    /// Extra one-off dates added on top of the rule (the user added a
+    /// single extra occurrence).
+    pub rdates: Vec<SystemTime>,
+}
+
+fn epoch_days(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64 / SECONDS_PER_DAY
+}
+
+fn time_of_day_secs(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64 % SECONDS_PER_DAY
+}
+
+fn at_epoch_day(days: i64, time_of_day_secs: i64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs((days * SECONDS_PER_DAY + time_of_day_secs).max(0) as u64)
+}
+
+/// Weekday of the day `days` days after the epoch, as a `Mon == 0` index
+/// (1970-01-01 was a Thursday).
+fn weekday_index(days: i64) -> i64 {
+    (days + 3).rem_euclid(7)
+}
+
+fn weekday_to_index(weekday: Weekday) -> i64 {
+    match weekday {
+        Weekday::Mon => 0,
+        Weekday::Tue => 1,
+        Weekday::Wed => 2,
+        Weekday::Thu => 3,
+        Weekday::Fri => 4,
+        Weekday::Sat => 5,
+        Weekday::Sun => 6,
+    }
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    (days_from_civil(next_year, next_month, 1) - days_from_civil(year, month, 1)) as u32
+}
+
+/// Every day-of-month (1-indexed) in `year`/`month` that falls on `weekday`.
+fn all_weekdays_in_month(year: i64, month: u32, weekday: Weekday) -> Vec<u32> {
+    let days_in_month = days_in_month(year, month);
+    let month_start = days_from_civil(year, month, 1);
+    let first_day = 1 + (weekday_to_index(weekday) - weekday_index(month_start)).rem_euclid(7);
+
+    let mut days = Vec::new();
+    let mut day = first_day;
+    while day as u32 <= days_in_month {
+        days.push(day as u32);
+        day += 7;
+    }
+    days
+}
+
+/// The `ordinal`-th occurrence of `weekday` in `year`/`month` (1-indexed
+/// from the start, or negative to count back from the end). `None` if there
+/// is no such occurrence (e.g. a 5th Friday that doesn't exist that month).
+fn nth_weekday_of_month(year: i64, month: u32, weekday: Weekday, ordinal: i32) -> Option<u32> {
+    let matches = all_weekdays_in_month(year, month, weekday);
+    if ordinal > 0 {
+        matches.get(ordinal as usize - 1).copied()
+    } else if ordinal < 0 {
+        let index = matches.len() as i32 + ordinal;
+        (index >= 0).then(|| matches[index as usize])
+    } else {
+        None
+    }
+}
+
+/// Which day(s) of `year`/`month` a `Monthly`/`Yearly` rule lands on.
+fn occurrences_in_month(year: i64, month: u32, rule: &RecurrenceRule, dtstart: SystemTime) -> Vec<u32> {
+    if !rule.by_day.is_empty() {
+        let mut days: Vec<u32> = rule
+            .by_day
+            .iter()
+            .flat_map(|by_day| match by_day.ordinal {
+                Some(ordinal) => nth_weekday_of_month(year, month, by_day.weekday, ordinal).into_iter().collect(),
+                None => all_weekdays_in_month(year, month, by_day.weekday),
+            })
+            .collect();
+        days.sort_unstable();
+        days.dedup();
+        days
+    } else if !rule.by_month_day.is_empty() {
+        let days_in_month = days_in_month(year, month) as i32;
+        let mut days: Vec<u32> = rule
+            .by_month_day
+            .iter()
+            .map(|&day| if day < 0 { days_in_month + day + 1 } else { day })
+            .filter(|&day| day >= 1 && day <= days_in_month)
+            .map(|day| day as u32)
+            .collect();
+        days.sort_unstable();
+        days
+    } else {
+        let (_, _, dtstart_day) = civil_from_days(epoch_days(dtstart));
+        if dtstart_day <= days_in_month(year, month) { vec![dtstart_day] } else { vec![] }
+    }
+}
+
+/// Expand `rule` starting at `dtstart`, returning every occurrence up to and
+/// including `range_end`, honoring `until`, `count`, `exdates`, and merging
+/// in `rdates`.
+pub fn expand_occurrences(rule: &RecurrenceRule, dtstart: SystemTime, range_end: SystemTime) -> Vec<SystemTime> {
+    let bound = match rule.until {
+        Some(until) => until.min(range_end),
+        None => range_end,
+    };
+    let time_of_day = time_of_day_secs(dtstart);
+    let mut occurrences = Vec::new();
+    let within_count = |occurrences: &Vec<SystemTime>| match rule.count {
+        Some(count) => (occurrences.len() as u32) < count,
+        None => true,
+    };
+
+    match rule.frequency {
+        Frequency::Daily => {
+            let step_days = rule.interval.max(1) as i64;
+            let mut day = epoch_days(dtstart);
+            while within_count(&occurrences) {
+                let occurrence = at_epoch_day(day, time_of_day);
+                if occurrence > bound {
+                    break;
+                }
+                if !rule.exdates.contains(&occurrence) {
+                    occurrences.push(occurrence);
+                }
+                day += step_days;
+            }
+        }
+        Frequency::Weekly => {
+            let step_days = 7 * rule.interval.max(1) as i64;
+            let dtstart_days = epoch_days(dtstart);
+            let mut week_start = dtstart_days - weekday_index(dtstart_days);
+            let weekday_offsets: Vec<i64> = if rule.by_day.is_empty() {
+                vec![weekday_index(dtstart_days)]
+            } else {
+                rule.by_day.iter().map(|by_day| weekday_to_index(by_day.weekday)).collect()
+            };
+
+            'weeks: while within_count(&occurrences) {
+                let mut days_this_week: Vec<i64> =
+                    weekday_offsets.iter().map(|offset| week_start + offset).filter(|&day| day >= dtstart_days).collect();
+                days_this_week.sort_unstable();
+
+                for day in days_this_week {
+                    if !within_count(&occurrences) {
+                        break 'weeks;
+                    }
+                    let occurrence = at_epoch_day(day, time_of_day);
+                    if occurrence > bound {
+                        break 'weeks;
+                    }
+                    if !rule.exdates.contains(&occurrence) {
+                        occurrences.push(occurrence);
+                    }
+                }
+                week_start += step_days;
+            }
+        }
+        Frequency::Monthly | Frequency::Yearly => {
+            let (mut year, mut month, _) = civil_from_days(epoch_days(dtstart));
+
+            'months: while within_count(&occurrences) {
+                if at_epoch_day(days_from_civil(year, month, 1), 0) > bound {
+                    break;
+                }
+                for day in occurrences_in_month(year, month, rule, dtstart) {
+                    if !within_count(&occurrences) {
+                        break 'months;
+                    }
+                    let occurrence = at_epoch_day(days_from_civil(year, month, day), time_of_day);
+                    if occurrence < dtstart {
+                        continue;
+                    }
+                    if occurrence > bound {
+                        break 'months;
+                    }
+                    if !rule.exdates.contains(&occurrence) {
+                        occurrences.push(occurrence);
+                    }
+                }
+                match rule.frequency {
+                    Frequency::Monthly => {
+                        let months_ahead = month - 1 + rule.interval.max(1);
+                        year += (months_ahead / 12) as i64;
+                        month = months_ahead % 12 + 1;
+                    }
+                    Frequency::Yearly => year += rule.interval.max(1) as i64,
+                    _ => unreachable!(),
+                }
+            }
+        }
+    }
+
+    for rdate in &rule.rdates {
+        if *rdate <= range_end && !occurrences.contains(rdate) && !rule.exdates.contains(rdate) {
+            occurrences.push(*rdate);
+        }
+    }
+
+    occurrences.sort();
+    occurrences
+}
+
+/// Expand `rule` for `master` into concrete [`CalendarEvent`]s within
+/// `[window_start, window_end]`. Each occurrence keeps `master`'s fields
+/// except `start`/`end`, and is stamped with `recurring_event_id`/
+/// `original_start_time` pointing back at `master` — unless `overrides`
+/// contains an event matching that occurrence (by `recurring_event_id` and
+/// `original_start_time`), in which case the override is returned in its
+/// place (letting a single occurrence be retitled, moved, or otherwise
+/// edited independently of the series).
+pub fn expand_recurrence(
+    master: &CalendarEvent,
+    rule: &RecurrenceRule,
+    overrides: &[CalendarEvent],
+    window_start: SystemTime,
+    window_end: SystemTime,
+) -> Vec<CalendarEvent> {
+    let duration = master.end.duration_since(master.start).unwrap_or_default();
+
+    expand_occurrences(rule, master.start, window_end)
+        .into_iter()
+        .filter(|occurrence_start| *occurrence_start + duration > window_start)
+        .map(|occurrence_start| {
+            overrides
+                .iter()
+                .find(|candidate| {
+                    candidate.recurring_event_id.as_deref() == Some(master.id.as_str())
+                        && candidate.original_start_time == Some(occurrence_start)
+                })
+                .cloned()
+                .unwrap_or_else(|| {
+                    let mut instance = master.clone();
+                    instance.start = occurrence_start;
+                    instance.end = occurrence_start + duration;
+                    instance.recurring_event_id = Some(master.id.clone());
+                    instance.original_start_time = Some(occurrence_start);
+                    instance
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::epoch_plus;
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    fn master(start_secs: u64, dur_secs: u64) -> CalendarEvent {
+        CalendarEvent {
+            id: "series-1".to_string(),
+            calendar_id: "cal-1".to_string(),
+            uid: None,
+            title: "Standup".to_string(),
+            start: epoch_plus(start_secs),
+            end: epoch_plus(start_secs + dur_secs),
+            description: None,
+            location: None,
+            attendee_count: 0,
+            recurring_event_id: None,
+            original_start_time: None,
+        }
+    }
+
+    #[test]
+    fn expands_daily_recurrence_honoring_exdate() {
+        let rule = RecurrenceRule {
+            frequency: Frequency::Daily,
+            interval: 1,
+            until: None,
+            count: None,
+            by_day: vec![],
+            by_month_day: vec![],
+            exdates: vec![epoch_plus(DAY)],
+            rdates: vec![],
+        };
+
+        let occurrences = expand_occurrences(&rule, epoch_plus(0), epoch_plus(DAY * 3));
+        assert_eq!(occurrences, vec![epoch_plus(0), epoch_plus(DAY * 2), epoch_plus(DAY * 3)]);
+    }
+
+    #[test]
+    fn merges_in_rdate_extra_occurrence() {
+        let rule = RecurrenceRule {
+            frequency: Frequency::Weekly,
+            interval: 1,
+            until: None,
+            count: None,
+            by_day: vec![],
+            by_month_day: vec![],
+            exdates: vec![],
+            rdates: vec![epoch_plus(DAY * 3)],
+        };
+
+        let occurrences = expand_occurrences(&rule, epoch_plus(0), epoch_plus(DAY * 7));
+        assert!(occurrences.contains(&epoch_plus(DAY * 3)));
+        assert!(occurrences.contains(&epoch_plus(0)));
+        assert!(occurrences.contains(&epoch_plus(DAY * 7)));
+    }
+
+    #[test]
+    fn weekly_by_day_lands_on_every_named_weekday() {
+        // 1970-01-01 is a Thursday; a Tue/Thu weekly rule starting that day
+        // should also hit the following Tuesday.
+        let rule = RecurrenceRule {
+            frequency: Frequency::Weekly,
+            interval: 1,
+            until: None,
+            count: None,
+            by_day: vec![ByDay { weekday: Weekday::Tue, ordinal: None }, ByDay { weekday: Weekday::Thu, ordinal: None }],
+            by_month_day: vec![],
+            exdates: vec![],
+            rdates: vec![],
+        };
+
+        let occurrences = expand_occurrences(&rule, epoch_plus(0), epoch_plus(DAY * 6));
+        assert_eq!(occurrences, vec![epoch_plus(0), epoch_plus(DAY * 5)]);
+    }
+
+    #[test]
+    fn monthly_by_day_lands_on_the_nth_weekday_of_each_month() {
+        // "the 2nd Tuesday of every month", starting on the 2nd Tuesday of
+        // January 1970 (1970-01-13).
+        let dtstart = epoch_plus(DAY * 12);
+        let rule = RecurrenceRule {
+            frequency: Frequency::Monthly,
+            interval: 1,
+            until: None,
+            count: Some(3),
+            by_day: vec![ByDay { weekday: Weekday::Tue, ordinal: Some(2) }],
+            by_month_day: vec![],
+            exdates: vec![],
+            rdates: vec![],
+        };
+
+        let occurrences = expand_occurrences(&rule, dtstart, epoch_plus(DAY * 400));
+        assert_eq!(occurrences, vec![epoch_plus(DAY * 12), epoch_plus(DAY * 40), epoch_plus(DAY * 68)]);
+    }
+
+    #[test]
+    fn a_daily_rule_spans_a_real_world_dst_transition_with_plain_utc_arithmetic() {
+        // 2026-03-08 is when US clocks spring forward; this crate has no
+        // timezone database, so the occurrence 24h later lands exactly on
+        // the UTC calendar day boundary rather than adjusting for the
+        // skipped wall-clock hour (see the module doc comment).
+        let dtstart_days = days_from_civil(2026, 3, 8);
+        let dtstart = at_epoch_day(dtstart_days, 9 * 3600);
+        let rule = RecurrenceRule {
+            frequency: Frequency::Daily,
+            interval: 1,
+            until: None,
+            count: Some(2),
+            by_day: vec![],
+            by_month_day: vec![],
+            exdates: vec![],
+            rdates: vec![],
+        };
+
+        let occurrences = expand_occurrences(&rule, dtstart, dtstart + Duration::from_secs(DAY * 10));
+        assert_eq!(occurrences, vec![dtstart, dtstart + Duration::from_secs(86_400)]);
+    }
+
+    #[test]
+    fn expand_recurrence_applies_an_override_in_place_of_the_generated_occurrence() {
+        let series = master(0, 1800);
+        let rule = RecurrenceRule {
+            frequency: Frequency::Daily,
+            interval: 1,
+            until: None,
+            count: Some(3),
+            by_day: vec![],
+            by_month_day: vec![],
+            exdates: vec![],
+            rdates: vec![],
+        };
+
+        let mut moved = master(DAY, 1800);
+        moved.title = "Standup (moved)".to_string();
+        moved.start = epoch_plus(DAY + 3600);
+        moved.end = epoch_plus(DAY + 5400);
+        moved.recurring_event_id = Some(series.id.clone());
+        moved.original_start_time = Some(epoch_plus(DAY));
+
+        let instances = expand_recurrence(&series, &rule, &[moved], epoch_plus(0), epoch_plus(DAY * 3));
+
+        assert_eq!(instances.len(), 3);
+        assert_eq!(instances[0].start, epoch_plus(0));
+        assert_eq!(instances[1].title, "Standup (moved)");
+        assert_eq!(instances[1].start, epoch_plus(DAY + 3600));
+        assert_eq!(instances[2].start, epoch_plus(DAY * 2));
+    }
+}
@@ -0,0 +1,101 @@
+//! Default reminders applied to newly created events, and syncing reminder
+//! changes back onto existing events (e.g. the user changes the account-wide
+//! default from 10 to 15 minutes and expects events without an explicit
+//! override to pick that up).
+
+use std::time::Duration;
+
+/// How far before an event's start a reminder should fire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ReminderOffset(pub Duration);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReminderSettings {
+    /// Applied to events that don't specify their own reminders.
+    pub default_offsets: Vec<ReminderOffset>,
+}
+
+impl Default for ReminderSettings {
+    fn default() -> Self {
+        Self {
+            default_offsets: vec![ReminderOffset(Duration::from_secs(10 * 60))],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventReminders {
+    /// `None` means "use the account default"; `Some(vec![])` means the
+    /// user explicitly removed all reminders for this event.
+    pub overrides: Option<Vec<ReminderOffset>>,
+}
+
+impl EventReminders {
+    /// The reminders that should actually fire for this event, resolving
+    /// the account default if no override is set.
+    pub fn effective_offsets<'a>(&'a self, settings: &'a ReminderSettings) -> &'a [ReminderOffset] {
+        match &self.overrides {
+            Some(offsets) => offsets,
+            None => &settings.default_offsets,
+        }
+    }
+}
+
+/// Recompute effective reminders for every event in `events` after
+/// `settings` changed, returning only the ids whose effective reminders
+/// actually changed (so the caller knows which events need a provider push).
+pub fn resync_reminders_after_settings_change<'a>(
+    events: impl IntoIterator<Item = (&'a str, &'a EventReminders)>,
+    old_settings: &ReminderSettings,
+    new_settings: &ReminderSettings,
+) -> Vec<&'a str> {
+    events
+        .into_iter()
+        .filter(|(_, reminders)| {
+            reminders.overrides.is_none()
+                && reminders.effective_offsets(old_settings) != reminders.effective_offsets(new_settings)
+        })
+        .map(|(id, _)| id)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn override_wins_over_default() {
+        let settings = ReminderSettings::default();
+        let reminders = EventReminders {
+            overrides: Some(vec![ReminderOffset(Duration::from_secs(3600))]),
+        };
+        assert_eq!(
+            reminders.effective_offsets(&settings),
+            &[ReminderOffset(Duration::from_secs(3600))]
+        );
+    }
+
+    #[test]
+    fn explicit_empty_override_suppresses_default() {
+        let settings = ReminderSettings::default();
+        let reminders = EventReminders { overrides: Some(vec![]) };
+        assert!(reminders.effective_offsets(&settings).is_empty());
+    }
+
+    #[test]
+    fn only_events_without_override_resync_on_default_change() {
+        let old_settings = ReminderSettings::default();
+        let new_settings = ReminderSettings {
+            default_offsets: vec![ReminderOffset(Duration::from_secs(15 * 60))],
+        };
+
+        let default_event = EventReminders { overrides: None };
+        let overridden_event = EventReminders {
+            overrides: Some(vec![ReminderOffset(Duration::from_secs(5 * 60))]),
+        };
+        let events = vec![("evt-default", &default_event), ("evt-overridden", &overridden_event)];
+
+        let changed = resync_reminders_after_settings_change(events, &old_settings, &new_settings);
+        assert_eq!(changed, vec!["evt-default"]);
+    }
+}
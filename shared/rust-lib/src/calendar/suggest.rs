@@ -0,0 +1,92 @@
+//! Meeting time suggestion: given a proposal (duration, candidate window,
+//! required attendees) and each attendee's aggregated free/busy, find slots
+//! that work for everyone.
+
+use super::freebusy::BusyInterval;
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, Clone)]
+pub struct MeetingProposal {
+    pub duration: Duration,
+    pub earliest_start: SystemTime,
+    pub latest_end: SystemTime,
+    /// Slots are only offered on this grid (e.g. 30 minutes), matching how
+    /// most calendar UIs snap new events.
+    pub granularity: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SuggestedSlot {
+    pub start: SystemTime,
+    pub end: SystemTime,
+}
+
+/// Suggest slots within `proposal`'s window that don't overlap any interval
+/// in `combined_busy` (the union of every required attendee's busy time —
+/// see [`super::freebusy::aggregate_free_busy`]), in chronological order.
+pub fn suggest_meeting_times(
+    proposal: &MeetingProposal,
+    combined_busy: &[BusyInterval],
+) -> Vec<SuggestedSlot> {
+    let mut suggestions = Vec::new();
+    let mut candidate_start = proposal.earliest_start;
+
+    while candidate_start + proposal.duration <= proposal.latest_end {
+        let candidate_end = candidate_start + proposal.duration;
+        let conflicts = combined_busy
+            .iter()
+            .any(|busy| candidate_start < busy.end && candidate_end > busy.start);
+
+        if !conflicts {
+            suggestions.push(SuggestedSlot {
+                start: candidate_start,
+                end: candidate_end,
+            });
+        }
+
+        candidate_start += proposal.granularity;
+    }
+
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::epoch_plus;
+
+    #[test]
+    fn skips_slots_overlapping_busy_intervals() {
+        let proposal = MeetingProposal {
+            duration: Duration::from_secs(1800),
+            earliest_start: epoch_plus(0),
+            latest_end: epoch_plus(3 * 3600),
+            granularity: Duration::from_secs(1800),
+        };
+        let busy = vec![BusyInterval {
+            start: epoch_plus(1800),
+            end: epoch_plus(3600),
+        }];
+
+        let slots = suggest_meeting_times(&proposal, &busy);
+
+        assert!(!slots
+            .iter()
+            .any(|slot| slot.start == epoch_plus(1800) || slot.start == epoch_plus(900)));
+        assert!(slots.iter().any(|slot| slot.start == epoch_plus(0)));
+        assert!(slots.iter().any(|slot| slot.start == epoch_plus(3600)));
+    }
+
+    #[test]
+    fn empty_busy_list_offers_every_grid_slot() {
+        let proposal = MeetingProposal {
+            duration: Duration::from_secs(1800),
+            earliest_start: epoch_plus(0),
+            latest_end: epoch_plus(3600),
+            granularity: Duration::from_secs(1800),
+        };
+
+        let slots = suggest_meeting_times(&proposal, &[]);
+        assert_eq!(slots.len(), 2);
+    }
+}
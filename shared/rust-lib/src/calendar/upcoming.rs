@@ -0,0 +1,65 @@
+//! "What's next" helpers: the soonest upcoming event and the time remaining
+//! until it starts.
+
+use super::{CalendarEngine, CalendarEvent};
+use std::time::{Duration, SystemTime};
+
+impl CalendarEngine {
+    /// The event with the earliest start time that hasn't ended yet, or
+    /// `None` if there are no upcoming events.
+    pub fn next_event(&self, now: SystemTime) -> Option<&CalendarEvent> {
+        self.events()
+            .iter()
+            .filter(|event| event.end > now)
+            .min_by_key(|event| event.start)
+    }
+
+    /// Time remaining until `event` starts. `Duration::ZERO` if it has
+    /// already started (or started exactly now).
+    pub fn time_until(event: &CalendarEvent, now: SystemTime) -> Duration {
+        event.start.duration_since(now).unwrap_or(Duration::ZERO)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::epoch_plus;
+
+    fn event(id: &str, start: SystemTime, end: SystemTime) -> CalendarEvent {
+        CalendarEvent {
+            id: id.to_string(),
+            calendar_id: "cal-1".to_string(),
+            uid: None,
+            title: id.to_string(),
+            start,
+            end,
+            description: None,
+            location: None,
+            attendee_count: 0,
+            recurring_event_id: None,
+            original_start_time: None,
+        }
+    }
+
+    #[test]
+    fn next_event_skips_events_already_ended() {
+        let mut engine = CalendarEngine::new();
+        engine.add_event(event("past", epoch_plus(0), epoch_plus(100)));
+        engine.add_event(event("soonest", epoch_plus(500), epoch_plus(600)));
+        engine.add_event(event("later", epoch_plus(900), epoch_plus(1000)));
+
+        let next = engine.next_event(epoch_plus(200)).unwrap();
+        assert_eq!(next.id, "soonest");
+    }
+
+    #[test]
+    fn time_until_is_zero_once_event_has_started() {
+        let evt = event("started", epoch_plus(100), epoch_plus(200));
+        assert_eq!(CalendarEngine::time_until(&evt, epoch_plus(150)), Duration::ZERO);
+        assert_eq!(
+            CalendarEngine::time_until(&evt, epoch_plus(50)),
+            Duration::from_secs(50)
+        );
+    }
+}
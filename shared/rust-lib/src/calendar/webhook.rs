@@ -0,0 +1,104 @@
+//! Verifies that an incoming calendar push notification actually came from
+//! the subscription we registered, rather than a forged request hitting
+//! the same webhook URL.
+//!
+//! Neither Google Calendar push notifications nor Microsoft Graph webhooks
+//! carry a cryptographic signature over the payload: both rely on a shared
+//! secret chosen when the channel/subscription was created and echoed back
+//! on every notification (Google's `X-Goog-Channel-Token` header,
+//! Microsoft's `clientState` field). There's no payload digest to
+//! recompute — verification is a constant-time comparison against the
+//! secret this crate stored at subscription time.
+
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookProvider {
+    Google,
+    Microsoft,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GoogleChannelNotification<'a> {
+    pub channel_id: &'a str,
+    pub channel_token: &'a str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MicrosoftNotification<'a> {
+    pub subscription_id: &'a str,
+    pub client_state: &'a str,
+}
+
+/// Compares two secrets in constant time, so a forged webhook can't use
+/// response timing to guess the secret one byte at a time.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.bytes().zip(b.bytes()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Tracks the secret registered for each active Google channel / Microsoft
+/// subscription so later notifications can be checked against it.
+#[derive(Debug, Default)]
+pub struct WebhookVerifier {
+    google_tokens: HashMap<String, String>,
+    microsoft_secrets: HashMap<String, String>,
+}
+
+impl WebhookVerifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register_google_channel(&mut self, channel_id: impl Into<String>, token: impl Into<String>) {
+        self.google_tokens.insert(channel_id.into(), token.into());
+    }
+
+    pub fn register_microsoft_subscription(&mut self, subscription_id: impl Into<String>, client_state: impl Into<String>) {
+        self.microsoft_secrets.insert(subscription_id.into(), client_state.into());
+    }
+
+    pub fn verify_google(&self, notification: &GoogleChannelNotification) -> bool {
+        self.google_tokens
+            .get(notification.channel_id)
+            .is_some_and(|expected| constant_time_eq(expected, notification.channel_token))
+    }
+
+    pub fn verify_microsoft(&self, notification: &MicrosoftNotification) -> bool {
+        self.microsoft_secrets
+            .get(notification.subscription_id)
+            .is_some_and(|expected| constant_time_eq(expected, notification.client_state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_google_notification_with_the_registered_token_is_accepted() {
+        let mut verifier = WebhookVerifier::new();
+        verifier.register_google_channel("chan-1", "secret-token");
+
+        assert!(verifier.verify_google(&GoogleChannelNotification { channel_id: "chan-1", channel_token: "secret-token" }));
+        assert!(!verifier.verify_google(&GoogleChannelNotification { channel_id: "chan-1", channel_token: "forged" }));
+    }
+
+    #[test]
+    fn a_microsoft_notification_with_the_registered_client_state_is_accepted() {
+        let mut verifier = WebhookVerifier::new();
+        verifier.register_microsoft_subscription("sub-1", "client-secret");
+
+        assert!(verifier.verify_microsoft(&MicrosoftNotification { subscription_id: "sub-1", client_state: "client-secret" }));
+        assert!(!verifier.verify_microsoft(&MicrosoftNotification { subscription_id: "sub-1", client_state: "forged" }));
+    }
+
+    #[test]
+    fn an_unregistered_channel_or_subscription_is_rejected() {
+        let verifier = WebhookVerifier::new();
+        assert!(!verifier.verify_google(&GoogleChannelNotification { channel_id: "ghost", channel_token: "anything" }));
+        assert!(!verifier.verify_microsoft(&MicrosoftNotification { subscription_id: "ghost", client_state: "anything" }));
+    }
+}
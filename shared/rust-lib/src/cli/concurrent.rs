@@ -0,0 +1,114 @@
+//! Concurrent command processing over [`super::Dispatcher`]: each request
+//! carries a caller-assigned id so responses — which can complete out of
+//! submission order once more than one worker is involved — are still
+//! correlated back to the request that produced them.
+
+use super::{CommandError, Dispatcher};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandRequest {
+    pub id: String,
+    pub name: String,
+    pub args: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandResponse {
+    pub id: String,
+    pub result: Result<String, CommandError>,
+}
+
+/// Runs requests against a shared [`Dispatcher`] across a bounded pool of
+/// worker threads, rather than one thread per request.
+pub struct ConcurrentDispatcher {
+    dispatcher: Arc<Dispatcher>,
+    worker_count: usize,
+}
+
+impl ConcurrentDispatcher {
+    pub fn new(dispatcher: Dispatcher, worker_count: usize) -> Self {
+        Self { dispatcher: Arc::new(dispatcher), worker_count: worker_count.max(1) }
+    }
+
+    /// Process every request in `requests`, returning one
+    /// [`CommandResponse`] per request. Responses are returned in
+    /// completion order, which need not match submission order.
+    pub fn process_all(&self, requests: Vec<CommandRequest>) -> Vec<CommandResponse> {
+        let (tx, rx) = mpsc::channel();
+        let queue = Arc::new(Mutex::new(requests.into_iter()));
+
+        let handles: Vec<_> = (0..self.worker_count.min(queue.lock().unwrap().len().max(1)))
+            .map(|_| {
+                let queue = Arc::clone(&queue);
+                let dispatcher = Arc::clone(&self.dispatcher);
+                let tx = tx.clone();
+                thread::spawn(move || loop {
+                    let next = queue.lock().unwrap().next();
+                    let Some(request) = next else { break };
+                    let result = dispatcher.dispatch(&request.name, &request.args);
+                    tx.send(CommandResponse { id: request.id, result }).expect("receiver dropped before workers finished");
+                })
+            })
+            .collect();
+
+        drop(tx);
+        for handle in handles {
+            handle.join().expect("worker thread panicked");
+        }
+        rx.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::CommandSpec;
+
+    fn echo(args: &[String]) -> Result<String, CommandError> {
+        Ok(args.join(" "))
+    }
+
+    fn dispatcher() -> Dispatcher {
+        let mut dispatcher = Dispatcher::new();
+        dispatcher.register(CommandSpec { name: "echo", description: "echoes its arguments", handler: echo });
+        dispatcher
+    }
+
+    fn request(id: &str, arg: &str) -> CommandRequest {
+        CommandRequest { id: id.to_string(), name: "echo".to_string(), args: vec![arg.to_string()] }
+    }
+
+    #[test]
+    fn every_request_gets_exactly_one_response() {
+        let concurrent = ConcurrentDispatcher::new(dispatcher(), 4);
+        let requests: Vec<_> = (0..20).map(|i| request(&i.to_string(), &i.to_string())).collect();
+
+        let responses = concurrent.process_all(requests);
+        assert_eq!(responses.len(), 20);
+        for i in 0..20 {
+            let response = responses.iter().find(|response| response.id == i.to_string()).unwrap();
+            assert_eq!(response.result, Ok(i.to_string()));
+        }
+    }
+
+    #[test]
+    fn responses_stay_correlated_to_their_request_id_under_concurrency() {
+        let concurrent = ConcurrentDispatcher::new(dispatcher(), 8);
+        let requests: Vec<_> = (0..50).map(|i| request(&format!("req-{i}"), &format!("payload-{i}"))).collect();
+
+        let responses = concurrent.process_all(requests);
+        for response in &responses {
+            let expected_payload = response.id.strip_prefix("req-").unwrap();
+            assert_eq!(response.result, Ok(format!("payload-{expected_payload}")));
+        }
+    }
+
+    #[test]
+    fn an_empty_batch_produces_no_responses() {
+        let concurrent = ConcurrentDispatcher::new(dispatcher(), 4);
+        assert!(concurrent.process_all(Vec::new()).is_empty());
+    }
+}
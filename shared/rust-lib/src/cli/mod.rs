@@ -0,0 +1,104 @@
+//! Table-driven command dispatch with structured errors.
+//!
+//! There's no CLI binary in this crate to dispatch for yet — no `main.rs`,
+//! no `process_command` loop — so this is the dispatcher such a binary
+//! would build on: commands are registered once into a lookup table rather
+//! than matched through a growing `if`/`else if` chain, and failures come
+//! back as a [`CommandError`] with a stable `code` a caller can match on
+//! instead of a bare string.
+
+use std::collections::HashMap;
+
+pub mod concurrent;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandError {
+    pub code: String,
+    pub message: String,
+}
+
+impl CommandError {
+    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { code: code.into(), message: message.into() }
+    }
+
+    fn unknown_command(name: &str) -> Self {
+        Self::new("unknown_command", format!("no command registered for '{name}'"))
+    }
+}
+
+pub type CommandHandler = fn(&[String]) -> Result<String, CommandError>;
+
+#[derive(Clone, Copy)]
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub handler: CommandHandler,
+}
+
+/// A registry of commands, keyed by name. Built once at startup and then
+/// looked up by name per invocation rather than re-matched.
+#[derive(Default)]
+pub struct Dispatcher {
+    commands: HashMap<&'static str, CommandSpec>,
+}
+
+impl Dispatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, spec: CommandSpec) {
+        self.commands.insert(spec.name, spec);
+    }
+
+    pub fn commands(&self) -> impl Iterator<Item = &CommandSpec> {
+        self.commands.values()
+    }
+
+    /// Look up `name` and run it with `args`. Returns a structured
+    /// [`CommandError`] rather than panicking or printing directly, so a
+    /// caller (CLI, NAPI binding, test) can decide how to present it.
+    pub fn dispatch(&self, name: &str, args: &[String]) -> Result<String, CommandError> {
+        let spec = self.commands.get(name).ok_or_else(|| CommandError::unknown_command(name))?;
+        (spec.handler)(args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn echo(args: &[String]) -> Result<String, CommandError> {
+        Ok(args.join(" "))
+    }
+
+    fn always_fails(_args: &[String]) -> Result<String, CommandError> {
+        Err(CommandError::new("boom", "simulated failure"))
+    }
+
+    fn dispatcher() -> Dispatcher {
+        let mut dispatcher = Dispatcher::new();
+        dispatcher.register(CommandSpec { name: "echo", description: "echoes its arguments", handler: echo });
+        dispatcher.register(CommandSpec { name: "fail", description: "always fails", handler: always_fails });
+        dispatcher
+    }
+
+    #[test]
+    fn dispatches_to_the_matching_registered_handler() {
+        let result = dispatcher().dispatch("echo", &["hello".to_string(), "world".to_string()]);
+        assert_eq!(result, Ok("hello world".to_string()));
+    }
+
+    #[test]
+    fn an_unregistered_command_reports_a_structured_error() {
+        let result = dispatcher().dispatch("missing", &[]);
+        assert_eq!(result, Err(CommandError::new("unknown_command", "no command registered for 'missing'")));
+    }
+
+    #[test]
+    fn a_handler_error_is_passed_through_unchanged() {
+        let result = dispatcher().dispatch("fail", &[]);
+        assert_eq!(result, Err(CommandError::new("boom", "simulated failure")));
+    }
+}
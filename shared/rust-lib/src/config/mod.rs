@@ -0,0 +1,127 @@
+//! Typed, validated configuration for every engine in this crate.
+//!
+//! Each engine gets its own config struct with a sane [`Default`] and a
+//! `validate()` that rejects impossible values early instead of failing
+//! confusingly deep in a sync loop.
+
+pub mod sync;
+pub mod vector_clock;
+
+pub use sync::{three_way_merge, ConfigSnapshot, SyncOutcome};
+
+use crate::error::{FlowDeskError, FlowDeskResult};
+
+pub trait EngineConfig: Default {
+    fn validate(&self) -> FlowDeskResult<()>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MailEngineConfig {
+    pub max_connections_per_account: u32,
+    pub preview_fetch_bytes: u32,
+    pub dead_letter_max_attempts: u32,
+}
+
+impl Default for MailEngineConfig {
+    fn default() -> Self {
+        Self {
+            max_connections_per_account: 4,
+            preview_fetch_bytes: crate::mail::imap::SNIPPET_FETCH_BYTES,
+            dead_letter_max_attempts: crate::mail::dead_letter::MAX_ATTEMPTS,
+        }
+    }
+}
+
+impl EngineConfig for MailEngineConfig {
+    fn validate(&self) -> FlowDeskResult<()> {
+        if self.max_connections_per_account == 0 {
+            return Err(FlowDeskError::InvalidInput(
+                "max_connections_per_account must be at least 1".to_string(),
+            ));
+        }
+        if self.dead_letter_max_attempts == 0 {
+            return Err(FlowDeskError::InvalidInput(
+                "dead_letter_max_attempts must be at least 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarEngineConfig {
+    pub agenda_lookahead_days: u32,
+    pub default_reminder_minutes: Vec<u32>,
+}
+
+impl Default for CalendarEngineConfig {
+    fn default() -> Self {
+        Self {
+            agenda_lookahead_days: 7,
+            default_reminder_minutes: vec![10],
+        }
+    }
+}
+
+impl EngineConfig for CalendarEngineConfig {
+    fn validate(&self) -> FlowDeskResult<()> {
+        if self.agenda_lookahead_days == 0 {
+            return Err(FlowDeskError::InvalidInput(
+                "agenda_lookahead_days must be at least 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchEngineConfig {
+    pub query_timeout_ms: u32,
+    pub max_results: usize,
+}
+
+impl Default for SearchEngineConfig {
+    fn default() -> Self {
+        Self {
+            query_timeout_ms: 300,
+            max_results: 200,
+        }
+    }
+}
+
+impl EngineConfig for SearchEngineConfig {
+    fn validate(&self) -> FlowDeskResult<()> {
+        if self.query_timeout_ms == 0 {
+            return Err(FlowDeskError::InvalidInput(
+                "query_timeout_ms must be at least 1".to_string(),
+            ));
+        }
+        if self.max_results == 0 {
+            return Err(FlowDeskError::InvalidInput(
+                "max_results must be at least 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_are_valid() {
+        assert!(MailEngineConfig::default().validate().is_ok());
+        assert!(CalendarEngineConfig::default().validate().is_ok());
+        assert!(SearchEngineConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_zero_connections() {
+        let config = MailEngineConfig {
+            max_connections_per_account: 0,
+            ..MailEngineConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
+}
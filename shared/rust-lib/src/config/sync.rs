@@ -0,0 +1,120 @@
+//! Config sync reconciliation: combines the [`vector_clock`] comparison
+//! (to short-circuit when one side is a strict descendant of the other)
+//! with a field-level three-way merge for genuinely concurrent edits.
+
+use super::vector_clock::{self, ClockOrdering, MergedField, VectorClock};
+use std::collections::BTreeMap;
+
+/// A syncable config blob: an opaque string-keyed field map plus the
+/// vector clock it was written with. Real config values are the typed
+/// `WorkspaceConfig` fields; they're flattened to strings here so the merge
+/// logic doesn't need to know the schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigSnapshot {
+    pub fields: BTreeMap<String, String>,
+    pub vector_clock: VectorClock,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncOutcome {
+    /// One side was already a descendant of the other — no field-level
+    /// merge needed, just take the newer snapshot.
+    FastForward(ConfigSnapshot),
+    /// Concurrent edits were merged field-by-field; `conflicts` lists keys
+    /// where both sides changed the field to different values and the
+    /// remote value was kept as a default pending user resolution.
+    Merged { snapshot: ConfigSnapshot, conflicts: Vec<String> },
+}
+
+/// Three-way merge `local` and `remote` against their common ancestor
+/// `base`, using vector clocks to avoid unnecessary field-level work when
+/// one side hasn't changed anything the other doesn't already have.
+pub fn three_way_merge(base: &ConfigSnapshot, local: &ConfigSnapshot, remote: &ConfigSnapshot) -> SyncOutcome {
+    match vector_clock::compare(&local.vector_clock, &remote.vector_clock) {
+        ClockOrdering::Equal | ClockOrdering::Before => SyncOutcome::FastForward(remote.clone()),
+        ClockOrdering::After => SyncOutcome::FastForward(local.clone()),
+        ClockOrdering::Concurrent => {
+            let mut merged_fields = BTreeMap::new();
+            let mut conflicts = Vec::new();
+
+            let keys: std::collections::BTreeSet<&String> =
+                base.fields.keys().chain(local.fields.keys()).chain(remote.fields.keys()).collect();
+
+            for key in keys {
+                let empty = String::new();
+                let base_value = base.fields.get(key).unwrap_or(&empty);
+                let local_value = local.fields.get(key).unwrap_or(&empty);
+                let remote_value = remote.fields.get(key).unwrap_or(&empty);
+
+                match vector_clock::merge_field(base_value, local_value, remote_value) {
+                    MergedField::Resolved(value) => {
+                        merged_fields.insert(key.clone(), value);
+                    }
+                    MergedField::Conflict { remote: remote_value, .. } => {
+                        conflicts.push(key.clone());
+                        merged_fields.insert(key.clone(), remote_value);
+                    }
+                }
+            }
+
+            let snapshot = ConfigSnapshot {
+                fields: merged_fields,
+                vector_clock: vector_clock::merge_clocks(&local.vector_clock, &remote.vector_clock),
+            };
+            SyncOutcome::Merged { snapshot, conflicts }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(fields: &[(&str, &str)], clock: &[(&str, u64)]) -> ConfigSnapshot {
+        ConfigSnapshot {
+            fields: fields.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+            vector_clock: clock.iter().map(|(d, c)| (d.to_string(), *c)).collect(),
+        }
+    }
+
+    #[test]
+    fn fast_forwards_when_one_side_strictly_newer() {
+        let base = snapshot(&[("theme", "light")], &[("a", 1)]);
+        let local = snapshot(&[("theme", "light")], &[("a", 1)]);
+        let remote = snapshot(&[("theme", "dark")], &[("a", 2)]);
+
+        let outcome = three_way_merge(&base, &local, &remote);
+        assert_eq!(outcome, SyncOutcome::FastForward(remote));
+    }
+
+    #[test]
+    fn merges_non_conflicting_concurrent_field_edits() {
+        let base = snapshot(&[("theme", "light"), ("font_size", "14")], &[("a", 1), ("b", 1)]);
+        let local = snapshot(&[("theme", "dark"), ("font_size", "14")], &[("a", 2), ("b", 1)]);
+        let remote = snapshot(&[("theme", "light"), ("font_size", "16")], &[("a", 1), ("b", 2)]);
+
+        match three_way_merge(&base, &local, &remote) {
+            SyncOutcome::Merged { snapshot, conflicts } => {
+                assert!(conflicts.is_empty());
+                assert_eq!(snapshot.fields.get("theme").unwrap(), "dark");
+                assert_eq!(snapshot.fields.get("font_size").unwrap(), "16");
+            }
+            other => panic!("expected a merge, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn reports_conflicting_field_and_defaults_to_remote_pending_resolution() {
+        let base = snapshot(&[("theme", "light")], &[("a", 1), ("b", 1)]);
+        let local = snapshot(&[("theme", "dark")], &[("a", 2), ("b", 1)]);
+        let remote = snapshot(&[("theme", "blue")], &[("a", 1), ("b", 2)]);
+
+        match three_way_merge(&base, &local, &remote) {
+            SyncOutcome::Merged { snapshot, conflicts } => {
+                assert_eq!(conflicts, vec!["theme".to_string()]);
+                assert_eq!(snapshot.fields.get("theme").unwrap(), "blue");
+            }
+            other => panic!("expected a merge, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! Vector clocks for detecting concurrent edits to synced config, and a
+//! three-way merge that uses them to tell "this device changed it" apart
+//! from "this field never changed" when reconciling local/remote/base
+//! config blobs.
+
+use std::collections::BTreeMap;
+
+pub type DeviceId = String;
+
+/// `device_id -> logical clock` at the time a config version was written.
+pub type VectorClock = BTreeMap<DeviceId, u64>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockOrdering {
+    Equal,
+    Before,
+    After,
+    /// Neither dominates the other — both sides made changes the other
+    /// hasn't seen, so a three-way (field-level) merge is needed.
+    Concurrent,
+}
+
+/// Compare two vector clocks. `a` is "before" `b` if every entry in `a` is
+/// `<=` the corresponding entry in `b` (treating missing entries as 0) and
+/// at least one is strictly less; symmetric for "after".
+pub fn compare(a: &VectorClock, b: &VectorClock) -> ClockOrdering {
+    let keys: std::collections::BTreeSet<&DeviceId> = a.keys().chain(b.keys()).collect();
+
+    let mut a_less = false;
+    let mut b_less = false;
+    for key in keys {
+        let av = a.get(key).copied().unwrap_or(0);
+        let bv = b.get(key).copied().unwrap_or(0);
+        if av < bv {
+            a_less = true;
+        } else if av > bv {
+            b_less = true;
+        }
+    }
+
+    match (a_less, b_less) {
+        (false, false) => ClockOrdering::Equal,
+        (true, false) => ClockOrdering::Before,
+        (false, true) => ClockOrdering::After,
+        (true, true) => ClockOrdering::Concurrent,
+    }
+}
+
+/// Merge two vector clocks by taking the max of each device's counter —
+/// the new clock for a merged config version that has "seen" both inputs.
+pub fn merge_clocks(a: &VectorClock, b: &VectorClock) -> VectorClock {
+    let mut merged = a.clone();
+    for (device, counter) in b {
+        let entry = merged.entry(device.clone()).or_insert(0);
+        *entry = (*entry).max(*counter);
+    }
+    merged
+}
+
+/// Result of a field-level three-way merge: either side's value, or a
+/// conflict requiring the caller to pick (both sides changed it from the
+/// base to *different* values).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergedField<T> {
+    Resolved(T),
+    Conflict { local: T, remote: T },
+}
+
+/// Three-way merge one field given its value in the common ancestor
+/// (`base`), the local version, and the remote version.
+pub fn merge_field<T: Clone + PartialEq>(base: &T, local: &T, remote: &T) -> MergedField<T> {
+    match (local == base, remote == base) {
+        (true, true) | (true, false) => MergedField::Resolved(remote.clone()),
+        (false, true) => MergedField::Resolved(local.clone()),
+        (false, false) if local == remote => MergedField::Resolved(local.clone()),
+        (false, false) => MergedField::Conflict {
+            local: local.clone(),
+            remote: remote.clone(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(pairs: &[(&str, u64)]) -> VectorClock {
+        pairs.iter().map(|(d, c)| (d.to_string(), *c)).collect()
+    }
+
+    #[test]
+    fn detects_before_after_and_concurrent() {
+        let a = clock(&[("device-a", 1)]);
+        let b = clock(&[("device-a", 2)]);
+        assert_eq!(compare(&a, &b), ClockOrdering::Before);
+        assert_eq!(compare(&b, &a), ClockOrdering::After);
+
+        let c = clock(&[("device-a", 2), ("device-b", 1)]);
+        let d = clock(&[("device-a", 1), ("device-b", 2)]);
+        assert_eq!(compare(&c, &d), ClockOrdering::Concurrent);
+
+        assert_eq!(compare(&a, &a), ClockOrdering::Equal);
+    }
+
+    #[test]
+    fn merge_clocks_takes_elementwise_max() {
+        let a = clock(&[("device-a", 3), ("device-b", 1)]);
+        let b = clock(&[("device-a", 2), ("device-b", 5)]);
+        let merged = merge_clocks(&a, &b);
+        assert_eq!(merged, clock(&[("device-a", 3), ("device-b", 5)]));
+    }
+
+    #[test]
+    fn unchanged_field_resolves_to_the_side_that_changed() {
+        assert_eq!(
+            merge_field(&"base".to_string(), &"base".to_string(), &"remote".to_string()),
+            MergedField::Resolved("remote".to_string())
+        );
+        assert_eq!(
+            merge_field(&"base".to_string(), &"local".to_string(), &"base".to_string()),
+            MergedField::Resolved("local".to_string())
+        );
+    }
+
+    #[test]
+    fn both_sides_changing_to_the_same_value_is_not_a_conflict() {
+        assert_eq!(
+            merge_field(&"base".to_string(), &"new".to_string(), &"new".to_string()),
+            MergedField::Resolved("new".to_string())
+        );
+    }
+
+    #[test]
+    fn both_sides_changing_to_different_values_is_a_conflict() {
+        assert_eq!(
+            merge_field(&"base".to_string(), &"local".to_string(), &"remote".to_string()),
+            MergedField::Conflict {
+                local: "local".to_string(),
+                remote: "remote".to_string(),
+            }
+        );
+    }
+}
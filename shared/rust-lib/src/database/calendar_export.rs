@@ -0,0 +1,97 @@
+//! Calendar data export for the settings "export data" action — dumps
+//! calendars and events to a JSON string the caller can write to disk,
+//! symmetric with the mail engine's own export path.
+//!
+//! Accounts, recurrence rules and privacy sync rules aren't modeled as
+//! persisted entities in this crate yet, so this covers what
+//! [`CalendarEngine`] actually tracks: calendars (derived from the events
+//! referencing them) and the events themselves. Extend [`CalendarExport`]
+//! rather than adding a second export path once that storage lands.
+
+use crate::calendar::{CalendarEngine, CalendarEvent};
+use std::collections::BTreeSet;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarExport {
+    pub calendar_ids: Vec<String>,
+    pub events: Vec<CalendarEvent>,
+}
+
+/// Snapshot every calendar and event currently held by `engine`. No
+/// credentials are touched here — this crate doesn't store any alongside
+/// calendar data.
+pub fn export_all(engine: &CalendarEngine) -> CalendarExport {
+    let calendar_ids: BTreeSet<String> = engine.events().iter().map(|event| event.calendar_id.clone()).collect();
+    CalendarExport { calendar_ids: calendar_ids.into_iter().collect(), events: engine.events().to_vec() }
+}
+
+fn json_escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn json_string_or_null(value: Option<&str>) -> String {
+    value.map(|v| format!("\"{}\"", json_escape(v))).unwrap_or_else(|| "null".to_string())
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+impl CalendarExport {
+    /// Render this export as a JSON string. Hand-rolled rather than
+    /// pulling in `serde_json` for this one call site, matching the
+    /// hand-rolled JSON handling already used in `ai::insights`.
+    pub fn to_json(&self) -> String {
+        let calendars_json =
+            self.calendar_ids.iter().map(|id| format!("\"{}\"", json_escape(id))).collect::<Vec<_>>().join(",");
+
+        let events_json = self
+            .events
+            .iter()
+            .map(|event| {
+                format!(
+                    "{{\"id\":\"{}\",\"calendar_id\":\"{}\",\"title\":\"{}\",\"start\":{},\"end\":{},\"description\":{},\"location\":{}}}",
+                    json_escape(&event.id),
+                    json_escape(&event.calendar_id),
+                    json_escape(&event.title),
+                    unix_seconds(event.start),
+                    unix_seconds(event.end),
+                    json_string_or_null(event.description.as_deref()),
+                    json_string_or_null(event.location.as_deref()),
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        format!("{{\"calendars\":[{calendars_json}],\"events\":[{events_json}]}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::epoch_plus;
+
+    #[test]
+    fn exported_json_contains_the_seeded_event() {
+        let mut engine = CalendarEngine::new();
+        engine.add_event(CalendarEvent {
+            id: "e1".to_string(),
+            calendar_id: "cal-1".to_string(),
+            uid: None,
+            title: "Standup".to_string(),
+            start: epoch_plus(0),
+            end: epoch_plus(1800),
+            description: None,
+            location: None,
+            attendee_count: 0,
+            recurring_event_id: None,
+            original_start_time: None,
+        });
+
+        let json = export_all(&engine).to_json();
+        assert!(json.contains("\"title\":\"Standup\""));
+        assert!(json.contains("\"calendars\":[\"cal-1\"]"));
+    }
+}
@@ -0,0 +1,103 @@
+//! A shared, long-lived connection pool per database, so maintenance
+//! helpers (vacuum, integrity check, connection optimization) and the
+//! `MailDatabase`/`CalendarDatabase` engines borrow a connection instead
+//! of each opening and immediately closing its own.
+//!
+//! The real pool wraps `sqlx::SqlitePool` in WAL mode with pragmas set
+//! once at pool creation; this models the acquire/release contract those
+//! callers depend on without a real SQLite connection underneath.
+
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DbKind {
+    Mail,
+    Calendar,
+}
+
+/// SQLite in WAL mode allows unlimited concurrent readers alongside a
+/// single writer, so the pool caps concurrent *connections* rather than
+/// serializing access the way a single shared connection would.
+const DEFAULT_MAX_CONNECTIONS: u32 = 8;
+
+#[derive(Debug)]
+pub struct ConnectionPool {
+    kind: DbKind,
+    max_connections: u32,
+    active: Mutex<u32>,
+}
+
+impl ConnectionPool {
+    pub fn new(kind: DbKind) -> Self {
+        Self { kind, max_connections: DEFAULT_MAX_CONNECTIONS, active: Mutex::new(0) }
+    }
+
+    pub fn kind(&self) -> DbKind {
+        self.kind
+    }
+
+    pub fn active_connections(&self) -> u32 {
+        *self.active.lock().unwrap()
+    }
+
+    /// Borrow a connection from the pool, or `None` if it's already at
+    /// capacity. The returned guard releases the connection back to the
+    /// pool when dropped.
+    pub fn acquire(&self) -> Option<PooledConnection<'_>> {
+        let mut active = self.active.lock().unwrap();
+        if *active >= self.max_connections {
+            return None;
+        }
+        *active += 1;
+        Some(PooledConnection { pool: self })
+    }
+}
+
+pub struct PooledConnection<'a> {
+    pool: &'a ConnectionPool,
+}
+
+impl Drop for PooledConnection<'_> {
+    fn drop(&mut self) {
+        *self.pool.active.lock().unwrap() -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn concurrent_reads_share_the_pool_without_being_rejected() {
+        let pool = Arc::new(ConnectionPool::new(DbKind::Mail));
+
+        let handles: Vec<_> = (0..DEFAULT_MAX_CONNECTIONS)
+            .map(|_| {
+                let pool = Arc::clone(&pool);
+                thread::spawn(move || {
+                    let connection = pool.acquire();
+                    assert!(connection.is_some(), "database is locked");
+                    thread::yield_now();
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(pool.active_connections(), 0);
+    }
+
+    #[test]
+    fn acquiring_beyond_capacity_is_rejected_not_blocked() {
+        let pool = ConnectionPool::new(DbKind::Calendar);
+        let guards: Vec<_> = (0..DEFAULT_MAX_CONNECTIONS).map(|_| pool.acquire().unwrap()).collect();
+
+        assert!(pool.acquire().is_none());
+        drop(guards);
+        assert!(pool.acquire().is_some());
+    }
+}
@@ -0,0 +1,151 @@
+//! Schema migration tracking, shared by every per-engine database so the
+//! UI progress view can show exactly what ran, when, and whether it
+//! failed — instead of collapsing that into a single "migrated or not"
+//! boolean.
+
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MigrationId(pub u32);
+
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub id: MigrationId,
+    pub description: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    pub id: MigrationId,
+    pub description: &'static str,
+    pub applied: bool,
+    pub newly_applied: bool,
+    pub applied_at: Option<SystemTime>,
+    pub error: Option<String>,
+}
+
+/// Runs a migration's schema changes against a real database connection.
+/// The SQLite-backed implementation executes the migration's SQL; this
+/// crate only models the bookkeeping around that call.
+pub trait MigrationApplier {
+    fn apply(&mut self, migration: &Migration) -> Result<(), String>;
+}
+
+/// An applier that always succeeds without doing anything, standing in
+/// for the real SQLite execution until this crate is wired to a database
+/// connection.
+#[derive(Debug, Default)]
+pub struct NoopMigrationApplier;
+
+impl MigrationApplier for NoopMigrationApplier {
+    fn apply(&mut self, _migration: &Migration) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Tracks which migrations have already been applied (and when), so
+/// re-running [`MigrationLedger::run`] against the same migration set is
+/// idempotent and reports the original `applied_at` instead of re-running
+/// or re-stamping it.
+#[derive(Debug, Default)]
+pub struct MigrationLedger {
+    applied: HashMap<MigrationId, SystemTime>,
+}
+
+impl MigrationLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply every migration in `migrations` that hasn't already been
+    /// recorded in this ledger, using `applier` to perform the actual
+    /// schema change, and return a status per migration in order.
+    pub fn run(
+        &mut self,
+        migrations: &[Migration],
+        applier: &mut impl MigrationApplier,
+        now: SystemTime,
+    ) -> Vec<MigrationStatus> {
+        migrations
+            .iter()
+            .map(|migration| {
+                if let Some(&applied_at) = self.applied.get(&migration.id) {
+                    return MigrationStatus {
+                        id: migration.id,
+                        description: migration.description,
+                        applied: true,
+                        newly_applied: false,
+                        applied_at: Some(applied_at),
+                        error: None,
+                    };
+                }
+
+                match applier.apply(migration) {
+                    Ok(()) => {
+                        self.applied.insert(migration.id, now);
+                        MigrationStatus {
+                            id: migration.id,
+                            description: migration.description,
+                            applied: true,
+                            newly_applied: true,
+                            applied_at: Some(now),
+                            error: None,
+                        }
+                    }
+                    Err(error) => MigrationStatus {
+                        id: migration.id,
+                        description: migration.description,
+                        applied: false,
+                        newly_applied: false,
+                        applied_at: None,
+                        error: Some(error),
+                    },
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn migrations() -> Vec<Migration> {
+        vec![
+            Migration { id: MigrationId(1), description: "create messages table" },
+            Migration { id: MigrationId(2), description: "add messages.thread_id index" },
+        ]
+    }
+
+    #[test]
+    fn second_run_reports_zero_newly_applied() {
+        let mut ledger = MigrationLedger::new();
+        let mut applier = NoopMigrationApplier;
+        let first_run_at = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1000);
+
+        let first = ledger.run(&migrations(), &mut applier, first_run_at);
+        assert!(first.iter().all(|status| status.newly_applied));
+
+        let second_run_at = first_run_at + std::time::Duration::from_secs(60);
+        let second = ledger.run(&migrations(), &mut applier, second_run_at);
+
+        assert!(second.iter().all(|status| status.applied && !status.newly_applied));
+        assert!(second.iter().all(|status| status.applied_at == Some(first_run_at)));
+    }
+
+    #[test]
+    fn failed_migration_is_reported_with_its_error() {
+        struct FailingApplier;
+        impl MigrationApplier for FailingApplier {
+            fn apply(&mut self, migration: &Migration) -> Result<(), String> {
+                Err(format!("syntax error in migration {}", migration.id.0))
+            }
+        }
+
+        let mut ledger = MigrationLedger::new();
+        let statuses = ledger.run(&migrations(), &mut FailingApplier, SystemTime::UNIX_EPOCH);
+
+        assert!(statuses.iter().all(|status| !status.applied && status.error.is_some()));
+    }
+}
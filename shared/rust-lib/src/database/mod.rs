@@ -0,0 +1,157 @@
+//! Local persistence for the mail and calendar engines.
+//!
+//! There's no real database connection wired up yet — [`MailDatabase`] and
+//! [`CalendarDatabase`] in [`crate::testing`] stand in for that during unit
+//! tests — but the schema migration bookkeeping in [`migrations`] doesn't
+//! depend on the storage backend, so it's modeled here for real.
+
+pub mod backup;
+pub mod calendar_export;
+pub mod connection_pool;
+pub mod migrations;
+pub mod schema_version;
+
+pub use backup::{backup_database, BackupCheckpoint, BackupResult};
+pub use calendar_export::{export_all as export_calendar_data, CalendarExport};
+pub use connection_pool::{ConnectionPool, DbKind, PooledConnection};
+pub use migrations::{Migration, MigrationApplier, MigrationId, MigrationLedger, MigrationStatus, NoopMigrationApplier};
+pub use schema_version::{enforce_schema_version, StoredSchemaVersion, CURRENT_SCHEMA_VERSION};
+
+use crate::error::FlowDeskResult;
+use std::time::SystemTime;
+
+const MAIL_MIGRATIONS: &[Migration] = &[
+    Migration { id: MigrationId(1), description: "create messages table" },
+    Migration { id: MigrationId(2), description: "create accounts table" },
+    Migration { id: MigrationId(3), description: "add messages.thread_id index" },
+];
+
+const CALENDAR_MIGRATIONS: &[Migration] = &[
+    Migration { id: MigrationId(1), description: "create events table" },
+    Migration { id: MigrationId(2), description: "create calendars table" },
+];
+
+/// Coordinates the per-engine databases. Owns one [`MigrationLedger`] per
+/// engine so each can be migrated independently (e.g. a mail-only restore
+/// doesn't need to touch the calendar schema).
+#[derive(Debug)]
+pub struct FlowDeskDatabase {
+    mail_migrations: MigrationLedger,
+    calendar_migrations: MigrationLedger,
+    mail_pool: ConnectionPool,
+    calendar_pool: ConnectionPool,
+}
+
+impl Default for FlowDeskDatabase {
+    fn default() -> Self {
+        Self {
+            mail_migrations: MigrationLedger::default(),
+            calendar_migrations: MigrationLedger::default(),
+            mail_pool: ConnectionPool::new(DbKind::Mail),
+            calendar_pool: ConnectionPool::new(DbKind::Calendar),
+        }
+    }
+}
+
+impl FlowDeskDatabase {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The long-lived connection pool for `db`, shared by the engine and
+    /// any maintenance helpers (vacuum, integrity check, connection
+    /// optimization) instead of each opening its own connection.
+    pub fn pool(&self, db: DbKind) -> &ConnectionPool {
+        match db {
+            DbKind::Mail => &self.mail_pool,
+            DbKind::Calendar => &self.calendar_pool,
+        }
+    }
+
+    /// Run every pending mail and calendar migration, in that order, and
+    /// return a status for each so the caller can surface migration
+    /// detail (e.g. in a startup progress view) instead of a single
+    /// success/failure flag.
+    pub fn run_all_migrations(
+        &mut self,
+        mail_applier: &mut impl MigrationApplier,
+        calendar_applier: &mut impl MigrationApplier,
+        now: SystemTime,
+    ) -> Vec<MigrationStatus> {
+        let mut statuses = self.mail_migrations.run(MAIL_MIGRATIONS, mail_applier, now);
+        statuses.extend(self.calendar_migrations.run(CALENDAR_MIGRATIONS, calendar_applier, now));
+        statuses
+    }
+
+    /// Open the database: refuse if either engine's `meta` table records a
+    /// schema version newer than [`CURRENT_SCHEMA_VERSION`] (the app would
+    /// be downgrading into a schema it doesn't understand), otherwise run
+    /// every pending migration as usual.
+    pub fn open(
+        &mut self,
+        stored_mail_version: StoredSchemaVersion,
+        stored_calendar_version: StoredSchemaVersion,
+        mail_applier: &mut impl MigrationApplier,
+        calendar_applier: &mut impl MigrationApplier,
+        now: SystemTime,
+    ) -> FlowDeskResult<Vec<MigrationStatus>> {
+        enforce_schema_version(stored_mail_version, CURRENT_SCHEMA_VERSION)?;
+        enforce_schema_version(stored_calendar_version, CURRENT_SCHEMA_VERSION)?;
+        Ok(self.run_all_migrations(mail_applier, calendar_applier, now))
+    }
+}
+
+/// Construct a [`FlowDeskDatabase`] and run its migrations to completion
+/// using the no-op appliers, so startup doesn't fail before a real
+/// database connection exists. Once SQLite is wired in, the appliers here
+/// become real connections instead.
+pub fn initialize_databases(now: SystemTime) -> (FlowDeskDatabase, Vec<MigrationStatus>) {
+    let mut database = FlowDeskDatabase::new();
+    let mut mail_applier = NoopMigrationApplier;
+    let mut calendar_applier = NoopMigrationApplier;
+    let statuses = database.run_all_migrations(&mut mail_applier, &mut calendar_applier, now);
+    (database, statuses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initialize_databases_reports_every_migration_applied() {
+        let (_, statuses) = initialize_databases(SystemTime::UNIX_EPOCH);
+        assert_eq!(statuses.len(), MAIL_MIGRATIONS.len() + CALENDAR_MIGRATIONS.len());
+        assert!(statuses.iter().all(|status| status.applied && status.newly_applied));
+    }
+
+    #[test]
+    fn opening_an_older_or_brand_new_database_runs_migrations_forward() {
+        let mut database = FlowDeskDatabase::new();
+        let mut mail_applier = NoopMigrationApplier;
+        let mut calendar_applier = NoopMigrationApplier;
+
+        let statuses = database
+            .open(StoredSchemaVersion(None), StoredSchemaVersion(Some(0)), &mut mail_applier, &mut calendar_applier, SystemTime::UNIX_EPOCH)
+            .expect("older/new databases should open and migrate");
+
+        assert_eq!(statuses.len(), MAIL_MIGRATIONS.len() + CALENDAR_MIGRATIONS.len());
+        assert!(statuses.iter().all(|status| status.applied && status.newly_applied));
+    }
+
+    #[test]
+    fn opening_a_database_written_by_a_newer_app_version_is_refused() {
+        let mut database = FlowDeskDatabase::new();
+        let mut mail_applier = NoopMigrationApplier;
+        let mut calendar_applier = NoopMigrationApplier;
+
+        let result = database.open(
+            StoredSchemaVersion(Some(CURRENT_SCHEMA_VERSION + 1)),
+            StoredSchemaVersion(None),
+            &mut mail_applier,
+            &mut calendar_applier,
+            SystemTime::UNIX_EPOCH,
+        );
+
+        assert!(matches!(result, Err(crate::error::FlowDeskError::Storage(_))));
+    }
+}
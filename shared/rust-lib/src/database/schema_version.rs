@@ -0,0 +1,56 @@
+//! Schema version enforcement, applied before migrations run.
+//!
+//! Each database file stores the schema version it was last written at in a
+//! `meta` table row (this crate models that row's value, not the table
+//! itself — see the [`database`](super) module doc comment on the backend
+//! not being wired up yet). Opening a database compares that stored version
+//! against [`CURRENT_SCHEMA_VERSION`]: an older file is forward-migrated as
+//! normal, but a file written by a *newer* app version is refused outright
+//! rather than silently opened, since an older app writing to a newer
+//! schema can corrupt rows it doesn't understand.
+
+use crate::error::{FlowDeskError, FlowDeskResult};
+
+/// The schema version this build of the application understands. Bump this
+/// whenever a migration is added to [`super::MAIL_MIGRATIONS`] or
+/// [`super::CALENDAR_MIGRATIONS`].
+pub const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+/// The `meta` table's `schema_version` row, as read back from the database
+/// file being opened. `None` means the file has no `meta` table yet (a
+/// brand-new database), which is always safe to proceed with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StoredSchemaVersion(pub Option<u32>);
+
+/// Compare `stored` against `current`, refusing to proceed if the database
+/// was written by a newer app version than this one.
+pub fn enforce_schema_version(stored: StoredSchemaVersion, current: u32) -> FlowDeskResult<()> {
+    match stored.0 {
+        Some(version) if version > current => Err(FlowDeskError::Storage(format!(
+            "database schema version {version} is newer than this app's schema version {current}; refusing to open it to avoid corrupting a schema this build doesn't understand. Update the app before opening this database."
+        ))),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_brand_new_database_with_no_meta_row_is_allowed() {
+        assert!(enforce_schema_version(StoredSchemaVersion(None), CURRENT_SCHEMA_VERSION).is_ok());
+    }
+
+    #[test]
+    fn an_older_or_equal_stored_version_is_allowed_so_migrations_can_run() {
+        assert!(enforce_schema_version(StoredSchemaVersion(Some(0)), CURRENT_SCHEMA_VERSION).is_ok());
+        assert!(enforce_schema_version(StoredSchemaVersion(Some(CURRENT_SCHEMA_VERSION)), CURRENT_SCHEMA_VERSION).is_ok());
+    }
+
+    #[test]
+    fn a_newer_stored_version_is_refused_with_a_storage_error() {
+        let result = enforce_schema_version(StoredSchemaVersion(Some(CURRENT_SCHEMA_VERSION + 1)), CURRENT_SCHEMA_VERSION);
+        assert!(matches!(result, Err(FlowDeskError::Storage(_))));
+    }
+}
@@ -0,0 +1,46 @@
+//! Shared error types for the Flow Desk Rust engine.
+
+use std::fmt;
+
+/// Top-level error type returned by every engine in this crate.
+#[derive(Debug)]
+pub enum FlowDeskError {
+    /// The remote mail/calendar server returned an error or closed the connection.
+    Connection(String),
+    /// A request could not be parsed or was otherwise malformed.
+    Protocol(String),
+    /// The requested item was not found.
+    NotFound(String),
+    /// The caller passed invalid arguments.
+    InvalidInput(String),
+    /// Something went wrong talking to local storage.
+    Storage(String),
+    /// An operation was not authorized (expired/invalid credentials).
+    Auth(String),
+    /// A generic I/O failure.
+    Io(std::io::Error),
+}
+
+impl fmt::Display for FlowDeskError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlowDeskError::Connection(msg) => write!(f, "connection error: {msg}"),
+            FlowDeskError::Protocol(msg) => write!(f, "protocol error: {msg}"),
+            FlowDeskError::NotFound(msg) => write!(f, "not found: {msg}"),
+            FlowDeskError::InvalidInput(msg) => write!(f, "invalid input: {msg}"),
+            FlowDeskError::Storage(msg) => write!(f, "storage error: {msg}"),
+            FlowDeskError::Auth(msg) => write!(f, "auth error: {msg}"),
+            FlowDeskError::Io(err) => write!(f, "io error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for FlowDeskError {}
+
+impl From<std::io::Error> for FlowDeskError {
+    fn from(err: std::io::Error) -> Self {
+        FlowDeskError::Io(err)
+    }
+}
+
+pub type FlowDeskResult<T> = Result<T, FlowDeskError>;
@@ -0,0 +1,227 @@
+//! C-ABI surface for embedding the search engine in a host that isn't
+//! Node (e.g. a Swift/Kotlin mobile shell talking to this crate directly
+//! instead of through NAPI).
+//!
+//! Every exported function except [`flowdesk_string_free`] catches panics
+//! at the boundary and returns an [`FfiErrorCode`] (as `i32`) instead of
+//! unwinding into the caller, which is
+//! undefined behavior across an `extern "C"` boundary. Any buffer this
+//! module hands back (`*mut c_char`) is owned by the caller once
+//! returned and must be released with [`flowdesk_string_free`] — never
+//! freed with the host's own allocator.
+
+use std::collections::HashMap;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FfiErrorCode {
+    Success = 0,
+    InvalidArgument = -1,
+    UnknownHandle = -2,
+    Malformed = -3,
+    Panic = -4,
+}
+
+/// An in-process document store, indexed by a caller-chosen handle so one
+/// host process can run several independent search engines.
+#[derive(Debug, Default)]
+struct SearchIndex {
+    documents: HashMap<String, String>,
+}
+
+impl SearchIndex {
+    fn query(&self, query: &str) -> Vec<(String, String, f64)> {
+        let needle = query.to_ascii_lowercase();
+        self.documents
+            .iter()
+            .filter(|(_, content)| content.to_ascii_lowercase().contains(&needle))
+            .map(|(id, content)| (id.clone(), content.clone(), 1.0))
+            .collect()
+    }
+}
+
+fn registry() -> &'static Mutex<HashMap<u64, SearchIndex>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<u64, SearchIndex>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn next_handle() -> u64 {
+    static NEXT_HANDLE: AtomicU64 = AtomicU64::new(1);
+    NEXT_HANDLE.fetch_add(1, Ordering::Relaxed)
+}
+
+fn extract_string_field(raw: &str, field: &str) -> Option<String> {
+    let needle = format!("\"{field}\"");
+    let field_start = raw.find(&needle)? + needle.len();
+    let after_colon = raw[field_start..].find(':')? + field_start + 1;
+    let rest = raw[after_colon..].trim_start();
+    let quote_start = rest.strip_prefix('"')?;
+    let end = quote_start.find('"')?;
+    Some(quote_start[..end].to_string())
+}
+
+fn json_escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// # Safety
+/// `ptr` must be either null or a valid, NUL-terminated C string.
+unsafe fn borrow_str<'a>(ptr: *const c_char) -> Option<&'a str> {
+    if ptr.is_null() {
+        return None;
+    }
+    CStr::from_ptr(ptr).to_str().ok()
+}
+
+fn catch_ffi_panic(work: impl FnOnce() -> FfiErrorCode) -> FfiErrorCode {
+    panic::catch_unwind(AssertUnwindSafe(work)).unwrap_or(FfiErrorCode::Panic)
+}
+
+/// Create a new search engine instance and write its handle to `out_handle`.
+/// `config_path` is reserved for loading a [`crate::config::SearchEngineConfig`]
+/// from disk once this binding is wired to real persistence; a null or
+/// empty path is accepted and uses defaults.
+///
+/// # Safety
+/// `out_handle` must point to valid, writable memory for a `u64`.
+#[no_mangle]
+pub unsafe extern "C" fn flowdesk_search_engine_init(_config_path: *const c_char, out_handle: *mut u64) -> i32 {
+    catch_ffi_panic(|| {
+        if out_handle.is_null() {
+            return FfiErrorCode::InvalidArgument;
+        }
+        let handle = next_handle();
+        registry().lock().unwrap().insert(handle, SearchIndex::default());
+        *out_handle = handle;
+        FfiErrorCode::Success
+    }) as i32
+}
+
+/// Index one document given as `{"id": "...", "content": "..."}`.
+///
+/// # Safety
+/// `document_json` must be null or a valid, NUL-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn flowdesk_search_index_document(handle: u64, document_json: *const c_char) -> i32 {
+    catch_ffi_panic(|| {
+        let Some(raw) = borrow_str(document_json) else {
+            return FfiErrorCode::InvalidArgument;
+        };
+        let (Some(id), Some(content)) = (extract_string_field(raw, "id"), extract_string_field(raw, "content")) else {
+            return FfiErrorCode::Malformed;
+        };
+
+        let mut registry = registry().lock().unwrap();
+        let Some(index) = registry.get_mut(&handle) else {
+            return FfiErrorCode::UnknownHandle;
+        };
+        index.documents.insert(id, content);
+        FfiErrorCode::Success
+    }) as i32
+}
+
+/// Run a query given as `{"query": "..."}` and write a JSON array of hits
+/// (`[{"id":"...","content":"...","relevance":1}, ...]`) to
+/// `out_result`. The caller owns the returned string and must release it
+/// with [`flowdesk_string_free`].
+///
+/// # Safety
+/// `query_json` must be null or a valid, NUL-terminated C string;
+/// `out_result` must point to valid, writable memory for a `*mut c_char`.
+#[no_mangle]
+pub unsafe extern "C" fn flowdesk_search_query(handle: u64, query_json: *const c_char, out_result: *mut *mut c_char) -> i32 {
+    catch_ffi_panic(|| {
+        if out_result.is_null() {
+            return FfiErrorCode::InvalidArgument;
+        }
+        let Some(raw) = borrow_str(query_json) else {
+            return FfiErrorCode::InvalidArgument;
+        };
+        let Some(query) = extract_string_field(raw, "query") else {
+            return FfiErrorCode::Malformed;
+        };
+
+        let registry = registry().lock().unwrap();
+        let Some(index) = registry.get(&handle) else {
+            return FfiErrorCode::UnknownHandle;
+        };
+
+        let hits_json = index
+            .query(&query)
+            .into_iter()
+            .map(|(id, content, relevance)| {
+                format!("{{\"id\":\"{}\",\"content\":\"{}\",\"relevance\":{relevance}}}", json_escape(&id), json_escape(&content))
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let Ok(result) = CString::new(format!("[{hits_json}]")) else {
+            return FfiErrorCode::Malformed;
+        };
+        *out_result = result.into_raw();
+        FfiErrorCode::Success
+    }) as i32
+}
+
+/// Release a string previously returned by this module (e.g. from
+/// [`flowdesk_search_query`]). Passing a null pointer is a no-op; passing
+/// any other pointer not obtained from this module is undefined behavior.
+///
+/// # Safety
+/// `ptr` must be either null or a value previously returned by a
+/// `flowdesk_*` function in this module, not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn flowdesk_string_free(ptr: *mut c_char) {
+    if !ptr.is_null() {
+        drop(CString::from_raw(ptr));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexes_and_queries_a_document_through_the_raw_c_abi() {
+        unsafe {
+            let mut handle = 0u64;
+            assert_eq!(flowdesk_search_engine_init(std::ptr::null(), &mut handle), FfiErrorCode::Success as i32);
+
+            let doc = CString::new(r#"{"id":"doc-1","content":"Flow Desk release notes"}"#).unwrap();
+            assert_eq!(flowdesk_search_index_document(handle, doc.as_ptr()), FfiErrorCode::Success as i32);
+
+            let query = CString::new(r#"{"query":"release"}"#).unwrap();
+            let mut out_result: *mut c_char = std::ptr::null_mut();
+            assert_eq!(flowdesk_search_query(handle, query.as_ptr(), &mut out_result), FfiErrorCode::Success as i32);
+
+            let result = CStr::from_ptr(out_result).to_str().unwrap();
+            assert!(result.contains("doc-1"));
+
+            flowdesk_string_free(out_result);
+        }
+    }
+
+    #[test]
+    fn querying_an_unknown_handle_is_reported_not_panicked() {
+        unsafe {
+            let query = CString::new(r#"{"query":"anything"}"#).unwrap();
+            let mut out_result: *mut c_char = std::ptr::null_mut();
+            let code = flowdesk_search_query(999_999, query.as_ptr(), &mut out_result);
+            assert_eq!(code, FfiErrorCode::UnknownHandle as i32);
+        }
+    }
+
+    #[test]
+    fn a_null_pointer_is_rejected_not_dereferenced() {
+        unsafe {
+            let mut handle = 0u64;
+            flowdesk_search_engine_init(std::ptr::null(), &mut handle);
+            let code = flowdesk_search_index_document(handle, std::ptr::null());
+            assert_eq!(code, FfiErrorCode::InvalidArgument as i32);
+        }
+    }
+}
@@ -0,0 +1,23 @@
+//! Flow Desk shared Rust engine.
+//!
+//! This crate implements the mail, calendar and search engines that power
+//! the desktop and mobile clients. It is compiled to a native module and
+//! exposed to the TypeScript layer through NAPI bindings (see
+//! `napi_bindings_minimal`).
+
+#[cfg(feature = "ai")]
+pub mod ai;
+pub mod calendar;
+pub mod cli;
+pub mod config;
+pub mod crypto;
+pub mod database;
+pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod mail;
+pub mod search;
+pub mod testing;
+pub mod transports;
+
+pub use error::{FlowDeskError, FlowDeskResult};
@@ -0,0 +1,61 @@
+//! Per-account display metadata: color and organization grouping, used by
+//! the UI to visually distinguish accounts in a unified inbox.
+
+use super::AccountId;
+
+/// A `#RRGGBB` hex color chosen for an account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountColor(String);
+
+impl AccountColor {
+    pub fn parse(hex: &str) -> Result<Self, String> {
+        let candidate = hex.trim();
+        if candidate.len() != 7 || !candidate.starts_with('#') {
+            return Err(format!("invalid color '{hex}': expected #RRGGBB"));
+        }
+        if !candidate[1..].chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("invalid color '{hex}': non-hex digits"));
+        }
+        Ok(Self(candidate.to_uppercase()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountMetadata {
+    pub account_id: AccountId,
+    pub color: AccountColor,
+    /// Free-form grouping label (e.g. "Work", "Personal") used to cluster
+    /// accounts in the sidebar.
+    pub organization: Option<String>,
+}
+
+/// A palette of visually distinct, accessible colors assigned round-robin
+/// to new accounts that don't pick their own.
+const DEFAULT_PALETTE: &[&str] = &["#4F46E5", "#059669", "#DC2626", "#D97706", "#7C3AED", "#0891B2"];
+
+pub fn default_color_for_index(index: usize) -> AccountColor {
+    AccountColor::parse(DEFAULT_PALETTE[index % DEFAULT_PALETTE.len()]).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_malformed_colors() {
+        assert!(AccountColor::parse("blue").is_err());
+        assert!(AccountColor::parse("#GGGGGG").is_err());
+        assert!(AccountColor::parse("#4F46E5").is_ok());
+    }
+
+    #[test]
+    fn default_colors_cycle_through_the_palette() {
+        let first = default_color_for_index(0);
+        let wrapped = default_color_for_index(DEFAULT_PALETTE.len());
+        assert_eq!(first, wrapped);
+    }
+}
@@ -0,0 +1,178 @@
+//! Account-management surface for [`super::MailEngine`]: add/list accounts,
+//! trigger a sync pass, read back synced messages, and send mail.
+//!
+//! This is the Rust side of what `napi_bindings_minimal` exposes to the
+//! TypeScript layer as `addMailAccount`/`listMailAccounts`/`syncMailAccount`/
+//! `getMailMessages`/`sendMail`. `napi` is only an optional dependency of
+//! this crate and no module here uses it, so there is no
+//! `#[napi]`-annotated wrapper here — these are the plain engine methods such a wrapper would call into,
+//! using this crate's ordinary [`FlowDeskResult`] error handling instead of
+//! a NAPI-specific error type.
+
+use super::{imap::AccountQuota, scheduled_send::NewMessage, AccountId, MessageId};
+use crate::error::{FlowDeskError, FlowDeskResult};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MailAccount {
+    pub id: AccountId,
+    pub email: String,
+    pub provider: String,
+    /// Mailbox storage quota, when the provider supports reporting one
+    /// (e.g. via the IMAP `QUOTA` extension). `None` until a sync pass
+    /// has fetched it, or for providers that don't report quota at all.
+    pub quota: Option<AccountQuota>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageSummary {
+    pub id: MessageId,
+    pub subject: String,
+    pub from: String,
+}
+
+/// In-memory account/message store backing [`super::MailEngine`]'s account
+/// API. The real implementation reads and writes the mail database; this
+/// holds the same shape of data so the engine's public methods have a
+/// stable contract to wrap once that database exists.
+#[derive(Debug, Default)]
+pub struct AccountStore {
+    accounts: HashMap<AccountId, MailAccount>,
+    messages: HashMap<AccountId, Vec<MessageSummary>>,
+    next_message_id: u64,
+}
+
+impl AccountStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn require_account(&self, account_id: &str) -> FlowDeskResult<()> {
+        if self.accounts.contains_key(account_id) {
+            Ok(())
+        } else {
+            Err(FlowDeskError::NotFound(format!("mail account '{account_id}' not found")))
+        }
+    }
+
+    pub fn add_account(&mut self, account: MailAccount) -> FlowDeskResult<AccountId> {
+        if self.accounts.contains_key(&account.id) {
+            return Err(FlowDeskError::InvalidInput(format!("mail account '{}' already exists", account.id)));
+        }
+        let id = account.id.clone();
+        self.accounts.insert(id.clone(), account);
+        Ok(id)
+    }
+
+    pub fn list_accounts(&self) -> Vec<MailAccount> {
+        self.accounts.values().cloned().collect()
+    }
+
+    /// Trigger a sync pass for `account_id`. This snapshot has no IMAP/API
+    /// polling loop to drive, so it just validates the account exists and
+    /// reports how many messages are currently held locally; a real sync
+    /// would fetch new messages and return how many were newly stored.
+    pub fn sync_account(&mut self, account_id: &str) -> FlowDeskResult<usize> {
+        self.require_account(account_id)?;
+        Ok(self.messages.get(account_id).map(Vec::len).unwrap_or(0))
+    }
+
+    pub fn messages(&self, account_id: &str) -> FlowDeskResult<&[MessageSummary]> {
+        self.require_account(account_id)?;
+        Ok(self.messages.get(account_id).map(Vec::as_slice).unwrap_or(&[]))
+    }
+
+    /// Record a freshly fetched quota for `account_id` (e.g. parsed via
+    /// [`super::imap::parse_quota_response`] during a sync pass).
+    pub fn set_quota(&mut self, account_id: &str, quota: AccountQuota) -> FlowDeskResult<()> {
+        self.require_account(account_id)?;
+        self.accounts.get_mut(account_id).expect("just checked require_account").quota = Some(quota);
+        Ok(())
+    }
+
+    /// Send `message` through `account_id`. There's no live SMTP/API
+    /// transport to send over in this snapshot, so this records the
+    /// message as sent locally and hands back the id it was assigned, the
+    /// same contract a real send would fulfil once wired to a provider.
+    pub fn send(&mut self, account_id: &str, message: NewMessage) -> FlowDeskResult<MessageId> {
+        self.require_account(account_id)?;
+        self.next_message_id += 1;
+        let id = format!("sent-{}", self.next_message_id);
+        self.messages.entry(account_id.to_string()).or_default().push(MessageSummary {
+            id: id.clone(),
+            subject: message.subject,
+            from: account_id.to_string(),
+        });
+        Ok(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account() -> MailAccount {
+        MailAccount {
+            id: "acct-1".to_string(),
+            email: "me@example.com".to_string(),
+            provider: "gmail".to_string(),
+            quota: None,
+        }
+    }
+
+    fn message() -> NewMessage {
+        NewMessage { to: vec!["team@example.com".to_string()], subject: "Launch".to_string(), body: "Go time.".to_string() }
+    }
+
+    #[test]
+    fn adding_the_same_account_twice_is_rejected() {
+        let mut store = AccountStore::new();
+        store.add_account(account()).unwrap();
+        assert!(matches!(store.add_account(account()), Err(FlowDeskError::InvalidInput(_))));
+        assert_eq!(store.list_accounts().len(), 1);
+    }
+
+    #[test]
+    fn operating_on_an_unknown_account_is_reported_not_panicked() {
+        let mut store = AccountStore::new();
+        assert!(matches!(store.sync_account("ghost"), Err(FlowDeskError::NotFound(_))));
+        assert!(matches!(store.messages("ghost"), Err(FlowDeskError::NotFound(_))));
+        assert!(matches!(store.send("ghost", message()), Err(FlowDeskError::NotFound(_))));
+    }
+
+    #[test]
+    fn sending_a_message_makes_it_visible_to_get_messages_and_sync() {
+        let mut store = AccountStore::new();
+        store.add_account(account()).unwrap();
+
+        let id = store.send("acct-1", message()).unwrap();
+        let messages = store.messages("acct-1").unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].id, id);
+        assert_eq!(messages[0].subject, "Launch");
+
+        assert_eq!(store.sync_account("acct-1").unwrap(), 1);
+    }
+
+    #[test]
+    fn setting_quota_on_an_unknown_account_is_reported_not_panicked() {
+        let mut store = AccountStore::new();
+        assert!(matches!(
+            store.set_quota("ghost", AccountQuota { used_kb: 1, limit_kb: 2 }),
+            Err(FlowDeskError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn quota_is_visible_on_the_account_after_being_set() {
+        let mut store = AccountStore::new();
+        store.add_account(account()).unwrap();
+        assert_eq!(store.list_accounts()[0].quota, None);
+
+        let quota = AccountQuota { used_kb: 10240, limit_kb: 102400 };
+        store.set_quota("acct-1", quota).unwrap();
+
+        let accounts = store.list_accounts();
+        assert_eq!(accounts[0].quota, Some(quota));
+    }
+}
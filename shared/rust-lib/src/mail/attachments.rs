@@ -0,0 +1,137 @@
+//! Resumable attachment downloads: a download interrupted partway through
+//! (app restart, dropped connection) picks up where it left off instead of
+//! restarting from byte zero.
+
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadProgress {
+    pub attachment_id: String,
+    pub total_bytes: u64,
+    pub downloaded_bytes: u64,
+}
+
+impl DownloadProgress {
+    pub fn is_complete(&self) -> bool {
+        self.downloaded_bytes >= self.total_bytes
+    }
+
+    /// Byte offset to resume a range request from (`Range: bytes=<offset>-`).
+    pub fn resume_offset(&self) -> u64 {
+        self.downloaded_bytes
+    }
+}
+
+/// Tracks in-flight and partial attachment downloads across app restarts.
+/// A real implementation persists this to disk; the bookkeeping logic is
+/// exercised here independent of storage.
+#[derive(Debug, Default)]
+pub struct AttachmentDownloadTracker {
+    progress: HashMap<String, DownloadProgress>,
+}
+
+impl AttachmentDownloadTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn start_or_resume(&mut self, attachment_id: &str, total_bytes: u64) -> DownloadProgress {
+        self.progress
+            .entry(attachment_id.to_string())
+            .or_insert_with(|| DownloadProgress {
+                attachment_id: attachment_id.to_string(),
+                total_bytes,
+                downloaded_bytes: 0,
+            })
+            .clone()
+    }
+
+    /// Record that `chunk_bytes` more bytes arrived; returns the updated
+    /// progress so the caller can write them to disk at `resume_offset()`
+    /// before this call (i.e. the offset captured prior to the write).
+    pub fn record_chunk(&mut self, attachment_id: &str, chunk_bytes: u64) -> Option<DownloadProgress> {
+        let entry = self.progress.get_mut(attachment_id)?;
+        entry.downloaded_bytes = (entry.downloaded_bytes + chunk_bytes).min(entry.total_bytes);
+        Some(entry.clone())
+    }
+
+    pub fn progress_for(&self, attachment_id: &str) -> Option<&DownloadProgress> {
+        self.progress.get(attachment_id)
+    }
+
+    /// Drop completed downloads so they don't accumulate forever.
+    pub fn clear_completed(&mut self) {
+        self.progress.retain(|_, p| !p.is_complete());
+    }
+}
+
+/// A destination for streamed attachment bytes (a file handle, in practice).
+/// Implementing this instead of returning `Vec<u8>` means the full
+/// attachment is never held in memory at once.
+pub trait ChunkSink {
+    fn write_chunk(&mut self, chunk: &[u8]) -> std::io::Result<()>;
+}
+
+impl ChunkSink for Vec<u8> {
+    fn write_chunk(&mut self, chunk: &[u8]) -> std::io::Result<()> {
+        self.extend_from_slice(chunk);
+        Ok(())
+    }
+}
+
+/// Stream `source` (an iterator of chunks, standing in for a network
+/// reader) into `sink` in bounded-size pieces, reporting progress as it
+/// goes instead of buffering the whole attachment.
+pub fn stream_attachment(
+    tracker: &mut AttachmentDownloadTracker,
+    attachment_id: &str,
+    total_bytes: u64,
+    source: impl IntoIterator<Item = Vec<u8>>,
+    sink: &mut impl ChunkSink,
+) -> std::io::Result<DownloadProgress> {
+    tracker.start_or_resume(attachment_id, total_bytes);
+
+    for chunk in source {
+        sink.write_chunk(&chunk)?;
+        tracker.record_chunk(attachment_id, chunk.len() as u64);
+    }
+
+    Ok(tracker.progress_for(attachment_id).cloned().unwrap_or(DownloadProgress {
+        attachment_id: attachment_id.to_string(),
+        total_bytes,
+        downloaded_bytes: 0,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resumes_from_last_downloaded_byte_after_interruption() {
+        let mut tracker = AttachmentDownloadTracker::new();
+        tracker.start_or_resume("att-1", 1_000_000);
+        tracker.record_chunk("att-1", 300_000);
+
+        // Simulate the app restarting: a fresh lookup still sees partial progress.
+        let resumed = tracker.start_or_resume("att-1", 1_000_000);
+        assert_eq!(resumed.resume_offset(), 300_000);
+        assert!(!resumed.is_complete());
+
+        tracker.record_chunk("att-1", 700_000);
+        let done = tracker.progress_for("att-1").unwrap();
+        assert!(done.is_complete());
+    }
+
+    #[test]
+    fn streams_chunks_without_buffering_the_whole_attachment() {
+        let mut tracker = AttachmentDownloadTracker::new();
+        let chunks: Vec<Vec<u8>> = vec![vec![0u8; 10], vec![0u8; 10], vec![0u8; 5]];
+        let mut sink: Vec<u8> = Vec::new();
+
+        let progress = stream_attachment(&mut tracker, "att-2", 25, chunks, &mut sink).unwrap();
+
+        assert_eq!(sink.len(), 25);
+        assert!(progress.is_complete());
+    }
+}
@@ -0,0 +1,183 @@
+//! Address-book extraction from mail: build up contacts' display names and
+//! interaction frequency purely from the `From`/`To`/`Cc` headers seen on
+//! sent and received messages, so the user never has to add contacts by
+//! hand. [`ContactBook::contains`] is what feeds
+//! [`super::focused_inbox::FocusSignals::sender_is_contact`].
+
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+/// A `"Display Name" <email>` participant parsed off a message header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MailAddress {
+    pub email: String,
+    pub display_name: Option<String>,
+}
+
+/// Split an RFC 5322 address list (`"A. Name" <a@x.com>, b@y.com`) into
+/// individual addresses. Quoted display names containing a literal `,`
+/// aren't handled — a full RFC 5322 parser would, but this covers the
+/// common unquoted case well enough for contact extraction.
+pub fn parse_address_list(header: &str) -> Vec<MailAddress> {
+    header.split(',').filter_map(|part| parse_address(part.trim())).collect()
+}
+
+fn parse_address(part: &str) -> Option<MailAddress> {
+    if part.is_empty() {
+        return None;
+    }
+
+    if let Some(start) = part.find('<') {
+        let end = part.find('>')?;
+        let email = part[start + 1..end].trim().to_lowercase();
+        if email.is_empty() {
+            return None;
+        }
+        let name = part[..start].trim().trim_matches('"').trim();
+        let display_name = if name.is_empty() { None } else { Some(name.to_string()) };
+        Some(MailAddress { email, display_name })
+    } else {
+        let email = part.to_lowercase();
+        if !email.contains('@') {
+            return None;
+        }
+        Some(MailAddress { email, display_name: None })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contact {
+    pub email: String,
+    pub display_name: Option<String>,
+    pub messages_sent_to: u32,
+    pub messages_received_from: u32,
+    pub last_seen: SystemTime,
+}
+
+/// Accumulates contacts across a mailbox's sent and received messages.
+#[derive(Debug, Default)]
+pub struct ContactBook {
+    contacts: HashMap<String, Contact>,
+}
+
+impl ContactBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a message the user sent to `recipients`.
+    pub fn record_sent(&mut self, recipients: &[MailAddress], seen_at: SystemTime) {
+        for addr in recipients {
+            self.touch(addr, seen_at, |contact| contact.messages_sent_to += 1);
+        }
+    }
+
+    /// Record a message the user received from `sender`.
+    pub fn record_received(&mut self, sender: &MailAddress, seen_at: SystemTime) {
+        self.touch(sender, seen_at, |contact| contact.messages_received_from += 1);
+    }
+
+    fn touch(&mut self, addr: &MailAddress, seen_at: SystemTime, bump: impl FnOnce(&mut Contact)) {
+        let email = addr.email.to_lowercase();
+        let contact = self.contacts.entry(email.clone()).or_insert_with(|| Contact {
+            email,
+            display_name: addr.display_name.clone(),
+            messages_sent_to: 0,
+            messages_received_from: 0,
+            last_seen: seen_at,
+        });
+        if addr.display_name.is_some() {
+            contact.display_name = addr.display_name.clone();
+        }
+        if seen_at > contact.last_seen {
+            contact.last_seen = seen_at;
+        }
+        bump(contact);
+    }
+
+    pub fn contact(&self, email: &str) -> Option<&Contact> {
+        self.contacts.get(&email.to_lowercase())
+    }
+
+    pub fn contains(&self, email: &str) -> bool {
+        self.contacts.contains_key(&email.to_lowercase())
+    }
+
+    /// All known contacts, most-contacted (sent + received) first, ties
+    /// broken alphabetically by email for a stable order.
+    pub fn contacts_by_frequency(&self) -> Vec<&Contact> {
+        let mut contacts: Vec<&Contact> = self.contacts.values().collect();
+        let frequency = |c: &Contact| c.messages_sent_to + c.messages_received_from;
+        contacts.sort_by(|a, b| frequency(b).cmp(&frequency(a)).then_with(|| a.email.cmp(&b.email)));
+        contacts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(seconds: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(seconds)
+    }
+
+    #[test]
+    fn parses_named_and_bare_addresses_from_a_list() {
+        let addresses = parse_address_list(r#""Ada Lovelace" <ada@example.com>, bob@example.com"#);
+        assert_eq!(
+            addresses,
+            vec![
+                MailAddress { email: "ada@example.com".to_string(), display_name: Some("Ada Lovelace".to_string()) },
+                MailAddress { email: "bob@example.com".to_string(), display_name: None },
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_entries_are_skipped_rather_than_panicking() {
+        let addresses = parse_address_list("not-an-address, , bob@example.com");
+        assert_eq!(addresses, vec![MailAddress { email: "bob@example.com".to_string(), display_name: None }]);
+    }
+
+    #[test]
+    fn sent_and_received_counts_accumulate_separately() {
+        let mut book = ContactBook::new();
+        let ada = MailAddress { email: "Ada@Example.com".to_string(), display_name: Some("Ada".to_string()) };
+
+        book.record_sent(std::slice::from_ref(&ada), at(1));
+        book.record_received(&ada, at(2));
+        book.record_received(&ada, at(3));
+
+        let contact = book.contact("ada@example.com").unwrap();
+        assert_eq!(contact.messages_sent_to, 1);
+        assert_eq!(contact.messages_received_from, 2);
+        assert_eq!(contact.last_seen, at(3));
+        assert!(book.contains("ADA@EXAMPLE.COM"));
+    }
+
+    #[test]
+    fn a_later_message_with_a_display_name_updates_the_contacts_name() {
+        let mut book = ContactBook::new();
+        book.record_received(&MailAddress { email: "bob@example.com".to_string(), display_name: None }, at(1));
+        book.record_received(
+            &MailAddress { email: "bob@example.com".to_string(), display_name: Some("Bob".to_string()) },
+            at(2),
+        );
+
+        assert_eq!(book.contact("bob@example.com").unwrap().display_name, Some("Bob".to_string()));
+    }
+
+    #[test]
+    fn contacts_are_ranked_by_total_interaction_frequency() {
+        let mut book = ContactBook::new();
+        let frequent = MailAddress { email: "frequent@example.com".to_string(), display_name: None };
+        let rare = MailAddress { email: "rare@example.com".to_string(), display_name: None };
+
+        book.record_received(&frequent, at(1));
+        book.record_sent(std::slice::from_ref(&frequent), at(2));
+        book.record_received(&rare, at(3));
+
+        let ranked: Vec<&str> = book.contacts_by_frequency().into_iter().map(|c| c.email.as_str()).collect();
+        assert_eq!(ranked, vec!["frequent@example.com", "rare@example.com"]);
+    }
+}
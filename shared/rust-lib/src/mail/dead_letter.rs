@@ -0,0 +1,129 @@
+//! Dead-letter handling for sync items that repeatedly fail to process.
+//!
+//! A single malformed message should not stall or repeatedly error a folder
+//! sync. After [`MAX_ATTEMPTS`] failures an item is recorded here and
+//! skipped on subsequent syncs until a caller explicitly retries it.
+
+use crate::mail::AccountId;
+use std::collections::HashMap;
+
+/// Number of processing failures before an item is dead-lettered.
+pub const MAX_ATTEMPTS: u32 = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeadLetter {
+    pub item_id: String,
+    pub attempts: u32,
+    pub last_error: String,
+}
+
+/// Outcome of feeding a failure into the tracker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeadLetterStatus {
+    /// Still under the retry threshold; sync should retry it next time.
+    Retryable,
+    /// Threshold reached; the item is now dead-lettered and should be skipped.
+    DeadLettered,
+}
+
+#[derive(Debug, Default)]
+pub struct DeadLetterQueue {
+    by_account: HashMap<AccountId, HashMap<String, DeadLetter>>,
+}
+
+impl DeadLetterQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a processing failure for `item_id`. Returns whether the item
+    /// has now crossed [`MAX_ATTEMPTS`] and become dead-lettered.
+    pub fn record_failure(&mut self, account_id: &str, item_id: &str, error: impl Into<String>) -> DeadLetterStatus {
+        let entry = self
+            .by_account
+            .entry(account_id.to_string())
+            .or_default()
+            .entry(item_id.to_string())
+            .or_insert_with(|| DeadLetter {
+                item_id: item_id.to_string(),
+                attempts: 0,
+                last_error: String::new(),
+            });
+
+        entry.attempts += 1;
+        entry.last_error = error.into();
+
+        if entry.attempts >= MAX_ATTEMPTS {
+            DeadLetterStatus::DeadLettered
+        } else {
+            DeadLetterStatus::Retryable
+        }
+    }
+
+    /// Whether `item_id` is currently dead-lettered and should be skipped
+    /// during a normal sync pass.
+    pub fn is_dead_lettered(&self, account_id: &str, item_id: &str) -> bool {
+        self.by_account
+            .get(account_id)
+            .and_then(|items| items.get(item_id))
+            .is_some_and(|d| d.attempts >= MAX_ATTEMPTS)
+    }
+
+    /// List dead-lettered items for manual inspection/retry.
+    pub fn list_dead_letter(&self, account_id: &str) -> Vec<DeadLetter> {
+        self.by_account
+            .get(account_id)
+            .map(|items| {
+                items
+                    .values()
+                    .filter(|d| d.attempts >= MAX_ATTEMPTS)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Clear an item's failure history so it will be retried on next sync.
+    pub fn retry(&mut self, account_id: &str, item_id: &str) {
+        if let Some(items) = self.by_account.get_mut(account_id) {
+            items.remove(item_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dead_letters_after_max_attempts_and_sync_can_proceed() {
+        let mut queue = DeadLetterQueue::new();
+        let mut status = DeadLetterStatus::Retryable;
+        for _ in 0..MAX_ATTEMPTS {
+            status = queue.record_failure("acct-1", "msg-42", "parse error: bad MIME boundary");
+        }
+
+        assert_eq!(status, DeadLetterStatus::DeadLettered);
+        assert!(queue.is_dead_lettered("acct-1", "msg-42"));
+
+        let dead = queue.list_dead_letter("acct-1");
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].attempts, MAX_ATTEMPTS);
+
+        // A fresh, unrelated item is unaffected and sync proceeds past the bad one.
+        assert!(!queue.is_dead_lettered("acct-1", "msg-43"));
+    }
+
+    #[test]
+    fn retry_clears_dead_letter_state() {
+        let mut queue = DeadLetterQueue::new();
+        for _ in 0..MAX_ATTEMPTS {
+            queue.record_failure("acct-1", "msg-42", "boom");
+        }
+        assert!(queue.is_dead_lettered("acct-1", "msg-42"));
+
+        queue.retry("acct-1", "msg-42");
+        assert!(!queue.is_dead_lettered("acct-1", "msg-42"));
+        assert!(queue.list_dead_letter("acct-1").is_empty());
+    }
+}
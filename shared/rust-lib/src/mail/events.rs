@@ -0,0 +1,44 @@
+//! Typed sync lifecycle events emitted to listeners (the NAPI bridge, CLI,
+//! etc.) in place of untyped status strings.
+
+use super::AccountId;
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncCompletedStats {
+    pub messages_added: u32,
+    pub messages_updated: u32,
+    pub messages_deleted: u32,
+    pub folders_synced: u32,
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncEvent {
+    Started { account_id: AccountId },
+    Completed { account_id: AccountId, stats: SyncCompletedStats },
+    Failed { account_id: AccountId, reason: String },
+}
+
+impl SyncCompletedStats {
+    pub fn total_changes(&self) -> u32 {
+        self.messages_added + self.messages_updated + self.messages_deleted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn total_changes_sums_all_categories() {
+        let stats = SyncCompletedStats {
+            messages_added: 3,
+            messages_updated: 2,
+            messages_deleted: 1,
+            folders_synced: 4,
+            duration: Duration::from_secs(5),
+        };
+        assert_eq!(stats.total_changes(), 6);
+    }
+}
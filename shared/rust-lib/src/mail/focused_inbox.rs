@@ -0,0 +1,73 @@
+//! "Focused inbox" scoring: rank incoming mail by how likely the user is to
+//! care about it, so the UI can split Focused/Other.
+
+/// Signals used to score a message. All are cheap to compute from metadata
+/// already available at sync time (no body analysis required).
+#[derive(Debug, Clone, Default)]
+pub struct FocusSignals {
+    /// The user has replied to this sender before.
+    pub replied_to_sender_before: bool,
+    /// The sender is in the user's contacts/address book.
+    pub sender_is_contact: bool,
+    /// The message was addressed directly to the user (not just CC'd or via a list).
+    pub addressed_directly: bool,
+    /// The message came through a mailing list or bulk-mail header
+    /// (`List-Unsubscribe`, `Precedence: bulk`).
+    pub is_bulk_mail: bool,
+    /// Fraction (0.0-1.0) of this sender's past messages the user has opened.
+    pub historical_open_rate: f32,
+}
+
+/// A score in `[0.0, 1.0]`; messages at or above [`FOCUSED_THRESHOLD`] go to
+/// the Focused tab.
+pub const FOCUSED_THRESHOLD: f32 = 0.5;
+
+pub fn score_message(signals: &FocusSignals) -> f32 {
+    if signals.is_bulk_mail && !signals.sender_is_contact {
+        return 0.05;
+    }
+
+    let mut score = 0.2;
+    if signals.replied_to_sender_before {
+        score += 0.35;
+    }
+    if signals.sender_is_contact {
+        score += 0.25;
+    }
+    if signals.addressed_directly {
+        score += 0.15;
+    }
+    score += signals.historical_open_rate.clamp(0.0, 1.0) * 0.25;
+
+    score.clamp(0.0, 1.0)
+}
+
+pub fn is_focused(signals: &FocusSignals) -> bool {
+    score_message(signals) >= FOCUSED_THRESHOLD
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_contact_reply_is_focused() {
+        let signals = FocusSignals {
+            replied_to_sender_before: true,
+            sender_is_contact: true,
+            addressed_directly: true,
+            is_bulk_mail: false,
+            historical_open_rate: 0.9,
+        };
+        assert!(is_focused(&signals));
+    }
+
+    #[test]
+    fn bulk_newsletter_from_unknown_sender_is_not_focused() {
+        let signals = FocusSignals {
+            is_bulk_mail: true,
+            ..Default::default()
+        };
+        assert!(!is_focused(&signals));
+    }
+}
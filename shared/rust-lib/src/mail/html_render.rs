@@ -0,0 +1,77 @@
+//! Size limits for rendering HTML email bodies, so a pathological or
+//! abusive message can't hang the renderer or blow up memory.
+
+/// Default cap on how much HTML is handed to the renderer.
+pub const DEFAULT_MAX_HTML_BYTES: usize = 2 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HtmlRenderLimits {
+    pub max_bytes: usize,
+}
+
+impl Default for HtmlRenderLimits {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_MAX_HTML_BYTES,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderableHtml {
+    pub html: String,
+    pub truncated: bool,
+}
+
+/// Truncate `html` to `limits.max_bytes`, cutting on a UTF-8 char boundary
+/// and dropping a tag the cut split in half, so the output never ends in a
+/// broken tag start. Elements opened before the cut are left unclosed.
+pub fn truncate_for_render(html: &str, limits: HtmlRenderLimits) -> RenderableHtml {
+    if html.len() <= limits.max_bytes {
+        return RenderableHtml {
+            html: html.to_string(),
+            truncated: false,
+        };
+    }
+
+    let mut cut = limits.max_bytes;
+    while !html.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    let mut truncated = html[..cut].to_string();
+
+    // If the cut landed inside a tag (`<div cl|ass=...`), back up to the
+    // last `<` so we don't emit a broken tag start.
+    if let Some(unclosed_tag_start) = truncated.rfind('<') {
+        if !truncated[unclosed_tag_start..].contains('>') {
+            truncated.truncate(unclosed_tag_start);
+        }
+    }
+
+    RenderableHtml {
+        html: truncated,
+        truncated: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaves_small_html_untouched() {
+        let result = truncate_for_render("<p>hi</p>", HtmlRenderLimits::default());
+        assert!(!result.truncated);
+        assert_eq!(result.html, "<p>hi</p>");
+    }
+
+    #[test]
+    fn truncates_and_drops_a_tag_split_by_the_cut() {
+        let html = format!("<p>{}</p><div clas", "x".repeat(50));
+        let limits = HtmlRenderLimits { max_bytes: 55 };
+        let result = truncate_for_render(&html, limits);
+        assert!(result.truncated);
+        assert!(!result.html.ends_with("<div clas"));
+        assert!(result.html.len() <= 55);
+    }
+}
@@ -0,0 +1,133 @@
+//! IMAP authentication mechanisms, including OAuth2 via `XOAUTH2` (Google/
+//! Microsoft) alongside plain `LOGIN`.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Credentials {
+    Password { user: String, password: String },
+    OAuth2 { user: String, access_token: String },
+}
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Standard (not base64url) encoding, with `=` padding. See
+/// [`crate::mail::smtp::dkim`] for the same hand-rolled approach applied to
+/// DKIM's `bh=`/`b=` tags — this crate has no `base64` dependency.
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = chunk.get(1).copied();
+        let b2 = chunk.get(2).copied();
+
+        out.push(BASE64_ALPHABET[(b0 >> 2) as usize] as char);
+        out.push(BASE64_ALPHABET[(((b0 & 0b11) << 4) | (b1.unwrap_or(0) >> 4)) as usize] as char);
+        out.push(match b1 {
+            Some(b1) => BASE64_ALPHABET[(((b1 & 0b1111) << 2) | (b2.unwrap_or(0) >> 6)) as usize] as char,
+            None => '=',
+        });
+        out.push(match b2 {
+            Some(b2) => BASE64_ALPHABET[(b2 & 0b0011_1111) as usize] as char,
+            None => '=',
+        });
+    }
+    out
+}
+
+/// Only exercised by this module's own tests — nothing in this crate yet
+/// decodes a server's base64-encoded response (see [`is_xoauth2_failure_response`],
+/// which already takes pre-decoded JSON).
+#[cfg(test)]
+fn base64_decode(encoded: &str) -> Result<Vec<u8>, String> {
+    fn value(byte: u8) -> Option<u8> {
+        BASE64_ALPHABET.iter().position(|&b| b == byte).map(|i| i as u8)
+    }
+
+    let stripped = encoded.trim_end_matches('=');
+    let mut bits: u32 = 0;
+    let mut bit_count = 0;
+    let mut out = Vec::with_capacity(stripped.len() * 3 / 4);
+
+    for byte in stripped.bytes() {
+        let v = value(byte).ok_or_else(|| format!("invalid base64 byte: {byte:#x}"))?;
+        bits = (bits << 6) | v as u32;
+        bit_count += 6;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            out.push((bits >> bit_count) as u8);
+        }
+    }
+
+    Ok(out)
+}
+
+/// Build the base64-encoded `XOAUTH2` SASL initial response per Google's/
+/// Microsoft's spec:
+/// `user=<user>\x01auth=Bearer <token>\x01\x01`
+pub fn build_xoauth2_initial_response(user: &str, access_token: &str) -> String {
+    let raw = format!("user={user}\x01auth=Bearer {access_token}\x01\x01");
+    base64_encode(raw.as_bytes())
+}
+
+/// Build the `AUTHENTICATE XOAUTH2` command, with the initial response sent
+/// inline per RFC 4959 (SASL-IR) instead of waiting for a server challenge.
+pub fn build_authenticate_command(credentials: &Credentials) -> String {
+    match credentials {
+        Credentials::Password { user, password } => {
+            format!("LOGIN \"{}\" \"{}\"", escape_quoted(user), escape_quoted(password))
+        }
+        Credentials::OAuth2 { user, access_token } => {
+            format!("AUTHENTICATE XOAUTH2 {}", build_xoauth2_initial_response(user, access_token))
+        }
+    }
+}
+
+/// Parse the (base64-encoded) error response the server sends back on an
+/// XOAUTH2 failure, e.g. `{"status":"400","schemes":"bearer","scope":"..."}`.
+pub fn is_xoauth2_failure_response(decoded_json: &str) -> bool {
+    decoded_json.contains("\"status\"")
+}
+
+fn escape_quoted(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_xoauth2_initial_response() {
+        let response = build_xoauth2_initial_response("user@example.com", "ya29.token");
+        let decoded = String::from_utf8(base64_decode(&response).unwrap()).unwrap();
+        assert_eq!(decoded, "user=user@example.com\x01auth=Bearer ya29.token\x01\x01");
+    }
+
+    #[test]
+    fn base64_round_trips_through_encode_and_decode() {
+        for input in ["", "f", "fo", "foo", "foob", "fooba", "foobar"] {
+            let encoded = base64_encode(input.as_bytes());
+            let decoded = base64_decode(&encoded).unwrap();
+            assert_eq!(decoded, input.as_bytes());
+        }
+    }
+
+    #[test]
+    fn authenticate_command_uses_xoauth2_for_oauth_credentials() {
+        let creds = Credentials::OAuth2 {
+            user: "user@example.com".to_string(),
+            access_token: "ya29.token".to_string(),
+        };
+        let command = build_authenticate_command(&creds);
+        assert!(command.starts_with("AUTHENTICATE XOAUTH2 "));
+    }
+
+    #[test]
+    fn authenticate_command_uses_login_for_password_credentials() {
+        let creds = Credentials::Password {
+            user: "user@example.com".to_string(),
+            password: "hunter2".to_string(),
+        };
+        let command = build_authenticate_command(&creds);
+        assert_eq!(command, "LOGIN \"user@example.com\" \"hunter2\"");
+    }
+}
@@ -0,0 +1,76 @@
+//! Minimal `BODYSTRUCTURE` model, used to decide what to fetch for list
+//! views without downloading the full message body.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BodyPart {
+    /// IMAP part number, e.g. "1", "1.2".
+    pub part_number: String,
+    pub media_type: String,
+    pub media_subtype: String,
+    pub size_bytes: u32,
+    pub has_attachment_disposition: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BodyStructure {
+    pub parts: Vec<BodyPart>,
+}
+
+impl BodyStructure {
+    /// The part to fetch for a list preview: the smallest `text/plain` part,
+    /// falling back to the smallest other `text/*` part. Attachments are
+    /// never chosen; there is no upper size limit.
+    pub fn preview_part(&self) -> Option<&BodyPart> {
+        self.parts
+            .iter()
+            .filter(|p| !p.has_attachment_disposition && p.media_type.eq_ignore_ascii_case("text"))
+            .min_by_key(|p| (p.media_subtype.to_lowercase() != "plain", p.size_bytes))
+    }
+
+    /// Total size of every part flagged as an attachment, without fetching
+    /// any of their bytes.
+    pub fn attachment_bytes(&self) -> u64 {
+        self.parts
+            .iter()
+            .filter(|p| p.has_attachment_disposition)
+            .map(|p| p.size_bytes as u64)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefers_smallest_text_plain_part_for_preview() {
+        let structure = BodyStructure {
+            parts: vec![
+                BodyPart {
+                    part_number: "1".into(),
+                    media_type: "text".into(),
+                    media_subtype: "html".into(),
+                    size_bytes: 500,
+                    has_attachment_disposition: false,
+                },
+                BodyPart {
+                    part_number: "2".into(),
+                    media_type: "text".into(),
+                    media_subtype: "plain".into(),
+                    size_bytes: 200,
+                    has_attachment_disposition: false,
+                },
+                BodyPart {
+                    part_number: "3".into(),
+                    media_type: "application".into(),
+                    media_subtype: "pdf".into(),
+                    size_bytes: 50_000,
+                    has_attachment_disposition: true,
+                },
+            ],
+        };
+
+        assert_eq!(structure.preview_part().unwrap().part_number, "2");
+        assert_eq!(structure.attachment_bytes(), 50_000);
+    }
+}
@@ -0,0 +1,92 @@
+//! Apply one IMAP operation across a batch of messages, reporting which
+//! UIDs failed rather than aborting the whole batch on the first error —
+//! mirrors `search::partial`'s succeed-what-you-can aggregation for
+//! cross-provider search.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkAction {
+    Delete,
+    SetFlag { flag: String, value: bool },
+    MoveToFolder(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkFailure {
+    pub uid: u32,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BulkOperationResult {
+    pub succeeded: Vec<u32>,
+    pub failures: Vec<BulkFailure>,
+}
+
+impl BulkOperationResult {
+    pub fn is_partial_failure(&self) -> bool {
+        !self.succeeded.is_empty() && !self.failures.is_empty()
+    }
+
+    pub fn all_failed(&self) -> bool {
+        self.succeeded.is_empty() && !self.failures.is_empty()
+    }
+}
+
+/// Run `action` against every uid in `uids`, one IMAP command per uid — a
+/// server isn't guaranteed to support batched `UID STORE`/`COPY`/`MOVE`
+/// across an arbitrary, non-contiguous set. The real implementation issues
+/// each command over a live connection; `execute` is a closure so the
+/// batching/reporting logic here is testable without one.
+pub fn bulk_operation(
+    uids: &[u32],
+    action: &BulkAction,
+    mut execute: impl FnMut(u32, &BulkAction) -> Result<(), String>,
+) -> BulkOperationResult {
+    let mut result = BulkOperationResult::default();
+    for &uid in uids {
+        match execute(uid, action) {
+            Ok(()) => result.succeeded.push(uid),
+            Err(reason) => result.failures.push(BulkFailure { uid, reason }),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_uid_succeeds_when_the_command_never_fails() {
+        let result = bulk_operation(&[1, 2, 3], &BulkAction::Delete, |_uid, _action| Ok(()));
+        assert_eq!(result.succeeded, vec![1, 2, 3]);
+        assert!(result.failures.is_empty());
+        assert!(!result.is_partial_failure());
+    }
+
+    #[test]
+    fn a_failing_uid_is_reported_without_aborting_the_rest() {
+        let result = bulk_operation(&[1, 2, 3], &BulkAction::MoveToFolder("Archive".to_string()), |uid, _action| {
+            if uid == 2 {
+                Err("server disconnected".to_string())
+            } else {
+                Ok(())
+            }
+        });
+
+        assert_eq!(result.succeeded, vec![1, 3]);
+        assert_eq!(result.failures, vec![BulkFailure { uid: 2, reason: "server disconnected".to_string() }]);
+        assert!(result.is_partial_failure());
+    }
+
+    #[test]
+    fn every_uid_failing_is_reported_as_all_failed_not_partial() {
+        let result =
+            bulk_operation(&[1, 2], &BulkAction::SetFlag { flag: "\\Seen".to_string(), value: true }, |_uid, _action| {
+                Err("permission denied".to_string())
+            });
+
+        assert!(result.all_failed());
+        assert!(!result.is_partial_failure());
+    }
+}
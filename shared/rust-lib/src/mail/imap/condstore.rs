@@ -0,0 +1,112 @@
+//! Incremental sync via CONDSTORE/QRESYNC (RFC 7162), tracking
+//! `HIGHESTMODSEQ` per folder so a resync only asks for what changed.
+
+use std::collections::HashMap;
+
+/// Per-folder sync state needed to resume with QRESYNC.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FolderSyncState {
+    pub uid_validity: u32,
+    pub highest_modseq: u64,
+}
+
+/// A change reported by an incremental `FETCH ... (CHANGEDSINCE ...)` / QRESYNC.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FolderChange {
+    Updated { uid: u32, modseq: u64 },
+    Expunged { uid: u32 },
+}
+
+#[derive(Debug, Default)]
+pub struct CondstoreTracker {
+    state_by_folder: HashMap<String, FolderSyncState>,
+}
+
+impl CondstoreTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn state_for(&self, folder: &str) -> Option<&FolderSyncState> {
+        self.state_by_folder.get(folder)
+    }
+
+    /// Build the `SELECT` parameters needed to resume sync for `folder`.
+    /// Returns `None` if we have no prior state (full sync required).
+    pub fn qresync_params(&self, folder: &str) -> Option<(u32, u64)> {
+        self.state_for(folder)
+            .map(|s| (s.uid_validity, s.highest_modseq))
+    }
+
+    /// Apply a batch of changes (as returned by a CONDSTORE `FETCH` using
+    /// `CHANGEDSINCE <highest_modseq>`) and advance the tracked watermark.
+    /// If `uid_validity` differs from what we last saw, the folder's
+    /// identifiers were invalidated server-side and a full resync is needed.
+    pub fn apply_changes(
+        &mut self,
+        folder: &str,
+        uid_validity: u32,
+        changes: &[FolderChange],
+    ) -> bool {
+        let needs_full_resync = self
+            .state_for(folder)
+            .is_some_and(|s| s.uid_validity != uid_validity);
+
+        let max_modseq = changes
+            .iter()
+            .filter_map(|c| match c {
+                FolderChange::Updated { modseq, .. } => Some(*modseq),
+                FolderChange::Expunged { .. } => None,
+            })
+            .max()
+            .unwrap_or(0);
+
+        let entry = self.state_by_folder.entry(folder.to_string()).or_default();
+        if needs_full_resync {
+            *entry = FolderSyncState {
+                uid_validity,
+                highest_modseq: max_modseq,
+            };
+        } else {
+            entry.uid_validity = uid_validity;
+            entry.highest_modseq = entry.highest_modseq.max(max_modseq);
+        }
+
+        needs_full_resync
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_highest_modseq_across_incremental_syncs() {
+        let mut tracker = CondstoreTracker::new();
+        assert!(tracker.qresync_params("INBOX").is_none());
+
+        tracker.apply_changes(
+            "INBOX",
+            1001,
+            &[FolderChange::Updated { uid: 5, modseq: 42 }],
+        );
+        assert_eq!(tracker.qresync_params("INBOX"), Some((1001, 42)));
+
+        tracker.apply_changes(
+            "INBOX",
+            1001,
+            &[FolderChange::Updated { uid: 6, modseq: 50 }],
+        );
+        assert_eq!(tracker.qresync_params("INBOX"), Some((1001, 50)));
+    }
+
+    #[test]
+    fn uid_validity_change_forces_full_resync() {
+        let mut tracker = CondstoreTracker::new();
+        tracker.apply_changes("INBOX", 1001, &[FolderChange::Updated { uid: 5, modseq: 42 }]);
+
+        let needs_resync = tracker.apply_changes("INBOX", 2002, &[]);
+        assert!(needs_resync);
+        assert_eq!(tracker.qresync_params("INBOX"), Some((2002, 0)));
+    }
+}
@@ -0,0 +1,94 @@
+//! Conversion from a raw fetched IMAP message into our internal message
+//! representation.
+
+use crate::mail::MessageId;
+
+/// Internal representation of a fetched message. Only the fields relevant to
+/// threading/identity are modeled here; body/attachments live elsewhere.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ConvertedMessage {
+    pub id: MessageId,
+    pub subject: String,
+    /// The message's own `Message-ID` header, exactly as the server sent it
+    /// (including angle brackets). Needed for threading and for replying
+    /// with a correct `In-Reply-To`.
+    pub message_id_header: Option<String>,
+    /// The `References` header, split into individual message-ids in order.
+    pub references: Vec<String>,
+    /// The `In-Reply-To` header, if present.
+    pub in_reply_to: Option<String>,
+}
+
+/// A minimal view of the headers returned by an IMAP `FETCH ... (BODY[HEADER.FIELDS (...)])`.
+pub struct RawHeaders<'a> {
+    pub subject: Option<&'a str>,
+    pub message_id: Option<&'a str>,
+    pub references: Option<&'a str>,
+    pub in_reply_to: Option<&'a str>,
+}
+
+/// Build a [`ConvertedMessage`], preserving the real `Message-ID` and
+/// `References` headers from the server instead of synthesizing our own —
+/// losing these breaks threading against the rest of the world.
+pub fn convert_imap_message(uid: u32, headers: RawHeaders<'_>) -> ConvertedMessage {
+    ConvertedMessage {
+        id: uid.to_string(),
+        subject: headers.subject.unwrap_or_default().to_string(),
+        message_id_header: headers.message_id.map(normalize_msgid),
+        references: headers
+            .references
+            .map(split_msgid_list)
+            .unwrap_or_default(),
+        in_reply_to: headers.in_reply_to.map(normalize_msgid),
+    }
+}
+
+fn normalize_msgid(raw: &str) -> String {
+    raw.trim().to_string()
+}
+
+fn split_msgid_list(raw: &str) -> Vec<String> {
+    raw.split_whitespace().map(|s| s.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preserves_real_message_id_and_references() {
+        let headers = RawHeaders {
+            subject: Some("Re: Launch plan"),
+            message_id: Some("<abc123@mail.example.com>"),
+            references: Some("<root@example.com> <reply1@example.com>"),
+            in_reply_to: Some("<reply1@example.com>"),
+        };
+
+        let converted = convert_imap_message(42, headers);
+
+        assert_eq!(converted.id, "42");
+        assert_eq!(
+            converted.message_id_header.as_deref(),
+            Some("<abc123@mail.example.com>")
+        );
+        assert_eq!(
+            converted.references,
+            vec!["<root@example.com>".to_string(), "<reply1@example.com>".to_string()]
+        );
+        assert_eq!(converted.in_reply_to.as_deref(), Some("<reply1@example.com>"));
+    }
+
+    #[test]
+    fn missing_headers_leave_identity_fields_empty_not_synthesized() {
+        let headers = RawHeaders {
+            subject: None,
+            message_id: None,
+            references: None,
+            in_reply_to: None,
+        };
+
+        let converted = convert_imap_message(7, headers);
+        assert!(converted.message_id_header.is_none());
+        assert!(converted.references.is_empty());
+    }
+}
@@ -0,0 +1,70 @@
+//! Lightweight partial fetches used to populate message lists without
+//! downloading full bodies.
+
+/// Number of bytes of body text to pull for a list preview.
+pub const SNIPPET_FETCH_BYTES: u32 = 2048;
+
+/// Target length of the rendered snippet shown in list rows.
+pub const SNIPPET_MAX_CHARS: usize = 200;
+
+/// Build the `FETCH` command for a bandwidth-minimizing preview: only the
+/// first [`SNIPPET_FETCH_BYTES`] bytes of the text body, via `BODY.PEEK`
+/// (which, unlike plain `BODY`, does not mark the message `\Seen`).
+pub fn build_preview_fetch_command(uid: u32) -> String {
+    format!("UID FETCH {uid} BODY.PEEK[TEXT]<0.{SNIPPET_FETCH_BYTES}>")
+}
+
+/// Turn the partial text returned by [`build_preview_fetch_command`] into a
+/// plain-text snippet suitable for list rendering.
+///
+/// Whitespace is collapsed and the result is truncated to
+/// [`SNIPPET_MAX_CHARS`] characters (on a char boundary) since the partial
+/// fetch may have cut a word or HTML tag mid-way.
+pub fn snippet_from_partial_body(partial_body: &str) -> String {
+    let stripped = strip_simple_html(partial_body);
+    let collapsed: String = stripped.split_whitespace().collect::<Vec<_>>().join(" ");
+    truncate_chars(&collapsed, SNIPPET_MAX_CHARS)
+}
+
+fn strip_simple_html(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut in_tag = false;
+    for c in input.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out
+}
+
+fn truncate_chars(input: &str, max_chars: usize) -> String {
+    if input.chars().count() <= max_chars {
+        return input.to_string();
+    }
+    input.chars().take(max_chars).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn issues_peek_partial_fetch_not_full_body() {
+        let command = build_preview_fetch_command(42);
+        assert_eq!(command, "UID FETCH 42 BODY.PEEK[TEXT]<0.2048>");
+        assert!(!command.contains("BODY[TEXT]") || command.contains("BODY.PEEK"));
+        assert!(command.contains("BODY.PEEK"), "must not trigger \\Seen or a full download");
+    }
+
+    #[test]
+    fn builds_snippet_without_full_body() {
+        let partial = "  Hi team,\n\nHere is the <b>weekly</b> update on the project status. ".repeat(5);
+        let snippet = snippet_from_partial_body(&partial);
+        assert!(snippet.len() <= SNIPPET_MAX_CHARS);
+        assert!(!snippet.contains('<'));
+        assert!(snippet.starts_with("Hi team,"));
+    }
+}
@@ -0,0 +1,90 @@
+//! IMAP IDLE (RFC 2177) push notifications: untagged `EXISTS`/`EXPUNGE`/
+//! `RECENT` lines received while idling are parsed into [`SyncChange`]s and
+//! forwarded to a channel, so a listener can react to new mail without
+//! polling the folder.
+
+use std::sync::mpsc::Sender;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncChange {
+    /// The folder now has this many messages (`* <n> EXISTS`).
+    Exists(u32),
+    /// The message at this sequence number was expunged (`* <n> EXPUNGE`).
+    Expunge(u32),
+    /// This many messages in the folder are recent (`* <n> RECENT`).
+    Recent(u32),
+}
+
+/// Parse a single untagged line received while idling. Returns `None` for
+/// lines that aren't one of the push notifications IDLE cares about (e.g.
+/// the `+ idling` continuation response).
+pub fn parse_idle_line(line: &str) -> Option<SyncChange> {
+    let rest = line.trim().strip_prefix("* ")?;
+    let mut parts = rest.splitn(2, ' ');
+    let number: u32 = parts.next()?.parse().ok()?;
+    match parts.next()?.trim() {
+        "EXISTS" => Some(SyncChange::Exists(number)),
+        "EXPUNGE" => Some(SyncChange::Expunge(number)),
+        "RECENT" => Some(SyncChange::Recent(number)),
+        _ => None,
+    }
+}
+
+/// Feed raw lines received while an `IDLE` command is outstanding, parsing
+/// and forwarding each push notification to `sender`. The real
+/// implementation reads lines off the live IMAP socket between `IDLE` and
+/// `DONE`; taking an iterator here lets it be driven by a socket reader or
+/// a test fixture identically. Returns the number of changes forwarded,
+/// stopping early if the receiver has gone away.
+pub fn pump_idle_lines<'a>(lines: impl IntoIterator<Item = &'a str>, sender: &Sender<SyncChange>) -> usize {
+    let mut forwarded = 0;
+    for line in lines {
+        if let Some(change) = parse_idle_line(line) {
+            if sender.send(change).is_err() {
+                break;
+            }
+            forwarded += 1;
+        }
+    }
+    forwarded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    #[test]
+    fn parses_exists_expunge_and_recent() {
+        assert_eq!(parse_idle_line("* 12 EXISTS"), Some(SyncChange::Exists(12)));
+        assert_eq!(parse_idle_line("* 3 EXPUNGE"), Some(SyncChange::Expunge(3)));
+        assert_eq!(parse_idle_line("* 1 RECENT"), Some(SyncChange::Recent(1)));
+    }
+
+    #[test]
+    fn ignores_lines_that_are_not_push_notifications() {
+        assert_eq!(parse_idle_line("+ idling"), None);
+        assert_eq!(parse_idle_line("* OK still here"), None);
+        assert_eq!(parse_idle_line(""), None);
+    }
+
+    #[test]
+    fn pump_forwards_every_change_to_the_channel() {
+        let (tx, rx) = mpsc::channel();
+        let lines = ["* 12 EXISTS", "+ idling", "* 5 EXPUNGE"];
+
+        let forwarded = pump_idle_lines(lines, &tx);
+
+        assert_eq!(forwarded, 2);
+        assert_eq!(rx.recv().unwrap(), SyncChange::Exists(12));
+        assert_eq!(rx.recv().unwrap(), SyncChange::Expunge(5));
+    }
+
+    #[test]
+    fn stops_pumping_once_the_receiver_is_dropped() {
+        let (tx, rx) = mpsc::channel();
+        drop(rx);
+        let lines = ["* 12 EXISTS", "* 13 EXISTS"];
+        assert_eq!(pump_idle_lines(lines, &tx), 0);
+    }
+}
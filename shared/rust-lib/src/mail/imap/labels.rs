@@ -0,0 +1,81 @@
+//! Labels backed by IMAP keyword flags (RFC 3501 §2.3.2) so `add_label`
+//! actually persists server-side instead of only updating local state.
+
+/// An IMAP keyword is a free-form atom; we namespace ours to avoid clashing
+/// with system flags or keywords other clients set.
+const LABEL_PREFIX: &str = "FlowDeskLabel_";
+
+/// Turn a user-facing label name into the IMAP keyword that stores it.
+/// IMAP atoms can't contain spaces or most punctuation, so unsupported
+/// characters are percent-escaped.
+pub fn label_to_keyword(label: &str) -> String {
+    let mut encoded = String::with_capacity(label.len());
+    for b in label.bytes() {
+        match b {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_' | b'-' => encoded.push(b as char),
+            _ => encoded.push_str(&format!("%{b:02X}")),
+        }
+    }
+    format!("{LABEL_PREFIX}{encoded}")
+}
+
+/// Recover the user-facing label name from an IMAP keyword, or `None` if
+/// the keyword isn't one of ours.
+pub fn keyword_to_label(keyword: &str) -> Option<String> {
+    let encoded = keyword.strip_prefix(LABEL_PREFIX)?;
+    let mut decoded = String::with_capacity(encoded.len());
+    let mut chars = encoded.chars();
+    while let Some(c) = chars.next() {
+        if c == '%' {
+            let hex: String = chars.by_ref().take(2).collect();
+            let byte = u8::from_str_radix(&hex, 16).ok()?;
+            decoded.push(byte as char);
+        } else {
+            decoded.push(c);
+        }
+    }
+    Some(decoded)
+}
+
+/// Build the `STORE` command that adds a label by setting its keyword flag.
+pub fn build_add_label_command(uid_set: &str, label: &str) -> String {
+    format!("UID STORE {uid_set} +FLAGS.SILENT ({})", label_to_keyword(label))
+}
+
+/// Build the `STORE` command that removes a label.
+pub fn build_remove_label_command(uid_set: &str, label: &str) -> String {
+    format!("UID STORE {uid_set} -FLAGS.SILENT ({})", label_to_keyword(label))
+}
+
+/// Extract the set of user-facing labels from a message's full flag list
+/// (which also contains system flags like `\Seen`).
+pub fn labels_from_flags(flags: &[String]) -> Vec<String> {
+    flags.iter().filter_map(|f| keyword_to_label(f)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_label_through_keyword_encoding() {
+        let keyword = label_to_keyword("Follow Up!");
+        assert_eq!(keyword_to_label(&keyword).as_deref(), Some("Follow Up!"));
+    }
+
+    #[test]
+    fn add_label_command_persists_as_a_keyword_flag() {
+        let command = build_add_label_command("5", "Important");
+        assert_eq!(command, "UID STORE 5 +FLAGS.SILENT (FlowDeskLabel_Important)");
+    }
+
+    #[test]
+    fn extracts_only_our_labels_from_mixed_flag_list() {
+        let flags = vec![
+            "\\Seen".to_string(),
+            "FlowDeskLabel_Important".to_string(),
+            "$SomeOtherClientFlag".to_string(),
+        ];
+        assert_eq!(labels_from_flags(&flags), vec!["Important".to_string()]);
+    }
+}
@@ -0,0 +1,154 @@
+//! IMAP client primitives: connection lifecycle, pooling and response parsing.
+
+mod auth;
+mod bodystructure;
+mod bulk;
+mod condstore;
+mod convert;
+mod idle;
+mod fetch;
+mod labels;
+mod paginate;
+mod pool;
+mod quota;
+mod sieve;
+
+pub use auth::{build_authenticate_command, build_xoauth2_initial_response, is_xoauth2_failure_response, Credentials};
+pub use bodystructure::{BodyPart, BodyStructure};
+pub use bulk::{bulk_operation, BulkAction, BulkFailure, BulkOperationResult};
+pub use condstore::{CondstoreTracker, FolderChange, FolderSyncState};
+pub use convert::{convert_imap_message, ConvertedMessage, RawHeaders};
+pub use idle::{parse_idle_line, pump_idle_lines, SyncChange};
+pub use labels::{
+    build_add_label_command, build_remove_label_command, keyword_to_label, label_to_keyword,
+    labels_from_flags,
+};
+pub use fetch::{build_preview_fetch_command, snippet_from_partial_body, SNIPPET_FETCH_BYTES, SNIPPET_MAX_CHARS};
+pub use paginate::{paginate_uids, Page};
+pub use pool::{ConnectionPool, PooledConnection};
+pub use quota::{parse_quota_response, AccountQuota};
+pub use sieve::{
+    build_deletescript_command, build_putscript_command, build_setactive_command,
+    parse_listscripts_response, SieveScript,
+};
+
+use std::time::Duration;
+
+/// Why the server sent an untagged `BYE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByeKind {
+    /// The server is shutting down the connection cleanly (e.g. `LOGOUT` completed,
+    /// idle timeout reached). No backoff is needed before reconnecting.
+    GracefulShutdown,
+    /// The server is overloaded or enforcing a connection limit
+    /// (e.g. "Too many connections", "Server busy"). Reconnects should back off.
+    Overload,
+    /// A `BYE` we don't recognize the cause of. Treated like `Overload` defensively.
+    Unknown,
+}
+
+/// A parsed untagged `BYE` response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ByeNotice {
+    pub kind: ByeKind,
+    pub text: String,
+}
+
+/// Inspect an untagged server line and, if it is a `BYE`, classify it.
+///
+/// Per RFC 3501 the server may send `* BYE <human text>` at any time, including
+/// as the last line before closing the connection. Any command in flight when
+/// this happens should be treated as "connection closed", not as a normal
+/// command failure.
+pub fn parse_bye(line: &str) -> Option<ByeNotice> {
+    let trimmed = line.trim();
+    let rest = trimmed.strip_prefix("* BYE")?;
+    let text = rest.trim().to_string();
+    let lowered = text.to_lowercase();
+
+    let kind = if lowered.contains("logout")
+        || lowered.contains("idle")
+        || lowered.contains("timeout")
+        || lowered.contains("closing")
+    {
+        ByeKind::GracefulShutdown
+    } else if lowered.contains("too many connections")
+        || lowered.contains("server busy")
+        || lowered.contains("overloaded")
+        || lowered.contains("try again later")
+    {
+        ByeKind::Overload
+    } else {
+        ByeKind::Unknown
+    };
+
+    Some(ByeNotice { kind, text })
+}
+
+/// Exponential backoff used before reconnecting after an overload `BYE`.
+#[derive(Debug, Clone, Copy)]
+pub struct ReconnectBackoff {
+    attempt: u32,
+    base: Duration,
+    max: Duration,
+}
+
+impl ReconnectBackoff {
+    pub fn new(base: Duration, max: Duration) -> Self {
+        Self {
+            attempt: 0,
+            base,
+            max,
+        }
+    }
+
+    /// Delay to wait before the next reconnect attempt, then advance the counter.
+    pub fn next_delay(&mut self) -> Duration {
+        let factor = 1u32.checked_shl(self.attempt).unwrap_or(u32::MAX);
+        let delay = self.base.saturating_mul(factor).min(self.max);
+        self.attempt = self.attempt.saturating_add(1);
+        delay
+    }
+
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+}
+
+impl Default for ReconnectBackoff {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(500), Duration::from_secs(60))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_graceful_shutdown() {
+        let notice = parse_bye("* BYE Autologout; idle for too long").unwrap();
+        assert_eq!(notice.kind, ByeKind::GracefulShutdown);
+    }
+
+    #[test]
+    fn classifies_overload() {
+        let notice = parse_bye("* BYE Too many connections, try again later").unwrap();
+        assert_eq!(notice.kind, ByeKind::Overload);
+    }
+
+    #[test]
+    fn ignores_non_bye_lines() {
+        assert!(parse_bye("* OK IMAP4rev1 Service Ready").is_none());
+    }
+
+    #[test]
+    fn backoff_grows_and_caps() {
+        let mut backoff = ReconnectBackoff::new(Duration::from_millis(100), Duration::from_secs(1));
+        assert_eq!(backoff.next_delay(), Duration::from_millis(100));
+        assert_eq!(backoff.next_delay(), Duration::from_millis(200));
+        assert_eq!(backoff.next_delay(), Duration::from_millis(400));
+        assert_eq!(backoff.next_delay(), Duration::from_millis(800));
+        assert_eq!(backoff.next_delay(), Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,95 @@
+//! Paginated message fetching, replacing a single hard `LIMIT` with
+//! cursor-based pages so large folders don't require one giant FETCH.
+
+/// One page worth of UIDs to fetch, plus the cursor to request the next page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page {
+    /// UID range expressed as an IMAP sequence set, e.g. `"101:150"`.
+    pub uid_set: String,
+    pub next_cursor: Option<u32>,
+}
+
+/// Split the UIDs in a folder (sorted ascending, as UID search returns them)
+/// into pages of at most `page_size`, starting after `cursor` (exclusive).
+pub fn paginate_uids(all_uids: &[u32], cursor: Option<u32>, page_size: usize) -> Option<Page> {
+    if page_size == 0 {
+        return None;
+    }
+
+    let start_index = match cursor {
+        Some(after) => all_uids.iter().position(|&uid| uid > after)?,
+        None => 0,
+    };
+
+    let page_uids = &all_uids[start_index..];
+    if page_uids.is_empty() {
+        return None;
+    }
+
+    let taken: &[u32] = if page_uids.len() > page_size {
+        &page_uids[..page_size]
+    } else {
+        page_uids
+    };
+
+    let next_cursor = if start_index + taken.len() < all_uids.len() {
+        taken.last().copied()
+    } else {
+        None
+    };
+
+    Some(Page {
+        uid_set: uid_set_expression(taken),
+        next_cursor,
+    })
+}
+
+fn uid_set_expression(uids: &[u32]) -> String {
+    // Collapse consecutive runs into ranges, e.g. [1,2,3,7] -> "1:3,7".
+    let mut parts = Vec::new();
+    let mut iter = uids.iter().peekable();
+    while let Some(&start) = iter.next() {
+        let mut end = start;
+        while let Some(&&next) = iter.peek() {
+            if next == end + 1 {
+                end = next;
+                iter.next();
+            } else {
+                break;
+            }
+        }
+        if start == end {
+            parts.push(start.to_string());
+        } else {
+            parts.push(format!("{start}:{end}"));
+        }
+    }
+    parts.join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pages_through_a_folder_with_a_cursor() {
+        let uids: Vec<u32> = (1..=250).collect();
+
+        let page1 = paginate_uids(&uids, None, 100).unwrap();
+        assert_eq!(page1.uid_set, "1:100");
+        assert_eq!(page1.next_cursor, Some(100));
+
+        let page2 = paginate_uids(&uids, page1.next_cursor, 100).unwrap();
+        assert_eq!(page2.uid_set, "101:200");
+        assert_eq!(page2.next_cursor, Some(200));
+
+        let page3 = paginate_uids(&uids, page2.next_cursor, 100).unwrap();
+        assert_eq!(page3.uid_set, "201:250");
+        assert_eq!(page3.next_cursor, None);
+    }
+
+    #[test]
+    fn empty_folder_has_no_page() {
+        assert!(paginate_uids(&[], None, 100).is_none());
+    }
+}
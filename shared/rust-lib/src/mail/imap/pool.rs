@@ -0,0 +1,151 @@
+//! Pool of live IMAP connections for a single account.
+
+use super::{parse_bye, ByeKind, ReconnectBackoff};
+use crate::mail::AccountId;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// A single pooled IMAP connection.
+#[derive(Debug, Clone)]
+pub struct PooledConnection {
+    pub id: u64,
+    pub account_id: AccountId,
+    pub alive: bool,
+}
+
+/// Tracks live connections per account and handles retirement/reconnect
+/// bookkeeping when the server sends an untagged `BYE`.
+#[derive(Debug, Default)]
+pub struct ConnectionPool {
+    connections: HashMap<u64, PooledConnection>,
+    next_id: u64,
+    backoff_by_account: HashMap<AccountId, ReconnectBackoff>,
+    /// Accounts for which a reconnect has been scheduled, with the delay chosen.
+    pending_reconnects: Vec<(AccountId, Duration)>,
+}
+
+impl ConnectionPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a freshly-established connection and return its pool id.
+    pub fn insert(&mut self, account_id: AccountId) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.connections.insert(
+            id,
+            PooledConnection {
+                id,
+                account_id,
+                alive: true,
+            },
+        );
+        id
+    }
+
+    pub fn is_alive(&self, id: u64) -> bool {
+        self.connections.get(&id).is_some_and(|c| c.alive)
+    }
+
+    pub fn len(&self) -> usize {
+        self.connections.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.connections.is_empty()
+    }
+
+    pub fn pending_reconnects(&self) -> &[(AccountId, Duration)] {
+        &self.pending_reconnects
+    }
+
+    /// Feed a raw untagged line read from a connection. If it is a `BYE`, the
+    /// connection is retired and removed from the pool. Overload/unknown BYEs
+    /// schedule a reconnect with exponential backoff; graceful shutdowns
+    /// reconnect immediately (no backoff needed).
+    pub fn handle_untagged_line(&mut self, id: u64, line: &str) {
+        let Some(notice) = parse_bye(line) else {
+            return;
+        };
+
+        let Some(conn) = self.connections.remove(&id) else {
+            return;
+        };
+
+        let delay = match notice.kind {
+            ByeKind::GracefulShutdown => Duration::ZERO,
+            ByeKind::Overload | ByeKind::Unknown => {
+                let backoff = self
+                    .backoff_by_account
+                    .entry(conn.account_id.clone())
+                    .or_default();
+                backoff.next_delay()
+            }
+        };
+
+        self.pending_reconnects.push((conn.account_id, delay));
+    }
+
+    /// Reset backoff state for an account once a reconnect succeeds.
+    pub fn note_reconnect_succeeded(&mut self, account_id: &str) {
+        if let Some(backoff) = self.backoff_by_account.get_mut(account_id) {
+            backoff.reset();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A minimal mock IMAP server that just replays canned lines.
+    struct MockServer {
+        lines: Vec<&'static str>,
+    }
+
+    impl MockServer {
+        fn send_bye(&self) -> &'static str {
+            self.lines[0]
+        }
+    }
+
+    #[test]
+    fn bye_retires_connection_and_schedules_reconnect() {
+        let mut pool = ConnectionPool::new();
+        let id = pool.insert("account-1".to_string());
+        assert!(pool.is_alive(id));
+
+        let server = MockServer {
+            lines: vec!["* BYE Too many connections, try again later"],
+        };
+        pool.handle_untagged_line(id, server.send_bye());
+
+        assert!(!pool.is_alive(id));
+        assert!(pool.is_empty());
+        assert_eq!(pool.pending_reconnects().len(), 1);
+        assert_eq!(pool.pending_reconnects()[0].0, "account-1");
+        assert!(pool.pending_reconnects()[0].1 > Duration::ZERO);
+    }
+
+    #[test]
+    fn graceful_bye_reconnects_without_backoff() {
+        let mut pool = ConnectionPool::new();
+        let id = pool.insert("account-1".to_string());
+
+        pool.handle_untagged_line(id, "* BYE Autologout; idle for too long");
+
+        assert_eq!(pool.pending_reconnects()[0].1, Duration::ZERO);
+    }
+
+    #[test]
+    fn non_bye_lines_are_ignored() {
+        let mut pool = ConnectionPool::new();
+        let id = pool.insert("account-1".to_string());
+
+        pool.handle_untagged_line(id, "* 4 EXISTS");
+
+        assert!(pool.is_alive(id));
+        assert!(pool.pending_reconnects().is_empty());
+    }
+}
@@ -0,0 +1,78 @@
+//! IMAP `QUOTA` extension (RFC 2087): parses the server's reported mailbox
+//! usage so it can be surfaced as part of account info
+//! (see [`super::super::accounts_api::MailAccount`]).
+
+/// Storage quota reported by the IMAP `QUOTA` extension, in the 1024-octet
+/// units the wire protocol uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountQuota {
+    pub used_kb: u64,
+    pub limit_kb: u64,
+}
+
+impl AccountQuota {
+    /// Percentage of the quota used, capped at 100 (a server can report
+    /// usage over the limit while it's enforcing deletion).
+    pub fn percent_used(&self) -> u8 {
+        match self.used_kb.checked_mul(100).and_then(|scaled| scaled.checked_div(self.limit_kb)) {
+            Some(percent) => percent.min(100) as u8,
+            None => 0,
+        }
+    }
+}
+
+/// Parse an untagged `* QUOTA <root> (<resource> <usage> <limit> ...)`
+/// response (RFC 2087 section 5.1), picking out the `STORAGE` resource —
+/// the one mail clients show. Other resources (e.g. `MESSAGE`) aren't
+/// surfaced yet.
+pub fn parse_quota_response(line: &str) -> Option<AccountQuota> {
+    let rest = line.trim().strip_prefix("* QUOTA")?;
+    let list_start = rest.find('(')? + 1;
+    let list_end = rest.rfind(')')?;
+    if list_end < list_start {
+        return None;
+    }
+    let tokens: Vec<&str> = rest[list_start..list_end].split_whitespace().collect();
+
+    let storage_pos = tokens.iter().position(|&t| t == "STORAGE")?;
+    let used_kb = tokens.get(storage_pos + 1)?.parse().ok()?;
+    let limit_kb = tokens.get(storage_pos + 2)?.parse().ok()?;
+    Some(AccountQuota { used_kb, limit_kb })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_standard_storage_quota_response() {
+        let quota = parse_quota_response("* QUOTA \"\" (STORAGE 10240 102400)").unwrap();
+        assert_eq!(quota, AccountQuota { used_kb: 10240, limit_kb: 102400 });
+        assert_eq!(quota.percent_used(), 10);
+    }
+
+    #[test]
+    fn parses_storage_alongside_other_resources() {
+        let quota = parse_quota_response("* QUOTA \"\" (MESSAGE 42 1000 STORAGE 512 1024)").unwrap();
+        assert_eq!(quota, AccountQuota { used_kb: 512, limit_kb: 1024 });
+    }
+
+    #[test]
+    fn returns_none_when_storage_resource_is_absent() {
+        assert!(parse_quota_response("* QUOTA \"\" (MESSAGE 42 1000)").is_none());
+    }
+
+    #[test]
+    fn returns_none_for_non_quota_lines() {
+        assert!(parse_quota_response("* OK IMAP4rev1 Service Ready").is_none());
+    }
+
+    #[test]
+    fn percent_used_is_capped_at_one_hundred_and_handles_zero_limit() {
+        let over = AccountQuota { used_kb: 200, limit_kb: 100 };
+        assert_eq!(over.percent_used(), 100);
+
+        let no_limit = AccountQuota { used_kb: 50, limit_kb: 0 };
+        assert_eq!(no_limit.percent_used(), 0);
+    }
+}
@@ -0,0 +1,83 @@
+//! Server-side mail filter management via ManageSieve (RFC 5804).
+//!
+//! This is a thin protocol-command builder / response-parser; the actual
+//! socket is owned by the caller (same split as [`super::fetch`]).
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SieveScript {
+    pub name: String,
+    pub active: bool,
+    pub content: String,
+}
+
+/// Build the `PUTSCRIPT` command to upload/replace a named script.
+pub fn build_putscript_command(name: &str, content: &str) -> String {
+    format!(
+        "PUTSCRIPT \"{}\" {{{}+}}\r\n{}",
+        escape_quoted(name),
+        content.len(),
+        content
+    )
+}
+
+/// Build the `SETACTIVE` command to make a script the one the server runs.
+pub fn build_setactive_command(name: &str) -> String {
+    format!("SETACTIVE \"{}\"", escape_quoted(name))
+}
+
+/// Build the `DELETESCRIPT` command.
+pub fn build_deletescript_command(name: &str) -> String {
+    format!("DELETESCRIPT \"{}\"", escape_quoted(name))
+}
+
+/// Parse the response to `LISTSCRIPTS`, e.g.:
+/// ```text
+/// "vacation" ACTIVE
+/// "spam-filter"
+/// OK
+/// ```
+pub fn parse_listscripts_response(response: &str) -> Vec<SieveScript> {
+    response
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if !line.starts_with('"') {
+                return None;
+            }
+            let end_quote = line[1..].find('"')? + 1;
+            let name = line[1..end_quote].to_string();
+            let active = line[end_quote + 1..].trim().eq_ignore_ascii_case("active");
+            Some(SieveScript {
+                name,
+                active,
+                content: String::new(),
+            })
+        })
+        .collect()
+}
+
+fn escape_quoted(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_putscript_with_literal_length() {
+        let cmd = build_putscript_command("vacation", "if true { discard; }");
+        assert!(cmd.starts_with("PUTSCRIPT \"vacation\" {20+}\r\n"));
+        assert!(cmd.ends_with("if true { discard; }"));
+    }
+
+    #[test]
+    fn parses_listscripts_response() {
+        let response = "\"vacation\" ACTIVE\r\n\"spam-filter\"\r\nOK";
+        let scripts = parse_listscripts_response(response);
+        assert_eq!(scripts.len(), 2);
+        assert_eq!(scripts[0].name, "vacation");
+        assert!(scripts[0].active);
+        assert!(!scripts[1].active);
+    }
+}
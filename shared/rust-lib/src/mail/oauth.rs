@@ -0,0 +1,116 @@
+//! Batch validation of OAuth2 account credentials, so the sync scheduler can
+//! flag accounts that need the user to reauthenticate before attempting a
+//! sync that would otherwise fail mid-flight.
+
+use super::AccountId;
+use std::time::{Duration, SystemTime};
+
+/// Reauth is flagged this far ahead of actual expiry, so there's time for a
+/// background refresh (or a user prompt) before the token is rejected.
+pub const REAUTH_LEAD_TIME: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OAuthCredentialState {
+    pub account_id: AccountId,
+    pub expires_at: SystemTime,
+    /// Set once a refresh attempt has already failed (e.g. the refresh
+    /// token itself was revoked) — these need interactive reauth, not just
+    /// a background refresh retry.
+    pub refresh_failed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CredentialVerdict {
+    Valid,
+    /// Still valid, but within [`REAUTH_LEAD_TIME`] of expiring — schedule a
+    /// background refresh.
+    RefreshSoon,
+    /// Expired, or a prior refresh attempt failed — requires the user to
+    /// sign in again.
+    ReauthRequired,
+}
+
+/// Validate a batch of account credential states against `now`, returning
+/// one verdict per account in the same order.
+pub fn validate_batch(
+    states: &[OAuthCredentialState],
+    now: SystemTime,
+) -> Vec<(AccountId, CredentialVerdict)> {
+    states
+        .iter()
+        .map(|state| (state.account_id.clone(), validate_one(state, now)))
+        .collect()
+}
+
+fn validate_one(state: &OAuthCredentialState, now: SystemTime) -> CredentialVerdict {
+    if state.refresh_failed {
+        return CredentialVerdict::ReauthRequired;
+    }
+
+    match state.expires_at.duration_since(now) {
+        Ok(remaining) if remaining > REAUTH_LEAD_TIME => CredentialVerdict::Valid,
+        Ok(_) => CredentialVerdict::RefreshSoon,
+        Err(_) => CredentialVerdict::ReauthRequired,
+    }
+}
+
+/// Convenience filter for callers that only care which accounts need the
+/// user's attention.
+pub fn accounts_needing_reauth(
+    states: &[OAuthCredentialState],
+    now: SystemTime,
+) -> Vec<AccountId> {
+    validate_batch(states, now)
+        .into_iter()
+        .filter(|(_, verdict)| *verdict == CredentialVerdict::ReauthRequired)
+        .map(|(account_id, _)| account_id)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    fn at(seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    #[test]
+    fn flags_expired_and_soon_expiring_tokens_distinctly() {
+        let states = vec![
+            OAuthCredentialState {
+                account_id: "expired".to_string(),
+                expires_at: at(100),
+                refresh_failed: false,
+            },
+            OAuthCredentialState {
+                account_id: "soon".to_string(),
+                expires_at: at(200 + 60),
+                refresh_failed: false,
+            },
+            OAuthCredentialState {
+                account_id: "fine".to_string(),
+                expires_at: at(200 + 3600),
+                refresh_failed: false,
+            },
+        ];
+
+        let verdicts = validate_batch(&states, at(200));
+        assert_eq!(verdicts[0].1, CredentialVerdict::ReauthRequired);
+        assert_eq!(verdicts[1].1, CredentialVerdict::RefreshSoon);
+        assert_eq!(verdicts[2].1, CredentialVerdict::Valid);
+    }
+
+    #[test]
+    fn prior_refresh_failure_forces_reauth_even_if_not_yet_expired() {
+        let states = vec![OAuthCredentialState {
+            account_id: "broken".to_string(),
+            expires_at: at(10_000),
+            refresh_failed: true,
+        }];
+
+        let needing_reauth = accounts_needing_reauth(&states, at(200));
+        assert_eq!(needing_reauth, vec!["broken".to_string()]);
+    }
+}
@@ -0,0 +1,102 @@
+//! Gmail provider: syncs via the History API instead of re-scanning
+//! folders, so incremental sync is O(changes) rather than O(messages).
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryChange {
+    MessageAdded { message_id: String },
+    MessageDeleted { message_id: String },
+    LabelsChanged { message_id: String, added: Vec<String>, removed: Vec<String> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct HistoryPage {
+    pub changes: Vec<HistoryChange>,
+    pub next_page_token: Option<String>,
+    pub new_history_id: String,
+}
+
+/// Result of applying a page of history: either it applied cleanly, or the
+/// `historyId` we had stored was too old and the server says to start over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeltaSyncOutcome {
+    Applied { new_history_id: String, changes_applied: usize },
+    HistoryIdExpired,
+}
+
+/// Tracks the last-seen Gmail `historyId` for an account and folds in
+/// successive pages of `users.history.list` results.
+#[derive(Debug, Default)]
+pub struct GmailDeltaSync {
+    history_id: Option<String>,
+}
+
+impl GmailDeltaSync {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn history_id(&self) -> Option<&str> {
+        self.history_id.as_deref()
+    }
+
+    pub fn seed(&mut self, history_id: impl Into<String>) {
+        self.history_id = Some(history_id.into());
+    }
+
+    /// Apply one page of history returned for `GET users.history.list?startHistoryId=...`.
+    /// `history_id_expired` models the `404`/`historyId too old` response Gmail
+    /// returns when the stored id has fallen out of the retention window,
+    /// which requires a full resync instead of a delta.
+    pub fn apply_page(&mut self, page: HistoryPage, history_id_expired: bool) -> DeltaSyncOutcome {
+        if history_id_expired {
+            self.history_id = None;
+            return DeltaSyncOutcome::HistoryIdExpired;
+        }
+
+        let changes_applied = page.changes.len();
+        self.history_id = Some(page.new_history_id.clone());
+        DeltaSyncOutcome::Applied {
+            new_history_id: page.new_history_id,
+            changes_applied,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advances_history_id_after_applying_changes() {
+        let mut sync = GmailDeltaSync::new();
+        sync.seed("1000");
+
+        let page = HistoryPage {
+            changes: vec![HistoryChange::MessageAdded {
+                message_id: "msg-1".to_string(),
+            }],
+            next_page_token: None,
+            new_history_id: "1005".to_string(),
+        };
+
+        let outcome = sync.apply_page(page, false);
+        assert_eq!(
+            outcome,
+            DeltaSyncOutcome::Applied {
+                new_history_id: "1005".to_string(),
+                changes_applied: 1
+            }
+        );
+        assert_eq!(sync.history_id(), Some("1005"));
+    }
+
+    #[test]
+    fn expired_history_id_requires_full_resync() {
+        let mut sync = GmailDeltaSync::new();
+        sync.seed("1000");
+
+        let outcome = sync.apply_page(HistoryPage::default(), true);
+        assert_eq!(outcome, DeltaSyncOutcome::HistoryIdExpired);
+        assert!(sync.history_id().is_none());
+    }
+}
@@ -0,0 +1,6 @@
+//! Native API providers (as opposed to generic IMAP/SMTP) for services that
+//! expose a richer sync protocol.
+
+pub mod gmail;
+pub mod outlook;
+pub mod raw_message;
@@ -0,0 +1,77 @@
+//! Expanding a collapsed quoted block (as produced by
+//! [`strip_quoted_and_signature`](super::quoted_text::strip_quoted_and_signature))
+//! back into its structured `>` nesting levels, for UIs that show quote
+//! history indented by reply depth instead of one flat blob.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuoteLevel {
+    /// Nesting depth: 1 for the immediate quoted message, 2 for a quote
+    /// within that quote, etc.
+    pub depth: u32,
+    pub lines: Vec<String>,
+}
+
+/// Expand `quoted_text` (the raw, still-`>`-prefixed block) into one
+/// [`QuoteLevel`] per contiguous run at a given depth, in document order.
+pub fn expand_quote_levels(quoted_text: &str) -> Vec<QuoteLevel> {
+    let mut levels: Vec<QuoteLevel> = Vec::new();
+
+    for raw_line in quoted_text.lines() {
+        let (depth, content) = strip_quote_markers(raw_line);
+        match levels.last_mut() {
+            Some(last) if last.depth == depth => last.lines.push(content),
+            _ => levels.push(QuoteLevel {
+                depth,
+                lines: vec![content],
+            }),
+        }
+    }
+
+    levels
+}
+
+/// Count leading `>` markers (each optionally followed by one space) and
+/// return the depth plus the remaining content.
+fn strip_quote_markers(line: &str) -> (u32, String) {
+    let mut depth = 0;
+    let mut rest = line;
+    loop {
+        let trimmed = rest.trim_start();
+        if let Some(after) = trimmed.strip_prefix('>') {
+            depth += 1;
+            rest = after.strip_prefix(' ').unwrap_or(after);
+        } else {
+            rest = trimmed;
+            break;
+        }
+    }
+    (depth, rest.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_contiguous_lines_by_nesting_depth() {
+        let quoted = "> On Mon, Alice wrote:\n> > On Sun, Bob wrote:\n> > original plan\n> back to Alice's reply";
+        let levels = expand_quote_levels(quoted);
+
+        assert_eq!(levels.len(), 3);
+        assert_eq!(levels[0].depth, 1);
+        assert_eq!(levels[0].lines, vec!["On Mon, Alice wrote:".to_string()]);
+        assert_eq!(levels[1].depth, 2);
+        assert_eq!(
+            levels[1].lines,
+            vec!["On Sun, Bob wrote:".to_string(), "original plan".to_string()]
+        );
+        assert_eq!(levels[2].depth, 1);
+        assert_eq!(levels[2].lines, vec!["back to Alice's reply".to_string()]);
+    }
+
+    #[test]
+    fn unquoted_text_is_depth_zero() {
+        let levels = expand_quote_levels("plain line, no markers");
+        assert_eq!(levels, vec![QuoteLevel { depth: 0, lines: vec!["plain line, no markers".to_string()] }]);
+    }
+}
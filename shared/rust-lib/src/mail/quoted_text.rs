@@ -0,0 +1,77 @@
+//! Detecting and stripping quoted reply history and signatures from a
+//! plain-text message body, so we can show/search just the new content.
+
+/// Lines that commonly introduce a quoted block, e.g.
+/// "On Mon, Jan 1, 2024 at 9:00 AM Alice <alice@example.com> wrote:".
+const QUOTE_HEADER_MARKERS: &[&str] = &["wrote:", "a écrit :", "escribió:"];
+
+/// Markers that introduce a signature block.
+const SIGNATURE_MARKERS: &[&str] = &["-- ", "--", "Sent from my iPhone", "Get Outlook for"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrippedBody {
+    pub reply_text: String,
+    pub quoted_text: Option<String>,
+    pub signature: Option<String>,
+}
+
+/// Split a message body into the new reply text, the quoted history (if
+/// any), and the signature (if any).
+pub fn strip_quoted_and_signature(body: &str) -> StrippedBody {
+    let lines: Vec<&str> = body.lines().collect();
+
+    let quote_start = find_quote_start(&lines);
+    let (reply_lines, quoted_text) = match quote_start {
+        Some(idx) => (&lines[..idx], Some(lines[idx..].join("\n"))),
+        None => (&lines[..], None),
+    };
+
+    let sig_start = find_signature_start(reply_lines);
+    let (final_reply, signature) = match sig_start {
+        Some(idx) => (reply_lines[..idx].join("\n"), Some(reply_lines[idx..].join("\n"))),
+        None => (reply_lines.join("\n"), None),
+    };
+
+    StrippedBody {
+        reply_text: final_reply.trim_end().to_string(),
+        quoted_text,
+        signature,
+    }
+}
+
+fn find_quote_start(lines: &[&str]) -> Option<usize> {
+    lines.iter().position(|line| {
+        let trimmed = line.trim();
+        (trimmed.starts_with('>'))
+            || QUOTE_HEADER_MARKERS.iter().any(|marker| trimmed.ends_with(marker))
+    })
+}
+
+fn find_signature_start(lines: &[&str]) -> Option<usize> {
+    lines.iter().position(|line| {
+        let trimmed = line.trim_end();
+        SIGNATURE_MARKERS.iter().any(|marker| trimmed == *marker || trimmed.starts_with(marker))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_quoted_history_after_wrote_marker() {
+        let body = "Sounds good!\n\nOn Mon, Jan 1, 2024 at 9:00 AM Alice <a@example.com> wrote:\n> original message";
+        let stripped = strip_quoted_and_signature(body);
+        assert_eq!(stripped.reply_text, "Sounds good!");
+        assert!(stripped.quoted_text.unwrap().contains("original message"));
+    }
+
+    #[test]
+    fn strips_signature_block() {
+        let body = "Thanks!\n\n-- \nAlice Smith\nCEO, Example Inc.";
+        let stripped = strip_quoted_and_signature(body);
+        assert_eq!(stripped.reply_text, "Thanks!");
+        assert!(stripped.signature.unwrap().contains("Alice Smith"));
+        assert!(stripped.quoted_text.is_none());
+    }
+}
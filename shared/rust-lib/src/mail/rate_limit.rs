@@ -0,0 +1,124 @@
+//! Per-account rate limiting shared across every operation that talks to a
+//! provider — sync, send, search — so a burst of concurrent operations on
+//! one account can't blow past the provider's own API rate limit. A classic
+//! token bucket per account: each starts with `capacity` tokens, refilled
+//! at `refill_per_second`, and every operation spends one before proceeding.
+
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use super::AccountId;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitConfig {
+    pub capacity: f64,
+    pub refill_per_second: f64,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self { capacity: 10.0, refill_per_second: 2.0 }
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: SystemTime,
+}
+
+impl TokenBucket {
+    fn new(config: &RateLimitConfig, now: SystemTime) -> Self {
+        Self { tokens: config.capacity, last_refill: now }
+    }
+
+    fn refill(&mut self, config: &RateLimitConfig, now: SystemTime) {
+        let elapsed = now.duration_since(self.last_refill).unwrap_or_default().as_secs_f64();
+        self.tokens = (self.tokens + elapsed * config.refill_per_second).min(config.capacity);
+        self.last_refill = now;
+    }
+
+    fn try_acquire(&mut self, config: &RateLimitConfig, now: SystemTime) -> bool {
+        self.refill(config, now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Shared across every mail operation for every account. Hand this one
+/// instance to the sync loop, the send path and the search indexer alike —
+/// they each draw from the same per-account budget, not separate ones.
+#[derive(Debug, Default)]
+pub struct RateLimitGovernor {
+    config: RateLimitConfig,
+    buckets: HashMap<AccountId, TokenBucket>,
+}
+
+impl RateLimitGovernor {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self { config, buckets: HashMap::new() }
+    }
+
+    /// Try to spend one token for `account_id`, returning whether an
+    /// operation may proceed. A first-seen account starts at full capacity.
+    pub fn try_acquire(&mut self, account_id: &str, now: SystemTime) -> bool {
+        let config = self.config;
+        let bucket = self
+            .buckets
+            .entry(account_id.to_string())
+            .or_insert_with(|| TokenBucket::new(&config, now));
+        bucket.try_acquire(&config, now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn config() -> RateLimitConfig {
+        RateLimitConfig { capacity: 2.0, refill_per_second: 1.0 }
+    }
+
+    #[test]
+    fn a_fresh_account_starts_with_a_full_bucket() {
+        let mut governor = RateLimitGovernor::new(config());
+        let now = SystemTime::UNIX_EPOCH;
+        assert!(governor.try_acquire("acct-1", now));
+        assert!(governor.try_acquire("acct-1", now));
+        assert!(!governor.try_acquire("acct-1", now));
+    }
+
+    #[test]
+    fn accounts_have_independent_budgets() {
+        let mut governor = RateLimitGovernor::new(config());
+        let now = SystemTime::UNIX_EPOCH;
+        assert!(governor.try_acquire("acct-1", now));
+        assert!(governor.try_acquire("acct-1", now));
+        assert!(!governor.try_acquire("acct-1", now));
+
+        assert!(governor.try_acquire("acct-2", now));
+    }
+
+    #[test]
+    fn tokens_refill_over_time_up_to_capacity() {
+        let mut governor = RateLimitGovernor::new(config());
+        let now = SystemTime::UNIX_EPOCH;
+        assert!(governor.try_acquire("acct-1", now));
+        assert!(governor.try_acquire("acct-1", now));
+        assert!(!governor.try_acquire("acct-1", now));
+
+        let later = now + Duration::from_secs(1);
+        assert!(governor.try_acquire("acct-1", later));
+        assert!(!governor.try_acquire("acct-1", later));
+
+        let much_later = now + Duration::from_secs(10);
+        assert!(governor.try_acquire("acct-1", much_later));
+        assert!(governor.try_acquire("acct-1", much_later));
+        assert!(!governor.try_acquire("acct-1", much_later));
+    }
+}
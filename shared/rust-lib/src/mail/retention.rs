@@ -0,0 +1,91 @@
+//! Per-folder message retention and auto-archive policies.
+
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetentionAction {
+    Archive,
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetentionPolicy {
+    pub folder: String,
+    pub max_age: Duration,
+    pub action: RetentionAction,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetentionCandidate {
+    pub message_id: String,
+    pub folder: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    pub id: String,
+    pub folder: String,
+    pub received_at: SystemTime,
+    /// Messages the user has pinned/flagged are never auto-archived or
+    /// auto-deleted, regardless of age.
+    pub is_pinned: bool,
+}
+
+/// Evaluate `policies` against `messages`, returning the messages that
+/// should be acted on along with which action applies. Pinned messages are
+/// always excluded.
+pub fn apply_retention_policies(
+    messages: &[Message],
+    policies: &[RetentionPolicy],
+    now: SystemTime,
+) -> Vec<(RetentionCandidate, RetentionAction)> {
+    messages
+        .iter()
+        .filter(|m| !m.is_pinned)
+        .filter_map(|m| {
+            let policy = policies.iter().find(|p| p.folder == m.folder)?;
+            let age = now.duration_since(m.received_at).ok()?;
+            (age >= policy.max_age).then_some((
+                RetentionCandidate {
+                    message_id: m.id.clone(),
+                    folder: m.folder.clone(),
+                },
+                policy.action,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(id: &str, folder: &str, age_days: u64, now: SystemTime, pinned: bool) -> Message {
+        Message {
+            id: id.to_string(),
+            folder: folder.to_string(),
+            received_at: now - Duration::from_secs(age_days * 86_400),
+            is_pinned: pinned,
+        }
+    }
+
+    #[test]
+    fn archives_old_messages_and_skips_pinned() {
+        let now = SystemTime::now();
+        let messages = vec![
+            msg("old", "Newsletters", 100, now, false),
+            msg("new", "Newsletters", 1, now, false),
+            msg("pinned-old", "Newsletters", 100, now, true),
+        ];
+        let policies = vec![RetentionPolicy {
+            folder: "Newsletters".to_string(),
+            max_age: Duration::from_secs(30 * 86_400),
+            action: RetentionAction::Archive,
+        }];
+
+        let results = apply_retention_policies(&messages, &policies, now);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0.message_id, "old");
+        assert_eq!(results[0].1, RetentionAction::Archive);
+    }
+}
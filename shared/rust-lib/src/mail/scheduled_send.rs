@@ -0,0 +1,195 @@
+//! Outbound send scheduling: queue a message to go out at a future time,
+//! then dispatch it through the account's provider when it's due, with
+//! retry/backoff on failure.
+//!
+//! The queue here is in-memory; the real implementation persists
+//! [`ScheduledSend`] rows in the mail DB so they survive a process
+//! restart, which is why every field on it is plain, serializable data
+//! rather than a handle into a live connection.
+
+use super::AccountId;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+pub type ScheduledSendId = String;
+
+/// Number of failed send attempts before a scheduled send gives up.
+pub const MAX_SEND_ATTEMPTS: u32 = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewMessage {
+    pub to: Vec<String>,
+    pub subject: String,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduledSendStatus {
+    Pending,
+    Sent,
+    Cancelled,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledSend {
+    pub id: ScheduledSendId,
+    pub account_id: AccountId,
+    pub message: NewMessage,
+    pub send_at: SystemTime,
+    pub status: ScheduledSendStatus,
+    pub attempts: u32,
+    next_attempt_at: SystemTime,
+}
+
+/// Exponential backoff with no jitter (the caller already staggers
+/// dispatch passes): 30s, 1m, 2m, 4m, ... doubling per attempt.
+fn retry_backoff(attempts: u32) -> Duration {
+    Duration::from_secs(30) * 2u32.saturating_pow(attempts.saturating_sub(1))
+}
+
+/// The provider-level send call a dispatch pass goes through. The real
+/// implementation is each provider's `send_message`; this lets the
+/// scheduler be tested without a live SMTP/API connection.
+pub trait MessageSender {
+    fn send_message(&mut self, account_id: &str, message: &NewMessage) -> Result<(), String>;
+}
+
+#[derive(Debug, Default)]
+pub struct SendScheduler {
+    queued: HashMap<ScheduledSendId, ScheduledSend>,
+    next_id: u64,
+}
+
+impl SendScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue `message` to be sent through `account_id`'s provider at
+    /// `send_at`.
+    pub fn schedule_send(&mut self, account_id: AccountId, message: NewMessage, send_at: SystemTime) -> ScheduledSendId {
+        self.next_id += 1;
+        let id = format!("scheduled-{}", self.next_id);
+        self.queued.insert(
+            id.clone(),
+            ScheduledSend {
+                id: id.clone(),
+                account_id,
+                message,
+                send_at,
+                status: ScheduledSendStatus::Pending,
+                attempts: 0,
+                next_attempt_at: send_at,
+            },
+        );
+        id
+    }
+
+    /// Cancel a send that hasn't gone out yet. Returns `false` if it was
+    /// already sent, cancelled, failed, or never existed.
+    pub fn cancel_scheduled(&mut self, id: &str) -> bool {
+        match self.queued.get_mut(id) {
+            Some(scheduled) if scheduled.status == ScheduledSendStatus::Pending => {
+                scheduled.status = ScheduledSendStatus::Cancelled;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn status(&self, id: &str) -> Option<ScheduledSendStatus> {
+        self.queued.get(id).map(|scheduled| scheduled.status)
+    }
+
+    /// Dispatch every pending, due message through `sender`. A failed send
+    /// is retried on a later pass once [`retry_backoff`] has elapsed, up
+    /// to [`MAX_SEND_ATTEMPTS`]. Returns the ids sent in this pass.
+    pub fn dispatch_due(&mut self, sender: &mut impl MessageSender, now: SystemTime) -> Vec<ScheduledSendId> {
+        let mut sent = Vec::new();
+        for scheduled in self.queued.values_mut() {
+            if scheduled.status != ScheduledSendStatus::Pending || scheduled.next_attempt_at > now {
+                continue;
+            }
+
+            match sender.send_message(&scheduled.account_id, &scheduled.message) {
+                Ok(()) => {
+                    scheduled.status = ScheduledSendStatus::Sent;
+                    sent.push(scheduled.id.clone());
+                }
+                Err(_) => {
+                    scheduled.attempts += 1;
+                    if scheduled.attempts >= MAX_SEND_ATTEMPTS {
+                        scheduled.status = ScheduledSendStatus::Failed;
+                    } else {
+                        scheduled.next_attempt_at = now + retry_backoff(scheduled.attempts);
+                    }
+                }
+            }
+        }
+        sent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::epoch_plus;
+
+    fn message() -> NewMessage {
+        NewMessage { to: vec!["team@example.com".to_string()], subject: "Launch".to_string(), body: "Go time.".to_string() }
+    }
+
+    struct AlwaysSucceeds;
+    impl MessageSender for AlwaysSucceeds {
+        fn send_message(&mut self, _account_id: &str, _message: &NewMessage) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    struct AlwaysFails;
+    impl MessageSender for AlwaysFails {
+        fn send_message(&mut self, _account_id: &str, _message: &NewMessage) -> Result<(), String> {
+            Err("connection refused".to_string())
+        }
+    }
+
+    #[test]
+    fn a_due_message_is_sent_and_not_sent_again() {
+        let mut scheduler = SendScheduler::new();
+        let id = scheduler.schedule_send("acct-1".to_string(), message(), epoch_plus(100));
+
+        assert!(scheduler.dispatch_due(&mut AlwaysSucceeds, epoch_plus(50)).is_empty());
+
+        let sent = scheduler.dispatch_due(&mut AlwaysSucceeds, epoch_plus(150));
+        assert_eq!(sent, vec![id.clone()]);
+        assert_eq!(scheduler.status(&id), Some(ScheduledSendStatus::Sent));
+
+        assert!(scheduler.dispatch_due(&mut AlwaysSucceeds, epoch_plus(200)).is_empty());
+    }
+
+    #[test]
+    fn cancelling_before_send_prevents_dispatch() {
+        let mut scheduler = SendScheduler::new();
+        let id = scheduler.schedule_send("acct-1".to_string(), message(), epoch_plus(100));
+
+        assert!(scheduler.cancel_scheduled(&id));
+        assert!(scheduler.dispatch_due(&mut AlwaysSucceeds, epoch_plus(200)).is_empty());
+        assert_eq!(scheduler.status(&id), Some(ScheduledSendStatus::Cancelled));
+        assert!(!scheduler.cancel_scheduled(&id));
+    }
+
+    #[test]
+    fn failures_back_off_and_eventually_give_up() {
+        let mut scheduler = SendScheduler::new();
+        let id = scheduler.schedule_send("acct-1".to_string(), message(), epoch_plus(0));
+
+        let mut now = epoch_plus(0);
+        for _ in 0..MAX_SEND_ATTEMPTS {
+            scheduler.dispatch_due(&mut AlwaysFails, now);
+            now += Duration::from_secs(600);
+        }
+
+        assert_eq!(scheduler.status(&id), Some(ScheduledSendStatus::Failed));
+    }
+}
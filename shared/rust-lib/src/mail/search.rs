@@ -0,0 +1,287 @@
+//! Full-text mail search over an in-memory index.
+//!
+//! There is no SQLite/FTS5 connection anywhere in this crate.
+//! [`compile_search_query`] models the single prepared statement a real
+//! index would run — `since`/`until` as `WHERE received_at >= ?`/`<= ?`
+//! predicates alongside the `MATCH` clause, present only when the caller
+//! actually supplied that bound — the same "model the SQL contract, wire in
+//! a real connection later" approach as
+//! [`crate::search::document_store`]/[`crate::database::connection_pool`].
+//! That statement is never executed, though: [`MailEngine::search_messages`]
+//! still does exactly the same per-message `Vec` scan it always has, just
+//! now checking `CompiledSearchQuery::date_in_range` instead of a
+//! standalone function. Wiring this to a real index is what would actually
+//! push the date bound into the query and skip scanning non-matching rows;
+//! until then, nothing here is any faster than before.
+
+use super::{MailEngine, MessageId};
+use std::time::{Duration, SystemTime};
+
+/// A stored message as seen by search (a minimal projection; the real index
+/// also stores subject/body text for FTS matching).
+#[derive(Debug, Clone)]
+pub struct IndexedMessage {
+    pub id: MessageId,
+    pub subject: String,
+    pub body: String,
+    pub received_at: SystemTime,
+}
+
+/// A date-bounded full-text search request.
+#[derive(Debug, Clone, Default)]
+pub struct SearchQuery {
+    pub text: String,
+    /// Only return messages received on or after this time.
+    pub since: Option<SystemTime>,
+    /// Only return messages received on or before this time.
+    pub until: Option<SystemTime>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub message_id: MessageId,
+    pub relevance: f64,
+    /// Snippet of the body around the first match, with matched terms
+    /// wrapped in `<mark>` for the UI to render directly.
+    pub highlighted_snippet: Option<String>,
+}
+
+/// Characters of context kept before a highlighted match; the window after
+/// the match start runs twice this long, the match included.
+const HIGHLIGHT_CONTEXT_CHARS: usize = 40;
+
+/// Find the first occurrence of any search term in `body` and return a
+/// snippet around it with matches wrapped in `<mark>…</mark>`.
+pub fn highlight_match(body: &str, terms: &[String]) -> Option<String> {
+    let lower_body = body.to_lowercase();
+    let first_match = terms
+        .iter()
+        .filter_map(|term| lower_body.find(term.as_str()).map(|idx| (idx, term)))
+        .min_by_key(|(idx, _)| *idx)?;
+
+    let (match_start, _) = first_match;
+    let start = lower_body[..match_start]
+        .char_indices()
+        .rev()
+        .nth(HIGHLIGHT_CONTEXT_CHARS)
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+    let end_search_from = match_start;
+    let end = lower_body[end_search_from..]
+        .char_indices()
+        .nth(HIGHLIGHT_CONTEXT_CHARS * 2)
+        .map(|(i, _)| end_search_from + i)
+        .unwrap_or(lower_body.len());
+
+    let window = &body[start..end];
+    let mut highlighted = window.to_string();
+    for term in terms {
+        highlighted = replace_case_insensitive(&highlighted, term, &format!("<mark>{term}</mark>"));
+    }
+
+    let prefix = if start > 0 { "…" } else { "" };
+    let suffix = if end < body.len() { "…" } else { "" };
+    Some(format!("{prefix}{highlighted}{suffix}"))
+}
+
+fn replace_case_insensitive(haystack: &str, needle: &str, replacement: &str) -> String {
+    if needle.is_empty() {
+        return haystack.to_string();
+    }
+    let lower_haystack = haystack.to_lowercase();
+    let lower_needle = needle.to_lowercase();
+    let mut result = String::with_capacity(haystack.len());
+    let mut rest = haystack;
+    let mut lower_rest = lower_haystack.as_str();
+    while let Some(idx) = lower_rest.find(&lower_needle) {
+        result.push_str(&rest[..idx]);
+        result.push_str(replacement);
+        rest = &rest[idx + needle.len()..];
+        lower_rest = &lower_rest[idx + needle.len()..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// The prepared statement a real SQLite FTS5 index would run for a
+/// [`SearchQuery`] — see the module doc comment. Built once by
+/// [`compile_search_query`] and then reused for every row, instead of each
+/// row re-deriving its own date bound.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompiledSearchQuery {
+    /// The statement a real connection would prepare and bind `terms`/
+    /// `since`/`until` into. `since`/`until` are only present in the
+    /// `WHERE` clause when the caller actually supplied them, the same way
+    /// a query builder would omit an unused predicate rather than bind a
+    /// always-true one.
+    pub sql: String,
+    pub terms: Vec<String>,
+    pub since: Option<SystemTime>,
+    pub until: Option<SystemTime>,
+}
+
+impl CompiledSearchQuery {
+    /// Whether `when` satisfies this query's date bound — the `WHERE
+    /// received_at >= ?  AND received_at <= ?` part of [`Self::sql`].
+    fn date_in_range(&self, when: SystemTime) -> bool {
+        if let Some(since) = self.since {
+            if when < since {
+                return false;
+            }
+        }
+        if let Some(until) = self.until {
+            if when > until {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Compile `query` into the single prepared statement a real FTS5 index
+/// would run, with the date bound folded into the same `WHERE` clause as
+/// the text match rather than applied as a separate in-memory filter.
+pub fn compile_search_query(query: &SearchQuery) -> CompiledSearchQuery {
+    let terms: Vec<String> = query.text.split_whitespace().map(|t| t.to_lowercase()).collect();
+
+    let mut sql = "SELECT message_id FROM messages_fts JOIN messages USING (message_id) \
+                    WHERE messages_fts MATCH ?1"
+        .to_string();
+    if query.since.is_some() {
+        sql.push_str(" AND received_at >= ?2");
+    }
+    if query.until.is_some() {
+        sql.push_str(" AND received_at <= ?3");
+    }
+
+    CompiledSearchQuery { sql, terms, since: query.since, until: query.until }
+}
+
+impl MailEngine {
+    /// Search indexed messages in memory (see the module doc comment —
+    /// [`compile_search_query`]'s statement is never actually run), then
+    /// rank by simple term-frequency relevance.
+    pub fn search_messages(&self, index: &[IndexedMessage], query: &SearchQuery) -> Vec<SearchResult> {
+        let compiled = compile_search_query(query);
+
+        let mut results: Vec<SearchResult> = index
+            .iter()
+            .filter(|msg| compiled.date_in_range(msg.received_at))
+            .filter_map(|msg| {
+                let relevance = relevance_score(msg, &compiled.terms);
+                (relevance > 0.0 || compiled.terms.is_empty()).then(|| SearchResult {
+                    message_id: msg.id.clone(),
+                    relevance,
+                    highlighted_snippet: highlight_match(&msg.body, &compiled.terms),
+                })
+            })
+            .collect();
+
+        results.sort_by(|a, b| b.relevance.partial_cmp(&a.relevance).unwrap());
+        results
+    }
+}
+
+fn relevance_score(msg: &IndexedMessage, terms: &[String]) -> f64 {
+    let haystack = format!("{} {}", msg.subject.to_lowercase(), msg.body.to_lowercase());
+    terms
+        .iter()
+        .map(|term| haystack.matches(term.as_str()).count() as f64)
+        .sum()
+}
+
+/// A relative date expression like "today", "yesterday", or "last week",
+/// resolved to an absolute instant relative to `now`.
+pub fn resolve_relative_date(expr: &str, now: SystemTime) -> Option<SystemTime> {
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+    let normalized = expr.trim().to_lowercase();
+
+    match normalized.as_str() {
+        "today" => Some(now),
+        "yesterday" => Some(now - DAY),
+        "last week" => Some(now - DAY * 7),
+        "last month" => Some(now - DAY * 30),
+        "last year" => Some(now - DAY * 365),
+        _ => {
+            if let Some(days) = normalized.strip_suffix(" days ago").and_then(|n| n.trim().parse::<u64>().ok()) {
+                return Some(now - DAY * days as u32);
+            }
+            // "after:2024-01" style absolute month reference is handled by the
+            // caller (`MailEngine::search_messages` takes an already-resolved
+            // `SystemTime`); unrecognized relative expressions resolve to None
+            // so the caller can fall back to treating the token as search text.
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(id: &str, subject: &str, days_old: u64, now: SystemTime) -> IndexedMessage {
+        IndexedMessage {
+            id: id.to_string(),
+            subject: subject.to_string(),
+            body: "invoice attached for services rendered".to_string(),
+            received_at: now - Duration::from_secs(days_old * 24 * 60 * 60),
+        }
+    }
+
+    #[test]
+    fn bounds_results_by_date_range() {
+        let now = SystemTime::now();
+        let index = vec![
+            msg("old", "invoice", 40, now),
+            msg("recent", "invoice", 2, now),
+        ];
+        let engine = MailEngine::new();
+        let query = SearchQuery {
+            text: "invoice".to_string(),
+            since: Some(now - Duration::from_secs(30 * 24 * 60 * 60)),
+            until: None,
+        };
+
+        let results = engine.search_messages(&index, &query);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].message_id, "recent");
+    }
+
+    #[test]
+    fn search_results_include_highlighted_snippet() {
+        let now = SystemTime::now();
+        let index = vec![msg("recent", "invoice", 1, now)];
+        let engine = MailEngine::new();
+        let query = SearchQuery {
+            text: "invoice".to_string(),
+            ..Default::default()
+        };
+
+        let results = engine.search_messages(&index, &query);
+        let snippet = results[0].highlighted_snippet.as_deref().unwrap();
+        assert!(snippet.contains("<mark>invoice</mark>"));
+    }
+
+    #[test]
+    fn relative_dates_resolve_to_absolute_range() {
+        let now = SystemTime::now();
+        let since = resolve_relative_date("last week", now).unwrap();
+        assert!(since < now);
+        assert!(now.duration_since(since).unwrap() >= Duration::from_secs(6 * 24 * 60 * 60));
+        assert!(resolve_relative_date("not a date", now).is_none());
+    }
+
+    #[test]
+    fn compiling_a_query_only_adds_the_date_predicates_that_were_asked_for() {
+        let bare = compile_search_query(&SearchQuery { text: "invoice".to_string(), ..Default::default() });
+        assert!(!bare.sql.contains("received_at"));
+
+        let bounded = compile_search_query(&SearchQuery {
+            text: "invoice".to_string(),
+            since: Some(SystemTime::now()),
+            until: Some(SystemTime::now()),
+        });
+        assert!(bounded.sql.contains("received_at >= ?2"));
+        assert!(bounded.sql.contains("received_at <= ?3"));
+    }
+}
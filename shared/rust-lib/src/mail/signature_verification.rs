@@ -0,0 +1,145 @@
+//! PGP/MIME and S/MIME signature detection for a parsed message, so it can
+//! report whether it claims to be signed and by whom.
+//!
+//! **Not wired into any provider yet.** No `Message`/`MailMessage` type in
+//! `mail::providers` has an `encryption` field for this to populate, and
+//! neither `gmail.rs` nor `outlook.rs` calls
+//! [`detect_signature_scheme`]/[`verify_detached_pgp_signature`] — this
+//! module is exercised only by its own unit tests below. It's scaffolding
+//! for that wiring to land against, not a delivered feature.
+//!
+//! Actually checking a signature needs a real OpenPGP/S-MIME implementation
+//! (e.g. the `sequoia-openpgp` and `openssl` crates), which this crate
+//! doesn't have. [`verify_detached_pgp_signature`] never reports
+//! [`SignatureValidity::Valid`] or `Invalid` as a result — both outcomes
+//! require an actual cryptographic check this crate can't perform, and
+//! reporting either from a fake digest comparison would let a message be
+//! trusted (or distrusted) on the basis of crypto that isn't real. It
+//! reports [`SignatureValidity::Unverifiable`] instead whenever a key is
+//! present, keeping `UnknownKey` only for the legitimately-no-key case.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureScheme {
+    PgpMime,
+    SMime,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureValidity {
+    Valid,
+    Invalid,
+    /// A key was available, but this build has no real OpenPGP/S-MIME
+    /// implementation to check the signature against it with — reported
+    /// instead of guessing at `Valid`/`Invalid` from fake crypto.
+    Unverifiable,
+    /// The signature is well-formed but no matching public key is
+    /// available in the keychain to check it against.
+    UnknownKey,
+}
+
+/// Signature (and, eventually, encryption) facts attached to a parsed
+/// message.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MessageEncryption {
+    pub scheme: Option<SignatureScheme>,
+    pub validity: Option<SignatureValidity>,
+    pub signer: Option<String>,
+    /// Whether the body itself was encrypted (as opposed to only signed).
+    /// Decryption is gated on a matching private key being available in
+    /// the keychain, which this crate doesn't attempt here.
+    pub body_encrypted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey {
+    pub owner: String,
+    pub key_material: Vec<u8>,
+}
+
+/// Identify a signature scheme from a MIME `Content-Type` header, e.g.
+/// `multipart/signed; protocol="application/pgp-signature"`.
+pub fn detect_signature_scheme(content_type: &str) -> Option<SignatureScheme> {
+    let lower = content_type.to_ascii_lowercase();
+    if !lower.starts_with("multipart/signed") {
+        return None;
+    }
+    if lower.contains("application/pgp-signature") {
+        Some(SignatureScheme::PgpMime)
+    } else if lower.contains("pkcs7-signature") {
+        Some(SignatureScheme::SMime)
+    } else {
+        None
+    }
+}
+
+/// Report on a detached PGP signature over `signed_content` without
+/// actually checking it cryptographically (see the module doc comment).
+/// Without a matching `key` the signature can't be tied to anyone at all,
+/// which is reported as [`SignatureValidity::UnknownKey`]; with a key, the
+/// honest answer this crate can give is [`SignatureValidity::Unverifiable`]
+/// rather than a guessed `Valid`/`Invalid`.
+pub fn verify_detached_pgp_signature(_signed_content: &[u8], _signature: &[u8], key: Option<&PublicKey>) -> MessageEncryption {
+    match key {
+        None => MessageEncryption {
+            scheme: Some(SignatureScheme::PgpMime),
+            validity: Some(SignatureValidity::UnknownKey),
+            signer: None,
+            body_encrypted: false,
+        },
+        Some(key) => MessageEncryption {
+            scheme: Some(SignatureScheme::PgpMime),
+            validity: Some(SignatureValidity::Unverifiable),
+            signer: Some(key.owner.clone()),
+            body_encrypted: false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> PublicKey {
+        PublicKey { owner: "alice@example.com".to_string(), key_material: vec![9u8; 32] }
+    }
+
+    #[test]
+    fn detects_pgp_mime_and_s_mime_from_content_type() {
+        assert_eq!(
+            detect_signature_scheme(r#"multipart/signed; protocol="application/pgp-signature""#),
+            Some(SignatureScheme::PgpMime)
+        );
+        assert_eq!(
+            detect_signature_scheme(r#"multipart/signed; protocol="application/pkcs7-signature""#),
+            Some(SignatureScheme::SMime)
+        );
+        assert_eq!(detect_signature_scheme("text/plain"), None);
+    }
+
+    #[test]
+    fn a_signed_message_with_a_known_key_is_reported_unverifiable_not_valid() {
+        let content = b"Launch is confirmed for Friday.";
+        let signature = b"not a real signature";
+
+        let result = verify_detached_pgp_signature(content, signature, Some(&key()));
+        assert_eq!(result.validity, Some(SignatureValidity::Unverifiable));
+        assert_eq!(result.signer.as_deref(), Some("alice@example.com"));
+    }
+
+    #[test]
+    fn a_tampered_message_is_still_reported_unverifiable_not_invalid() {
+        let signature = b"not a real signature";
+
+        let result = verify_detached_pgp_signature(b"Launch is confirmed for Monday.", signature, Some(&key()));
+        assert_eq!(result.validity, Some(SignatureValidity::Unverifiable));
+    }
+
+    #[test]
+    fn a_missing_key_is_reported_as_unknown_rather_than_unverifiable() {
+        let content = b"Launch is confirmed for Friday.";
+        let signature = b"not a real signature";
+
+        let result = verify_detached_pgp_signature(content, signature, None);
+        assert_eq!(result.validity, Some(SignatureValidity::UnknownKey));
+    }
+}
@@ -0,0 +1,8 @@
+//! SMTP client pieces: DKIM signing and connection pooling. There is no
+//! message submission over SMTP here.
+
+pub mod dkim;
+pub mod pool;
+
+pub use dkim::{sign_dkim, DkimSignature, DkimSigningKey};
+pub use pool::{PooledSmtpConnection, SmtpConnectionPool, KEEPALIVE_INTERVAL};
@@ -0,0 +1,99 @@
+//! SMTP connection pooling and keepalive, mirroring the IMAP connection
+//! pool (`mail::imap::ConnectionPool`) so both protocols reuse connections
+//! the same way instead of opening one per send.
+
+use crate::mail::AccountId;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+/// How long an idle SMTP connection may sit in the pool before it's assumed
+/// dead and a `NOOP` keepalive (or a fresh connection) is required.
+pub const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone)]
+pub struct PooledSmtpConnection {
+    pub id: u64,
+    pub account_id: AccountId,
+    pub last_used: SystemTime,
+}
+
+#[derive(Debug, Default)]
+pub struct SmtpConnectionPool {
+    idle: HashMap<u64, PooledSmtpConnection>,
+    next_id: u64,
+}
+
+impl SmtpConnectionPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return an idle, still-fresh connection for `account_id` if one
+    /// exists, removing it from the idle set (the caller now owns it).
+    pub fn checkout(&mut self, account_id: &str, now: SystemTime) -> Option<PooledSmtpConnection> {
+        let id = self
+            .idle
+            .values()
+            .find(|c| c.account_id == account_id && !needs_keepalive(c, now))
+            .map(|c| c.id)?;
+        self.idle.remove(&id)
+    }
+
+    /// Whether a checked-out connection needs a `NOOP` keepalive before
+    /// reuse, given how long it's been idle.
+    pub fn needs_keepalive(&self, conn: &PooledSmtpConnection, now: SystemTime) -> bool {
+        needs_keepalive(conn, now)
+    }
+
+    /// Return a connection to the pool after use.
+    pub fn release(&mut self, account_id: AccountId, now: SystemTime) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.idle.insert(
+            id,
+            PooledSmtpConnection {
+                id,
+                account_id,
+                last_used: now,
+            },
+        );
+        id
+    }
+
+    pub fn idle_count(&self) -> usize {
+        self.idle.len()
+    }
+}
+
+fn needs_keepalive(conn: &PooledSmtpConnection, now: SystemTime) -> bool {
+    now.duration_since(conn.last_used)
+        .map(|idle_for| idle_for >= KEEPALIVE_INTERVAL)
+        .unwrap_or(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reuses_fresh_idle_connection() {
+        let mut pool = SmtpConnectionPool::new();
+        let now = SystemTime::now();
+        pool.release("acct-1".to_string(), now);
+
+        let checked_out = pool.checkout("acct-1", now + Duration::from_secs(5));
+        assert!(checked_out.is_some());
+        assert_eq!(pool.idle_count(), 0);
+    }
+
+    #[test]
+    fn stale_connection_is_not_handed_out_without_keepalive() {
+        let mut pool = SmtpConnectionPool::new();
+        let now = SystemTime::now();
+        pool.release("acct-1".to_string(), now);
+
+        let checked_out = pool.checkout("acct-1", now + KEEPALIVE_INTERVAL);
+        assert!(checked_out.is_none());
+        assert_eq!(pool.idle_count(), 1);
+    }
+}
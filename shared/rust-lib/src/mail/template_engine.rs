@@ -0,0 +1,255 @@
+//! Compose mail from a mustache-style template: `{{var}}` substitution,
+//! `{{#if var}}...{{/if}}` conditionals, and `{{#each list}}...{{/each}}`
+//! loops for recipient-specific mail merge.
+//!
+//! Missing variables are handled according to [`MissingVarPolicy`]: fail
+//! loudly during a preview/send-check (`Strict`), or degrade gracefully
+//! for best-effort rendering (`LeaveUnknown`/`Blank`).
+
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateValue {
+    Text(String),
+    List(Vec<HashMap<String, String>>),
+}
+
+pub type TemplateVars = HashMap<String, TemplateValue>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissingVarPolicy {
+    /// Fail with every missing variable name instead of guessing.
+    Strict,
+    /// Leave `{{var}}` untouched in the output.
+    LeaveUnknown,
+    /// Substitute an empty string.
+    Blank,
+}
+
+/// Whether substituted values need HTML-escaping. Plain-text templates
+/// (e.g. notification emails' text part) never escape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateFormat {
+    PlainText,
+    Html,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    MissingVariables(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Text(String),
+    Var(String),
+    If { var: String, body: Vec<Node> },
+    Each { var: String, body: Vec<Node> },
+}
+
+/// Parse `template` into a node tree up to (but not including) a
+/// `{{/tag}}` closing the current block, or the end of input at the top
+/// level. Returns the parsed nodes and the position just past the
+/// consumed closing tag (or the input length at the top level).
+fn parse_block(template: &str, mut pos: usize) -> (Vec<Node>, usize) {
+    let mut nodes = Vec::new();
+    let bytes = template.as_bytes();
+
+    while pos < bytes.len() {
+        match template[pos..].find("{{") {
+            None => {
+                nodes.push(Node::Text(template[pos..].to_string()));
+                pos = bytes.len();
+            }
+            Some(offset) => {
+                if offset > 0 {
+                    nodes.push(Node::Text(template[pos..pos + offset].to_string()));
+                }
+                let tag_start = pos + offset + 2;
+                let Some(tag_end_offset) = template[tag_start..].find("}}") else {
+                    // Unterminated tag: treat the rest as literal text.
+                    nodes.push(Node::Text(template[pos + offset..].to_string()));
+                    return (nodes, bytes.len());
+                };
+                let tag = template[tag_start..tag_start + tag_end_offset].trim();
+                let after_tag = tag_start + tag_end_offset + 2;
+
+                if let Some(name) = tag.strip_prefix("#if ") {
+                    let (body, next) = parse_block(template, after_tag);
+                    nodes.push(Node::If { var: name.trim().to_string(), body });
+                    pos = next;
+                } else if let Some(name) = tag.strip_prefix("#each ") {
+                    let (body, next) = parse_block(template, after_tag);
+                    nodes.push(Node::Each { var: name.trim().to_string(), body });
+                    pos = next;
+                } else if tag == "/if" || tag == "/each" {
+                    return (nodes, after_tag);
+                } else {
+                    nodes.push(Node::Var(tag.to_string()));
+                    pos = after_tag;
+                }
+            }
+        }
+    }
+
+    (nodes, pos)
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+fn is_truthy(value: &TemplateValue) -> bool {
+    match value {
+        TemplateValue::Text(text) => !text.is_empty(),
+        TemplateValue::List(items) => !items.is_empty(),
+    }
+}
+
+fn render_nodes(nodes: &[Node], vars: &TemplateVars, format: TemplateFormat, policy: MissingVarPolicy, missing: &mut Vec<String>, out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Var(name) => match vars.get(name) {
+                Some(TemplateValue::Text(value)) => {
+                    out.push_str(&if format == TemplateFormat::Html { escape_html(value) } else { value.clone() })
+                }
+                // A list referenced as a scalar renders empty; it's present, just the wrong shape.
+                Some(TemplateValue::List(_)) => {}
+                None => match policy {
+                    MissingVarPolicy::Strict => missing.push(name.clone()),
+                    MissingVarPolicy::LeaveUnknown => {
+                        out.push_str("{{");
+                        out.push_str(name);
+                        out.push_str("}}");
+                    }
+                    MissingVarPolicy::Blank => {}
+                },
+            },
+            Node::If { var, body } => {
+                let condition = match vars.get(var) {
+                    Some(value) => is_truthy(value),
+                    None => {
+                        if policy == MissingVarPolicy::Strict {
+                            missing.push(var.clone());
+                        }
+                        false
+                    }
+                };
+                if condition {
+                    render_nodes(body, vars, format, policy, missing, out);
+                }
+            }
+            Node::Each { var, body } => match vars.get(var) {
+                Some(TemplateValue::List(items)) => {
+                    for item in items {
+                        let mut scope = vars.clone();
+                        for (key, value) in item {
+                            scope.insert(key.clone(), TemplateValue::Text(value.clone()));
+                        }
+                        render_nodes(body, &scope, format, policy, missing, out);
+                    }
+                }
+                Some(TemplateValue::Text(_)) => {}
+                None => {
+                    if policy == MissingVarPolicy::Strict {
+                        missing.push(var.clone());
+                    }
+                }
+            },
+        }
+    }
+}
+
+fn render_with_policy(template: &str, vars: &TemplateVars, format: TemplateFormat, policy: MissingVarPolicy) -> Result<String, TemplateError> {
+    let (nodes, _) = parse_block(template, 0);
+    let mut missing = Vec::new();
+    let mut out = String::new();
+    render_nodes(&nodes, vars, format, policy, &mut missing, &mut out);
+
+    if !missing.is_empty() {
+        missing.sort();
+        missing.dedup();
+        return Err(TemplateError::MissingVariables(missing));
+    }
+    Ok(out)
+}
+
+/// Render `template`, failing with every referenced-but-missing variable
+/// name instead of guessing what the caller meant.
+pub fn render_strict(template: &str, vars: &TemplateVars, format: TemplateFormat) -> Result<String, TemplateError> {
+    render_with_policy(template, vars, format, MissingVarPolicy::Strict)
+}
+
+/// Render `template`, degrading missing variables according to `policy`
+/// (which must not be [`MissingVarPolicy::Strict`] — use
+/// [`render_strict`] for that).
+pub fn render(template: &str, vars: &TemplateVars, format: TemplateFormat, policy: MissingVarPolicy) -> String {
+    render_with_policy(template, vars, format, policy).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, TemplateValue)]) -> TemplateVars {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.clone())).collect()
+    }
+
+    #[test]
+    fn strict_mode_reports_every_missing_variable() {
+        let template = "Hi {{name}}, your invite to {{event}} is confirmed.";
+        let result = render_strict(template, &vars(&[]), TemplateFormat::PlainText);
+        assert_eq!(
+            result,
+            Err(TemplateError::MissingVariables(vec!["event".to_string(), "name".to_string()]))
+        );
+    }
+
+    #[test]
+    fn lenient_mode_leaves_unknown_placeholders_untouched() {
+        let template = "Hi {{name}}!";
+        let output = render(template, &vars(&[]), TemplateFormat::PlainText, MissingVarPolicy::LeaveUnknown);
+        assert_eq!(output, "Hi {{name}}!");
+    }
+
+    #[test]
+    fn lenient_mode_blanks_unknown_placeholders() {
+        let template = "Hi {{name}}!";
+        let output = render(template, &vars(&[]), TemplateFormat::PlainText, MissingVarPolicy::Blank);
+        assert_eq!(output, "Hi !");
+    }
+
+    #[test]
+    fn html_format_escapes_substituted_values() {
+        let template = "<p>{{bio}}</p>";
+        let bio = vars(&[("bio", TemplateValue::Text("<script>alert(1)</script>".to_string()))]);
+        let output = render_strict(template, &bio, TemplateFormat::Html).unwrap();
+        assert_eq!(output, "<p>&lt;script&gt;alert(1)&lt;/script&gt;</p>");
+    }
+
+    #[test]
+    fn loops_over_a_recipient_list() {
+        let template = "{{#each recipients}}Hi {{name}}! {{/each}}";
+        let recipients = vec![
+            HashMap::from([("name".to_string(), "Ada".to_string())]),
+            HashMap::from([("name".to_string(), "Grace".to_string())]),
+        ];
+        let data = vars(&[("recipients", TemplateValue::List(recipients))]);
+        let output = render_strict(template, &data, TemplateFormat::PlainText).unwrap();
+        assert_eq!(output, "Hi Ada! Hi Grace! ");
+    }
+
+    #[test]
+    fn conditional_skips_body_when_falsy() {
+        let template = "{{#if vip}}VIP access{{/if}}";
+        let data = vars(&[("vip", TemplateValue::Text(String::new()))]);
+        let output = render_strict(template, &data, TemplateFormat::PlainText).unwrap();
+        assert_eq!(output, "");
+    }
+}
@@ -0,0 +1,255 @@
+//! Message threading (JWZ algorithm): builds reply trees from the
+//! `References`/`In-Reply-To` headers, falling back to subject grouping
+//! only for messages that carry neither header. Threading is folder- and
+//! account-agnostic — it only looks at message-id/subject/date, so a
+//! thread can span multiple folders or accounts.
+//!
+//! <https://www.jwz.org/doc/threading.html>
+
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use super::MessageId;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageHeader {
+    pub id: MessageId,
+    pub in_reply_to: Option<MessageId>,
+    pub references: Vec<MessageId>,
+    pub subject: String,
+    pub date: SystemTime,
+}
+
+/// A node in a thread tree. `message` is `None` for a "ghost" — a
+/// referenced message that was never fetched (e.g. deleted, or sitting in
+/// a folder that wasn't synced) but is still needed to link its children
+/// together.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThreadNode {
+    pub message: Option<MessageHeader>,
+    pub children: Vec<ThreadNode>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailThread {
+    pub root: ThreadNode,
+}
+
+#[derive(Debug, Default)]
+struct Container {
+    message: Option<MessageHeader>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+}
+
+#[derive(Debug, Default)]
+pub struct ThreadingEngine {
+    arena: Vec<Container>,
+    index_by_id: HashMap<MessageId, usize>,
+}
+
+impl ThreadingEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn get_or_create(&mut self, id: &str) -> usize {
+        if let Some(&index) = self.index_by_id.get(id) {
+            return index;
+        }
+        let index = self.arena.len();
+        self.arena.push(Container::default());
+        self.index_by_id.insert(id.to_string(), index);
+        index
+    }
+
+    fn link(&mut self, parent: usize, child: usize) {
+        if parent == child || self.arena[child].parent.is_some() {
+            return;
+        }
+        // Don't create a cycle by linking an ancestor of `parent` as its
+        // own descendant.
+        let mut ancestor = Some(parent);
+        while let Some(current) = ancestor {
+            if current == child {
+                return;
+            }
+            ancestor = self.arena[current].parent;
+        }
+        self.arena[child].parent = Some(parent);
+        self.arena[parent].children.push(child);
+    }
+
+    /// Feed one message's headers into the threader. Call this for every
+    /// message before calling [`ThreadingEngine::build_threads`].
+    pub fn add_message(&mut self, header: MessageHeader) {
+        let own_index = self.get_or_create(&header.id);
+
+        let mut chain: Vec<&str> = header.references.iter().map(String::as_str).collect();
+        if let Some(in_reply_to) = header.in_reply_to.as_deref() {
+            if chain.last().copied() != Some(in_reply_to) {
+                chain.push(in_reply_to);
+            }
+        }
+
+        let mut previous = None;
+        for id in &chain {
+            let index = self.get_or_create(id);
+            if let Some(parent) = previous {
+                self.link(parent, index);
+            }
+            previous = Some(index);
+        }
+        if let Some(parent) = previous {
+            self.link(parent, own_index);
+        }
+
+        self.arena[own_index].message = Some(header);
+    }
+
+    /// Normalize a subject for fallback grouping: strip repeated
+    /// `Re:`/`Fwd:`/`Fw:` prefixes and surrounding whitespace, case-
+    /// insensitively.
+    fn normalized_subject(subject: &str) -> String {
+        let mut remaining = subject.trim();
+        loop {
+            let lower = remaining.to_ascii_lowercase();
+            let stripped = ["re:", "fwd:", "fw:"].iter().find_map(|prefix| {
+                lower.starts_with(prefix).then(|| remaining[prefix.len()..].trim_start())
+            });
+            match stripped {
+                Some(next) => remaining = next,
+                None => break,
+            }
+        }
+        remaining.to_ascii_lowercase()
+    }
+
+    fn sort_by_date(&self, node: &mut ThreadNode) {
+        node.children.sort_by_key(|child| child.message.as_ref().map(|m| m.date).unwrap_or(SystemTime::UNIX_EPOCH));
+        for child in &mut node.children {
+            self.sort_by_date(child);
+        }
+    }
+
+    fn to_tree(&self, index: usize) -> ThreadNode {
+        ThreadNode {
+            message: self.arena[index].message.clone(),
+            children: self.arena[index].children.iter().map(|&child| self.to_tree(child)).collect(),
+        }
+    }
+
+    /// Build the final set of threads: one per root container, after
+    /// merging headerless roots that share a normalized subject.
+    pub fn build_threads(&self) -> Vec<EmailThread> {
+        let roots: Vec<usize> = (0..self.arena.len()).filter(|&index| self.arena[index].parent.is_none()).collect();
+
+        // Headerless roots (no References/In-Reply-To at all) fall back to
+        // subject grouping; roots that are ghosts (ended up parentless only
+        // because their real parent was never seen) keep their own thread.
+        let mut by_subject: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut standalone: Vec<usize> = Vec::new();
+
+        for &index in &roots {
+            match &self.arena[index].message {
+                Some(message) if message.in_reply_to.is_none() && message.references.is_empty() => {
+                    by_subject.entry(Self::normalized_subject(&message.subject)).or_default().push(index);
+                }
+                _ => standalone.push(index),
+            }
+        }
+
+        let mut threads = Vec::new();
+        for index in standalone {
+            let mut tree = self.to_tree(index);
+            self.sort_by_date(&mut tree);
+            threads.push(EmailThread { root: tree });
+        }
+
+        for (_, group) in by_subject {
+            if group.len() == 1 {
+                let mut tree = self.to_tree(group[0]);
+                self.sort_by_date(&mut tree);
+                threads.push(EmailThread { root: tree });
+                continue;
+            }
+
+            // Multiple unlinked messages share a subject: group them under
+            // a ghost root so they present as one thread.
+            let mut children: Vec<ThreadNode> = group.into_iter().map(|index| self.to_tree(index)).collect();
+            children.sort_by_key(|child| child.message.as_ref().map(|m| m.date).unwrap_or(SystemTime::UNIX_EPOCH));
+            threads.push(EmailThread { root: ThreadNode { message: None, children } });
+        }
+
+        threads
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::epoch_plus;
+
+    fn header(id: &str, in_reply_to: Option<&str>, references: &[&str], subject: &str, seconds: u64) -> MessageHeader {
+        MessageHeader {
+            id: id.to_string(),
+            in_reply_to: in_reply_to.map(str::to_string),
+            references: references.iter().map(|r| r.to_string()).collect(),
+            subject: subject.to_string(),
+            date: epoch_plus(seconds),
+        }
+    }
+
+    #[test]
+    fn a_reply_chain_collapses_into_one_thread() {
+        let mut engine = ThreadingEngine::new();
+        engine.add_message(header("msg-1", None, &[], "Launch plan", 0));
+        engine.add_message(header("msg-2", Some("msg-1"), &["msg-1"], "Re: Launch plan", 60));
+        engine.add_message(header("msg-3", Some("msg-2"), &["msg-1", "msg-2"], "Re: Launch plan", 120));
+
+        let threads = engine.build_threads();
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].root.message.as_ref().unwrap().id, "msg-1");
+        assert_eq!(threads[0].root.children.len(), 1);
+        assert_eq!(threads[0].root.children[0].message.as_ref().unwrap().id, "msg-2");
+        assert_eq!(threads[0].root.children[0].children[0].message.as_ref().unwrap().id, "msg-3");
+    }
+
+    #[test]
+    fn two_unrelated_subjects_stay_separate() {
+        let mut engine = ThreadingEngine::new();
+        engine.add_message(header("msg-1", None, &[], "Launch plan", 0));
+        engine.add_message(header("msg-2", None, &[], "Lunch tomorrow?", 60));
+
+        let threads = engine.build_threads();
+        assert_eq!(threads.len(), 2);
+    }
+
+    #[test]
+    fn a_missing_intermediate_message_becomes_a_ghost_node() {
+        let mut engine = ThreadingEngine::new();
+        engine.add_message(header("msg-1", None, &[], "Launch plan", 0));
+        // msg-3 references msg-2, which is never added — msg-2 should
+        // still thread msg-1 and msg-3 together as a ghost.
+        engine.add_message(header("msg-3", Some("msg-2"), &["msg-1", "msg-2"], "Re: Launch plan", 120));
+
+        let threads = engine.build_threads();
+        assert_eq!(threads.len(), 1);
+        let root = &threads[0].root;
+        assert_eq!(root.message.as_ref().unwrap().id, "msg-1");
+        assert_eq!(root.children[0].message, None);
+        assert_eq!(root.children[0].children[0].message.as_ref().unwrap().id, "msg-3");
+    }
+
+    #[test]
+    fn headerless_messages_with_the_same_subject_are_grouped() {
+        let mut engine = ThreadingEngine::new();
+        engine.add_message(header("msg-1", None, &[], "Status update", 0));
+        engine.add_message(header("msg-2", None, &[], "Re: Status update", 60));
+
+        let threads = engine.build_threads();
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].root.message, None);
+        assert_eq!(threads[0].root.children.len(), 2);
+    }
+}
@@ -0,0 +1,186 @@
+//! Search analytics persisted across restarts: which queries were run, how
+//! often, and when, so a "recent/frequent searches" surface doesn't go
+//! empty every time the process restarts.
+
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryStat {
+    pub query: String,
+    pub count: u64,
+    pub last_used: SystemTime,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchAnalytics {
+    queries: HashMap<String, QueryStat>,
+}
+
+impl SearchAnalytics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_query(&mut self, query: &str, now: SystemTime) {
+        let stat = self.queries.entry(query.to_string()).or_insert_with(|| QueryStat {
+            query: query.to_string(),
+            count: 0,
+            last_used: now,
+        });
+        stat.count += 1;
+        stat.last_used = now;
+    }
+
+    pub fn stat(&self, query: &str) -> Option<&QueryStat> {
+        self.queries.get(query)
+    }
+
+    /// Most-run queries first, ties broken by most recently used.
+    pub fn top_queries(&self, limit: usize) -> Vec<QueryStat> {
+        let mut stats: Vec<_> = self.queries.values().cloned().collect();
+        stats.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| b.last_used.cmp(&a.last_used)));
+        stats.truncate(limit);
+        stats
+    }
+
+    /// Serialize to a snapshot a caller can write to disk and hand back to
+    /// [`SearchAnalytics::restore`] on the next startup. Hand-rolled rather
+    /// than pulling in `serde_json`, matching `database::calendar_export`.
+    pub fn to_snapshot(&self) -> String {
+        let mut entries: Vec<_> = self.queries.values().collect();
+        entries.sort_by(|a, b| a.query.cmp(&b.query));
+        let body = entries
+            .iter()
+            .map(|stat| {
+                format!(
+                    "{{\"query\":\"{}\",\"count\":{},\"last_used\":{}}}",
+                    json_escape(&stat.query),
+                    stat.count,
+                    unix_seconds(stat.last_used)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        format!("{{\"queries\":[{body}]}}")
+    }
+
+    /// Rebuild analytics from a snapshot produced by [`to_snapshot`].
+    /// Unlike most parsing in this crate, a malformed entry is skipped
+    /// rather than failing the whole restore — a corrupted analytics file
+    /// should only lose history, not block startup.
+    pub fn restore(snapshot: &str) -> Self {
+        let mut analytics = Self::new();
+        let Some(queries_pos) = snapshot.find("\"queries\"") else {
+            return analytics;
+        };
+        let Some(bracket_offset) = snapshot[queries_pos..].find('[') else {
+            return analytics;
+        };
+        let array_start = queries_pos + bracket_offset + 1;
+        let Some(array_end) = snapshot[array_start..].rfind(']') else {
+            return analytics;
+        };
+
+        for entry in split_top_level_objects(&snapshot[array_start..array_start + array_end]) {
+            if let Some(stat) = parse_entry(&entry) {
+                analytics.queries.insert(stat.query.clone(), stat);
+            }
+        }
+        analytics
+    }
+}
+
+fn split_top_level_objects(body: &str) -> Vec<String> {
+    let mut objects = Vec::new();
+    let mut depth = 0i32;
+    let mut start = None;
+    for (i, ch) in body.char_indices() {
+        match ch {
+            '{' => {
+                if depth == 0 {
+                    start = Some(i);
+                }
+                depth += 1;
+            }
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    if let Some(s) = start.take() {
+                        objects.push(body[s..=i].to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    objects
+}
+
+fn parse_entry(entry: &str) -> Option<QueryStat> {
+    let query = extract_string_field(entry, "query")?;
+    let count = extract_u64_field(entry, "count")?;
+    let last_used_secs = extract_u64_field(entry, "last_used")?;
+    Some(QueryStat { query, count, last_used: UNIX_EPOCH + Duration::from_secs(last_used_secs) })
+}
+
+fn extract_string_field(raw: &str, field: &str) -> Option<String> {
+    let needle = format!("\"{field}\"");
+    let field_start = raw.find(&needle)? + needle.len();
+    let after_colon = raw[field_start..].find(':')? + field_start + 1;
+    let rest = raw[after_colon..].trim_start();
+    let quote_start = rest.strip_prefix('"')?;
+    let end = quote_start.find('"')?;
+    Some(quote_start[..end].to_string())
+}
+
+fn extract_u64_field(raw: &str, field: &str) -> Option<u64> {
+    let needle = format!("\"{field}\"");
+    let field_start = raw.find(&needle)? + needle.len();
+    let after_colon = raw[field_start..].find(':')? + field_start + 1;
+    let rest = raw[after_colon..].trim_start();
+    let end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+    rest[..end].parse().ok()
+}
+
+fn json_escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_restored_snapshot_matches_the_original() {
+        let mut analytics = SearchAnalytics::new();
+        analytics.record_query("invoice", UNIX_EPOCH + Duration::from_secs(100));
+        analytics.record_query("invoice", UNIX_EPOCH + Duration::from_secs(200));
+        analytics.record_query("roadmap", UNIX_EPOCH + Duration::from_secs(150));
+
+        let restored = SearchAnalytics::restore(&analytics.to_snapshot());
+        assert_eq!(restored, analytics);
+        assert_eq!(restored.stat("invoice").unwrap().count, 2);
+    }
+
+    #[test]
+    fn top_queries_ranks_by_count_then_recency() {
+        let mut analytics = SearchAnalytics::new();
+        analytics.record_query("a", UNIX_EPOCH + Duration::from_secs(1));
+        analytics.record_query("b", UNIX_EPOCH + Duration::from_secs(2));
+        analytics.record_query("b", UNIX_EPOCH + Duration::from_secs(3));
+
+        let top = analytics.top_queries(1);
+        assert_eq!(top[0].query, "b");
+    }
+
+    #[test]
+    fn a_corrupted_snapshot_restores_empty_instead_of_panicking() {
+        let restored = SearchAnalytics::restore("not json at all");
+        assert_eq!(restored, SearchAnalytics::new());
+    }
+}
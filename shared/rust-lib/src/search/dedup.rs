@@ -0,0 +1,126 @@
+//! Cross-provider result deduplication: the same logical item can surface
+//! from more than one provider (a meeting synced natively and via its
+//! CalDAV fallback, a doc mirrored into two trackers). Collapses
+//! near-duplicate hits down to the highest-relevance copy, using
+//! [`super::fuzzy::edit_distance`] for the same typo-tolerant comparison
+//! the query matcher uses, and records which other providers also had it.
+
+use super::{fuzzy::edit_distance, SearchHit};
+
+/// How similar two titles must be (as a fraction of the longer title's
+/// length) to be treated as the same item.
+const TITLE_SIMILARITY_THRESHOLD: f64 = 0.85;
+
+fn titles_match(a: &str, b: &str) -> bool {
+    let (a, b) = (a.trim().to_lowercase(), b.trim().to_lowercase());
+    if a == b {
+        return true;
+    }
+    let longer = a.chars().count().max(b.chars().count());
+    if longer == 0 {
+        return true;
+    }
+    let similarity = 1.0 - (edit_distance(&a, &b) as f64 / longer as f64);
+    similarity >= TITLE_SIMILARITY_THRESHOLD
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeduplicatedHit {
+    pub hit: SearchHit,
+    /// Other providers that also surfaced this item, beyond `hit.provider`.
+    pub also_seen_in: Vec<String>,
+}
+
+/// Collapse duplicate hits (same content type, near-identical title) down
+/// to the highest-relevance copy per group. Grouping is done against each
+/// group's first member, so results are sensitive to input order the same
+/// way [`crate::calendar::dedup`]'s event grouping is.
+pub fn deduplicate_hits(hits: &[SearchHit]) -> Vec<DeduplicatedHit> {
+    let mut groups: Vec<Vec<SearchHit>> = Vec::new();
+
+    for hit in hits {
+        match groups
+            .iter_mut()
+            .find(|group| group[0].content_type == hit.content_type && titles_match(&group[0].title, &hit.title))
+        {
+            Some(group) => group.push(hit.clone()),
+            None => groups.push(vec![hit.clone()]),
+        }
+    }
+
+    groups.into_iter().map(dedup_group).collect()
+}
+
+fn dedup_group(group: Vec<SearchHit>) -> DeduplicatedHit {
+    let best_index = group
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.relevance.partial_cmp(&b.relevance).unwrap())
+        .map(|(index, _)| index)
+        .expect("groups are never empty");
+    let best = group[best_index].clone();
+
+    let mut also_seen_in: Vec<String> = group
+        .iter()
+        .map(|hit| hit.provider.clone())
+        .filter(|provider| provider != &best.provider)
+        .collect();
+    also_seen_in.sort();
+    also_seen_in.dedup();
+
+    DeduplicatedHit { hit: best, also_seen_in }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(provider: &str, title: &str, content_type: &str, relevance: f64) -> SearchHit {
+        SearchHit {
+            provider: provider.to_string(),
+            id: format!("{provider}-1"),
+            title: title.to_string(),
+            content_type: content_type.to_string(),
+            relevance,
+        }
+    }
+
+    #[test]
+    fn exact_title_duplicates_across_providers_collapse_to_one() {
+        let hits = vec![
+            hit("caldav", "Team sync", "event", 0.6),
+            hit("google_calendar", "Team sync", "event", 0.9),
+        ];
+
+        let deduped = deduplicate_hits(&hits);
+        assert_eq!(deduped.len(), 1);
+        assert_eq!(deduped[0].hit.provider, "google_calendar");
+        assert_eq!(deduped[0].also_seen_in, vec!["caldav".to_string()]);
+    }
+
+    #[test]
+    fn near_identical_titles_are_still_treated_as_duplicates() {
+        let hits = vec![hit("jira", "Fix login bug", "issue", 0.5), hit("confluence", "Fix login bu", "issue", 0.7)];
+
+        let deduped = deduplicate_hits(&hits);
+        assert_eq!(deduped.len(), 1);
+        assert_eq!(deduped[0].hit.provider, "confluence");
+    }
+
+    #[test]
+    fn different_content_types_are_never_merged_even_with_the_same_title() {
+        let hits = vec![hit("mail", "Launch", "email", 0.8), hit("calendar", "Launch", "event", 0.8)];
+
+        let deduped = deduplicate_hits(&hits);
+        assert_eq!(deduped.len(), 2);
+    }
+
+    #[test]
+    fn unrelated_hits_pass_through_untouched() {
+        let hits = vec![hit("mail", "Invoice", "email", 0.5), hit("jira", "Sprint planning", "issue", 0.4)];
+
+        let deduped = deduplicate_hits(&hits);
+        assert_eq!(deduped.len(), 2);
+        assert!(deduped.iter().all(|d| d.also_seen_in.is_empty()));
+    }
+}
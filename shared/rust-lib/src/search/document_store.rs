@@ -0,0 +1,102 @@
+//! A local cache of already-fetched document content, keyed by provider and
+//! document id, so a full index rebuild ([`super::rebuild::rebuild_index`])
+//! can replay from what was fetched before instead of re-querying every
+//! provider again — the slow, rate-limited part of a rebuild.
+//!
+//! The real store is a SQLite table (`provider TEXT, id TEXT,
+//! content_version TEXT, content TEXT`, primary key `(provider, id)`); this
+//! models the get/put/list contract callers depend on so swapping a real
+//! `rusqlite`/`sqlx` connection in later just replaces this struct's
+//! insides.
+
+use super::reindex::IndexedDocument;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredDocument {
+    pub document: IndexedDocument,
+    pub content: String,
+}
+
+#[derive(Debug, Default)]
+pub struct DocumentStore {
+    rows: HashMap<(String, String), StoredDocument>,
+}
+
+impl DocumentStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert or replace the cached content for `provider`'s `document`.
+    pub fn put(&mut self, provider: &str, document: IndexedDocument, content: String) {
+        self.rows.insert((provider.to_string(), document.id.clone()), StoredDocument { document, content });
+    }
+
+    pub fn get(&self, provider: &str, id: &str) -> Option<&StoredDocument> {
+        self.rows.get(&(provider.to_string(), id.to_string()))
+    }
+
+    /// Drop a document from the cache, e.g. once [`super::reindex`] reports
+    /// it was deleted upstream.
+    pub fn remove(&mut self, provider: &str, id: &str) -> Option<StoredDocument> {
+        self.rows.remove(&(provider.to_string(), id.to_string()))
+    }
+
+    /// Every document cached for `provider`, for rebuilding its slice of
+    /// the index without refetching from it.
+    pub fn documents_for_provider(&self, provider: &str) -> Vec<&StoredDocument> {
+        self.rows.iter().filter(|((p, _), _)| p == provider).map(|(_, row)| row).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(id: &str, version: &str) -> IndexedDocument {
+        IndexedDocument { id: id.to_string(), content_version: version.to_string() }
+    }
+
+    #[test]
+    fn put_then_get_round_trips_the_content() {
+        let mut store = DocumentStore::new();
+        store.put("gmail", doc("1", "v1"), "hello world".to_string());
+
+        let stored = store.get("gmail", "1").unwrap();
+        assert_eq!(stored.content, "hello world");
+        assert_eq!(stored.document, doc("1", "v1"));
+    }
+
+    #[test]
+    fn the_same_document_id_from_different_providers_does_not_collide() {
+        let mut store = DocumentStore::new();
+        store.put("gmail", doc("1", "v1"), "gmail content".to_string());
+        store.put("jira", doc("1", "v1"), "jira content".to_string());
+
+        assert_eq!(store.get("gmail", "1").unwrap().content, "gmail content");
+        assert_eq!(store.get("jira", "1").unwrap().content, "jira content");
+    }
+
+    #[test]
+    fn removing_a_document_makes_it_unavailable() {
+        let mut store = DocumentStore::new();
+        store.put("gmail", doc("1", "v1"), "hello".to_string());
+        assert!(store.remove("gmail", "1").is_some());
+        assert!(store.get("gmail", "1").is_none());
+        assert!(store.remove("gmail", "1").is_none());
+    }
+
+    #[test]
+    fn documents_for_provider_only_returns_that_providers_rows() {
+        let mut store = DocumentStore::new();
+        store.put("gmail", doc("1", "v1"), "a".to_string());
+        store.put("gmail", doc("2", "v1"), "b".to_string());
+        store.put("jira", doc("3", "v1"), "c".to_string());
+
+        let mut gmail_ids: Vec<&str> =
+            store.documents_for_provider("gmail").into_iter().map(|row| row.document.id.as_str()).collect();
+        gmail_ids.sort();
+        assert_eq!(gmail_ids, vec!["1", "2"]);
+    }
+}
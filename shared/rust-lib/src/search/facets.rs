@@ -0,0 +1,60 @@
+//! Faceted counts over a result set, so the search UI can render
+//! "Email (12) · Calendar (3) · Jira (5)" style filter chips alongside the
+//! results.
+
+use super::SearchHit;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Facets {
+    /// Counts keyed by `content_type` (e.g. "email", "event", "issue"),
+    /// sorted alphabetically for stable UI rendering.
+    pub by_content_type: BTreeMap<String, usize>,
+    /// Counts keyed by provider id.
+    pub by_provider: BTreeMap<String, usize>,
+}
+
+pub fn compute_facets(hits: &[SearchHit]) -> Facets {
+    let mut facets = Facets::default();
+    for hit in hits {
+        *facets.by_content_type.entry(hit.content_type.clone()).or_insert(0) += 1;
+        *facets.by_provider.entry(hit.provider.clone()).or_insert(0) += 1;
+    }
+    facets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(provider: &str, content_type: &str) -> SearchHit {
+        SearchHit {
+            provider: provider.to_string(),
+            id: "1".to_string(),
+            title: "title".to_string(),
+            content_type: content_type.to_string(),
+            relevance: 1.0,
+        }
+    }
+
+    #[test]
+    fn counts_hits_by_content_type_and_provider() {
+        let hits = vec![
+            hit("mail", "email"),
+            hit("mail", "email"),
+            hit("calendar", "event"),
+            hit("jira", "issue"),
+        ];
+
+        let facets = compute_facets(&hits);
+        assert_eq!(facets.by_content_type.get("email"), Some(&2));
+        assert_eq!(facets.by_content_type.get("event"), Some(&1));
+        assert_eq!(facets.by_provider.get("mail"), Some(&2));
+        assert_eq!(facets.by_provider.get("jira"), Some(&1));
+    }
+
+    #[test]
+    fn empty_hits_produce_empty_facets() {
+        assert_eq!(compute_facets(&[]), Facets::default());
+    }
+}
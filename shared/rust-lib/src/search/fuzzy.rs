@@ -0,0 +1,80 @@
+//! Fuzzy term matching using Levenshtein edit distance, for catching typos
+//! in search queries ("recieve" matching "receive").
+
+/// Levenshtein edit distance between two strings (case-insensitive).
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut prev_diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let temp = row[j + 1];
+            row[j + 1] = if ca == cb {
+                prev_diagonal
+            } else {
+                1 + prev_diagonal.min(row[j]).min(row[j + 1])
+            };
+            prev_diagonal = temp;
+        }
+    }
+    row[b.len()]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuzzyMatchOptions {
+    /// Maximum edit distance still considered a match.
+    pub max_distance: usize,
+}
+
+impl Default for FuzzyMatchOptions {
+    fn default() -> Self {
+        Self { max_distance: 2 }
+    }
+}
+
+/// Whether `candidate` fuzzily matches `query` within `options.max_distance`.
+pub fn fuzzy_matches(query: &str, candidate: &str, options: FuzzyMatchOptions) -> bool {
+    edit_distance(query, candidate) <= options.max_distance
+}
+
+/// Find every word in `words` that fuzzily matches `query`, sorted by edit
+/// distance (closest first).
+pub fn fuzzy_find<'a>(query: &str, words: &'a [String], options: FuzzyMatchOptions) -> Vec<&'a str> {
+    let mut scored: Vec<(usize, &str)> = words
+        .iter()
+        .map(|word| (edit_distance(query, word), word.as_str()))
+        .filter(|(distance, _)| *distance <= options.max_distance)
+        .collect();
+    scored.sort_by_key(|(distance, _)| *distance);
+    scored.into_iter().map(|(_, word)| word).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_strings_have_zero_distance() {
+        assert_eq!(edit_distance("receive", "receive"), 0);
+    }
+
+    #[test]
+    fn single_typo_matches_within_distance_two() {
+        assert!(fuzzy_matches("receive", "recieve", FuzzyMatchOptions::default()));
+    }
+
+    #[test]
+    fn unrelated_words_do_not_match() {
+        assert!(!fuzzy_matches("receive", "banana", FuzzyMatchOptions { max_distance: 2 }));
+    }
+
+    #[test]
+    fn fuzzy_find_orders_by_closeness() {
+        let words = vec!["receive".to_string(), "recieve".to_string(), "deceive".to_string()];
+        let matches = fuzzy_find("receive", &words, FuzzyMatchOptions { max_distance: 2 });
+        assert_eq!(matches, vec!["receive", "deceive", "recieve"]);
+    }
+}
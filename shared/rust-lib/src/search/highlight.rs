@@ -0,0 +1,83 @@
+//! General-purpose result highlighting, usable by any [`SearchProvider`](super::SearchProvider)
+//! hit, not just mail search (which has its own copy of this logic in
+//! [`crate::mail::search`] predating the cross-provider engine).
+
+/// Characters of context kept before a highlighted match; the window after
+/// the match start runs twice this long, the match included.
+const HIGHLIGHT_CONTEXT_CHARS: usize = 40;
+
+/// Find the first occurrence of any of `terms` in `content` and return a
+/// snippet around it with matches wrapped in `<mark>…</mark>`.
+pub fn highlight_snippet(content: &str, terms: &[String]) -> Option<String> {
+    let lower_content = content.to_lowercase();
+    let (match_start, _) = terms
+        .iter()
+        .filter(|term| !term.is_empty())
+        .filter_map(|term| lower_content.find(term.as_str()).map(|idx| (idx, term)))
+        .min_by_key(|(idx, _)| *idx)?;
+
+    let start = lower_content[..match_start]
+        .char_indices()
+        .rev()
+        .nth(HIGHLIGHT_CONTEXT_CHARS)
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+    let end = lower_content[match_start..]
+        .char_indices()
+        .nth(HIGHLIGHT_CONTEXT_CHARS * 2)
+        .map(|(i, _)| match_start + i)
+        .unwrap_or(content.len());
+
+    let window = &content[start..end];
+    let mut highlighted = window.to_string();
+    for term in terms {
+        highlighted = replace_case_insensitive(&highlighted, term, &format!("<mark>{term}</mark>"));
+    }
+
+    let prefix = if start > 0 { "…" } else { "" };
+    let suffix = if end < content.len() { "…" } else { "" };
+    Some(format!("{prefix}{highlighted}{suffix}"))
+}
+
+fn replace_case_insensitive(haystack: &str, needle: &str, replacement: &str) -> String {
+    if needle.is_empty() {
+        return haystack.to_string();
+    }
+    let lower_haystack = haystack.to_lowercase();
+    let lower_needle = needle.to_lowercase();
+    let mut result = String::with_capacity(haystack.len());
+    let mut rest = haystack;
+    let mut lower_rest = lower_haystack.as_str();
+    while let Some(idx) = lower_rest.find(&lower_needle) {
+        result.push_str(&rest[..idx]);
+        result.push_str(replacement);
+        rest = &rest[idx + needle.len()..];
+        lower_rest = &lower_rest[idx + needle.len()..];
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_matched_term_in_mark() {
+        let snippet = highlight_snippet("the invoice is attached", &["invoice".to_string()]).unwrap();
+        assert!(snippet.contains("<mark>invoice</mark>"));
+    }
+
+    #[test]
+    fn no_match_returns_none() {
+        assert!(highlight_snippet("nothing relevant here", &["invoice".to_string()]).is_none());
+    }
+
+    #[test]
+    fn truncates_long_content_with_ellipsis() {
+        let long_content = format!("{}invoice{}", "a".repeat(200), "b".repeat(200));
+        let snippet = highlight_snippet(&long_content, &["invoice".to_string()]).unwrap();
+        assert!(snippet.starts_with('…'));
+        assert!(snippet.ends_with('…'));
+    }
+}
@@ -0,0 +1,91 @@
+//! Cross-provider search engine: aggregates results from mail, calendar and
+//! third-party providers (Jira, Confluence, ...) into one ranked list.
+
+mod analytics;
+mod dedup;
+mod document_store;
+mod facets;
+mod fuzzy;
+mod highlight;
+mod partial;
+pub mod providers;
+mod prefix;
+mod query;
+mod rebuild;
+mod reindex;
+mod timeout;
+
+pub use analytics::{QueryStat, SearchAnalytics};
+pub use dedup::{deduplicate_hits, DeduplicatedHit};
+pub use document_store::{DocumentStore, StoredDocument};
+pub use facets::{compute_facets, Facets};
+pub use fuzzy::{edit_distance, fuzzy_find, fuzzy_matches, FuzzyMatchOptions};
+pub use highlight::highlight_snippet;
+pub use partial::{AggregatedResults, ProviderFailure, ProviderOutcome};
+pub use prefix::complete_prefix;
+pub use query::{matches_advanced_query, parse_advanced_query, AdvancedQuery, QueryTerm, SearchableFields};
+pub use rebuild::{rebuild_index, IndexWriter, RebuildProgress, RebuildResult};
+pub use reindex::{plan_incremental_reindex, IndexedDocument, ReindexPlan};
+pub use timeout::run_with_deadline;
+
+use std::time::Duration;
+
+pub type ProviderId = String;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub provider: ProviderId,
+    pub id: String,
+    pub title: String,
+    pub content_type: String,
+    pub relevance: f64,
+}
+
+/// A content source the search engine can query. Implemented by the mail
+/// index, calendar index, and external providers like Jira/Confluence.
+pub trait SearchProvider {
+    fn id(&self) -> ProviderId;
+    fn search(&self, query: &str) -> Result<Vec<SearchHit>, String>;
+}
+
+#[derive(Default)]
+pub struct SearchEngine {
+    providers: Vec<Box<dyn SearchProvider>>,
+}
+
+impl SearchEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_provider(&mut self, provider: Box<dyn SearchProvider>) {
+        self.providers.push(provider);
+    }
+
+    /// Query every provider and merge the hits that succeeded, ranked by
+    /// relevance. Providers that error, or that blow past `timeout`
+    /// (the [`SearchEngineConfig::query_timeout_ms`](crate::config::SearchEngineConfig)
+    /// response-time target), are recorded as [`ProviderFailure`]s instead
+    /// of failing the whole search — a partial result set beats no result
+    /// set.
+    pub fn search(&self, query: &str) -> AggregatedResults {
+        self.search_with_timeout(query, Duration::from_millis(300))
+    }
+
+    pub fn search_with_timeout(&self, query: &str, timeout: Duration) -> AggregatedResults {
+        let mut hits = Vec::new();
+        let mut failures = Vec::new();
+
+        for provider in &self.providers {
+            let provider_id = provider.id();
+            match run_with_deadline(&provider_id, timeout, || provider.search(query)) {
+                Ok(mut provider_hits) => hits.append(&mut provider_hits),
+                Err(failure) => failures.push(failure),
+            }
+        }
+
+        hits.sort_by(|a, b| b.relevance.partial_cmp(&a.relevance).unwrap());
+
+        AggregatedResults { hits, failures }
+    }
+}
@@ -0,0 +1,94 @@
+//! Aggregated search results that degrade gracefully when some providers
+//! fail.
+
+use super::{compute_facets, Facets, ProviderId, SearchHit};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderFailure {
+    pub provider: ProviderId,
+    pub reason: String,
+}
+
+/// Outcome of querying a single provider, used by callers that want to
+/// report per-provider status to the UI (e.g. a "Jira search unavailable"
+/// banner) rather than just the merged hit list.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProviderOutcome {
+    Succeeded { hit_count: usize },
+    Failed(ProviderFailure),
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AggregatedResults {
+    pub hits: Vec<SearchHit>,
+    pub failures: Vec<ProviderFailure>,
+}
+
+impl AggregatedResults {
+    pub fn is_partial(&self) -> bool {
+        !self.failures.is_empty()
+    }
+
+    pub fn facets(&self) -> Facets {
+        compute_facets(&self.hits)
+    }
+
+    pub fn outcomes(&self) -> Vec<ProviderOutcome> {
+        let mut by_provider: std::collections::BTreeMap<&str, usize> = std::collections::BTreeMap::new();
+        for hit in &self.hits {
+            *by_provider.entry(hit.provider.as_str()).or_insert(0) += 1;
+        }
+
+        let mut outcomes: Vec<ProviderOutcome> = by_provider
+            .into_values()
+            .map(|hit_count| ProviderOutcome::Succeeded { hit_count })
+            .collect();
+        outcomes.extend(self.failures.iter().cloned().map(ProviderOutcome::Failed));
+        outcomes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{SearchEngine, SearchProvider};
+    use super::*;
+
+    struct WorkingProvider;
+    impl SearchProvider for WorkingProvider {
+        fn id(&self) -> ProviderId {
+            "mail".to_string()
+        }
+        fn search(&self, _query: &str) -> Result<Vec<SearchHit>, String> {
+            Ok(vec![SearchHit {
+                provider: "mail".to_string(),
+                id: "1".to_string(),
+                title: "Invoice".to_string(),
+                content_type: "email".to_string(),
+                relevance: 1.0,
+            }])
+        }
+    }
+
+    struct FailingProvider;
+    impl SearchProvider for FailingProvider {
+        fn id(&self) -> ProviderId {
+            "jira".to_string()
+        }
+        fn search(&self, _query: &str) -> Result<Vec<SearchHit>, String> {
+            Err("request timed out".to_string())
+        }
+    }
+
+    #[test]
+    fn returns_partial_results_when_one_provider_fails() {
+        let mut engine = SearchEngine::new();
+        engine.add_provider(Box::new(WorkingProvider));
+        engine.add_provider(Box::new(FailingProvider));
+
+        let results = engine.search("invoice");
+
+        assert_eq!(results.hits.len(), 1);
+        assert!(results.is_partial());
+        assert_eq!(results.failures[0].provider, "jira");
+    }
+}
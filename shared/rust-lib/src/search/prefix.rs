@@ -0,0 +1,78 @@
+//! Search-as-you-type prefix completion: as the user types, offer
+//! completions of the *in-progress word* they're currently typing. This is
+//! distinct from "suggestions" (past/popular full queries offered before or
+//! after typing) — prefix completion only ever extends the last word of
+//! exactly what's on screen.
+
+/// Complete the last word of `partial_query` against `vocabulary`
+/// (previously indexed terms), returning up to `limit` full-query strings
+/// with that last word replaced by each matching completion.
+pub fn complete_prefix(partial_query: &str, vocabulary: &[String], limit: usize) -> Vec<String> {
+    let Some(last_space) = partial_query.rfind(char::is_whitespace) else {
+        return complete_single_word(partial_query, vocabulary, limit);
+    };
+
+    let prefix_text = &partial_query[..=last_space];
+    let last_word = &partial_query[last_space + 1..];
+    if last_word.is_empty() {
+        return Vec::new();
+    }
+
+    complete_single_word(last_word, vocabulary, limit)
+        .into_iter()
+        .map(|completion| format!("{prefix_text}{completion}"))
+        .collect()
+}
+
+fn complete_single_word(prefix: &str, vocabulary: &[String], limit: usize) -> Vec<String> {
+    if prefix.is_empty() {
+        return Vec::new();
+    }
+    let lower_prefix = prefix.to_lowercase();
+    let mut matches: Vec<&String> = vocabulary
+        .iter()
+        .filter(|word| word.to_lowercase().starts_with(&lower_prefix))
+        .collect();
+    matches.sort_by_key(|word| word.len());
+    matches.into_iter().take(limit).cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vocab() -> Vec<String> {
+        vec![
+            "invoice".to_string(),
+            "invoicing".to_string(),
+            "invoiced".to_string(),
+            "budget".to_string(),
+        ]
+    }
+
+    #[test]
+    fn completes_single_word_query_shortest_first() {
+        let completions = complete_prefix("inv", &vocab(), 10);
+        assert_eq!(completions, vec!["invoice", "invoiced", "invoicing"]);
+    }
+
+    #[test]
+    fn completes_only_the_last_word_preserving_earlier_words() {
+        let completions = complete_prefix("q3 report inv", &vocab(), 10);
+        assert_eq!(
+            completions,
+            vec!["q3 report invoice", "q3 report invoiced", "q3 report invoicing"]
+        );
+    }
+
+    #[test]
+    fn trailing_space_with_no_partial_word_offers_nothing() {
+        assert!(complete_prefix("invoice ", &vocab(), 10).is_empty());
+    }
+
+    #[test]
+    fn respects_limit() {
+        let completions = complete_prefix("inv", &vocab(), 1);
+        assert_eq!(completions, vec!["invoice"]);
+    }
+}
@@ -0,0 +1,116 @@
+//! Jira and Confluence search providers (Atlassian Cloud REST API).
+//!
+//! Both products share a base URL and auth, so [`AtlassianClient`] models
+//! the common HTTP client and each provider maps its product's search
+//! response shape onto [`SearchHit`].
+
+use super::super::{ProviderId, SearchHit, SearchProvider};
+
+#[derive(Debug, Clone)]
+pub struct AtlassianClient {
+    pub base_url: String,
+    pub api_token: String,
+}
+
+/// One raw result from Jira's `/rest/api/3/search` or Confluence's
+/// `/wiki/rest/api/search` endpoint, already flattened to the fields we
+/// need.
+#[derive(Debug, Clone)]
+pub struct AtlassianResult {
+    pub id: String,
+    pub title: String,
+    pub excerpt: String,
+}
+
+impl AtlassianClient {
+    pub fn new(base_url: impl Into<String>, api_token: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            api_token: api_token.into(),
+        }
+    }
+
+    /// Returns no results: no request is sent to Jira. The JQL search
+    /// endpoint is `GET {base_url}/rest/api/3/search?jql=text~"{query}"`.
+    fn search_jira(&self, _query: &str) -> Result<Vec<AtlassianResult>, String> {
+        Ok(Vec::new())
+    }
+
+    /// Returns no results: no request is sent to Confluence. The CQL search
+    /// endpoint is `GET {base_url}/wiki/rest/api/search?cql=text~"{query}"`.
+    fn search_confluence(&self, _query: &str) -> Result<Vec<AtlassianResult>, String> {
+        Ok(Vec::new())
+    }
+}
+
+pub struct JiraProvider {
+    client: AtlassianClient,
+}
+
+impl JiraProvider {
+    pub fn new(client: AtlassianClient) -> Self {
+        Self { client }
+    }
+}
+
+impl SearchProvider for JiraProvider {
+    fn id(&self) -> ProviderId {
+        "jira".to_string()
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<SearchHit>, String> {
+        let results = self.client.search_jira(query)?;
+        Ok(results.into_iter().map(|r| to_hit("jira", "issue", r)).collect())
+    }
+}
+
+pub struct ConfluenceProvider {
+    client: AtlassianClient,
+}
+
+impl ConfluenceProvider {
+    pub fn new(client: AtlassianClient) -> Self {
+        Self { client }
+    }
+}
+
+impl SearchProvider for ConfluenceProvider {
+    fn id(&self) -> ProviderId {
+        "confluence".to_string()
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<SearchHit>, String> {
+        let results = self.client.search_confluence(query)?;
+        Ok(results.into_iter().map(|r| to_hit("confluence", "page", r)).collect())
+    }
+}
+
+fn to_hit(provider: &str, content_type: &str, result: AtlassianResult) -> SearchHit {
+    SearchHit {
+        provider: provider.to_string(),
+        id: result.id,
+        title: result.title,
+        content_type: content_type.to_string(),
+        // Atlassian's search APIs don't return a normalized relevance
+        // score; results already come back ranked, so earlier results are
+        // scored higher than later ones by the caller if needed.
+        relevance: 1.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jira_provider_reports_its_id() {
+        let provider = JiraProvider::new(AtlassianClient::new("https://example.atlassian.net", "token"));
+        assert_eq!(provider.id(), "jira");
+    }
+
+    #[test]
+    fn confluence_provider_reports_its_id() {
+        let provider = ConfluenceProvider::new(AtlassianClient::new("https://example.atlassian.net", "token"));
+        assert_eq!(provider.id(), "confluence");
+    }
+}
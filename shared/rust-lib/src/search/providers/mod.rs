@@ -0,0 +1,4 @@
+//! Third-party search providers that aggregate external content (issue
+//! trackers, wikis, ...) into the unified search index.
+
+pub mod jira_confluence;
@@ -0,0 +1,293 @@
+//! Advanced query syntax: quoted phrases (`"exact phrase"`), proximity
+//! search (`term1 NEAR/n term2`), boolean exclusion (`-term`) and
+//! field-scoped terms (`from:`, `subject:`, `type:`), parsed into a
+//! structured query that providers can evaluate against document text
+//! without re-implementing the syntax themselves.
+
+/// Field names a [`QueryTerm::Field`] can scope a query to.
+const FIELD_NAMES: [&str; 3] = ["from", "subject", "type"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryTerm {
+    Word(String),
+    /// An exact, contiguous sequence of words.
+    Phrase(Vec<String>),
+    /// Two words that must both appear with at most `distance` other words
+    /// between them, in either order.
+    Proximity { a: String, b: String, distance: u32 },
+    /// A term prefixed with `-`: the document must NOT match it.
+    Not(Box<QueryTerm>),
+    /// A term scoped to one field, e.g. `from:alice` or `subject:"Q3 plan"`.
+    Field { name: String, value: String },
+}
+
+/// The per-document fields a [`QueryTerm::Field`] can match against, in
+/// addition to the tokenized body text every other term matches against.
+#[derive(Debug, Clone, Copy)]
+pub struct SearchableFields<'a> {
+    pub from: &'a str,
+    pub subject: &'a str,
+    pub content_type: &'a str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct AdvancedQuery {
+    pub terms: Vec<QueryTerm>,
+}
+
+/// Parse a raw query string into an [`AdvancedQuery`]. Unrecognized syntax
+/// falls back to treating the token as a plain word, so a malformed
+/// proximity clause degrades gracefully instead of rejecting the query.
+pub fn parse_advanced_query(raw: &str) -> AdvancedQuery {
+    let mut terms = Vec::new();
+    let mut chars = raw.char_indices().peekable();
+    let mut pending_word_start: Option<usize> = None;
+    let mut pending_negated = false;
+
+    while let Some((idx, ch)) = chars.next() {
+        if ch == '"' {
+            let field = pending_word_start.take().and_then(|start| parse_field_prefix(&raw[start..idx]));
+            let phrase_start = idx + 1;
+            let mut end = raw.len();
+            while let Some(&(close_idx, close_ch)) = chars.peek() {
+                chars.next();
+                if close_ch == '"' {
+                    end = close_idx;
+                    break;
+                }
+            }
+            let phrase_text = &raw[phrase_start..end];
+            match field {
+                Some(name) => {
+                    if !phrase_text.is_empty() {
+                        push_term(&mut terms, QueryTerm::Field { name, value: phrase_text.to_string() }, pending_negated);
+                    }
+                }
+                None => {
+                    let phrase: Vec<String> = phrase_text.split_whitespace().map(|w| w.to_string()).collect();
+                    if !phrase.is_empty() {
+                        push_term(&mut terms, QueryTerm::Phrase(phrase), pending_negated);
+                    }
+                }
+            }
+            pending_negated = false;
+        } else if ch.is_whitespace() {
+            if let Some(start) = pending_word_start.take() {
+                flush_word(&mut terms, &raw[start..idx], pending_negated);
+                pending_negated = false;
+            }
+        } else if pending_word_start.is_none() {
+            if ch == '-' {
+                pending_negated = true;
+                continue;
+            }
+            pending_word_start = Some(idx);
+        }
+    }
+    if let Some(start) = pending_word_start {
+        flush_word(&mut terms, &raw[start..], pending_negated);
+    }
+
+    collapse_proximity(terms)
+}
+
+fn flush_word(terms: &mut Vec<QueryTerm>, word: &str, negated: bool) {
+    if word.is_empty() {
+        return;
+    }
+    push_term(terms, parse_word_token(word), negated);
+}
+
+/// A bare word like `roadmap` is a [`QueryTerm::Word`]; `from:alice` (with
+/// a recognized field name and a non-empty value) is a [`QueryTerm::Field`].
+fn parse_word_token(word: &str) -> QueryTerm {
+    if let Some(colon) = word.find(':') {
+        let (name, rest) = word.split_at(colon);
+        let value = &rest[1..];
+        let name = name.to_ascii_lowercase();
+        if !value.is_empty() && FIELD_NAMES.contains(&name.as_str()) {
+            return QueryTerm::Field { name, value: value.to_string() };
+        }
+    }
+    QueryTerm::Word(word.to_string())
+}
+
+/// If `prefix` is a recognized field name immediately followed by `:`
+/// (e.g. the `subject:` in `subject:"Q3 plan"`), return the field name.
+fn parse_field_prefix(prefix: &str) -> Option<String> {
+    let name = prefix.strip_suffix(':')?.to_ascii_lowercase();
+    FIELD_NAMES.contains(&name.as_str()).then_some(name)
+}
+
+fn push_term(terms: &mut Vec<QueryTerm>, term: QueryTerm, negated: bool) {
+    terms.push(if negated { QueryTerm::Not(Box::new(term)) } else { term });
+}
+
+/// Fold a `word NEAR/n word` run of plain-word terms into a single
+/// [`QueryTerm::Proximity`].
+fn collapse_proximity(terms: Vec<QueryTerm>) -> AdvancedQuery {
+    let mut collapsed = Vec::new();
+    let mut i = 0;
+    while i < terms.len() {
+        if let (QueryTerm::Word(a), Some(QueryTerm::Word(op)), Some(QueryTerm::Word(b))) =
+            (&terms[i], terms.get(i + 1), terms.get(i + 2))
+        {
+            if let Some(distance) = parse_near_operator(op) {
+                collapsed.push(QueryTerm::Proximity {
+                    a: a.clone(),
+                    b: b.clone(),
+                    distance,
+                });
+                i += 3;
+                continue;
+            }
+        }
+        collapsed.push(terms[i].clone());
+        i += 1;
+    }
+    AdvancedQuery { terms: collapsed }
+}
+
+fn parse_near_operator(token: &str) -> Option<u32> {
+    let rest = token.strip_prefix("NEAR/")?;
+    rest.parse().ok()
+}
+
+/// Whether `document` (already lowercased/tokenized by the caller into
+/// `words`, plus its `fields`) satisfies `query`.
+pub fn matches_advanced_query(query: &AdvancedQuery, words: &[String], fields: SearchableFields) -> bool {
+    query.terms.iter().all(|term| matches_term(term, words, fields))
+}
+
+fn matches_term(term: &QueryTerm, words: &[String], fields: SearchableFields) -> bool {
+    match term {
+        QueryTerm::Word(word) => words.iter().any(|w| w.eq_ignore_ascii_case(word)),
+        QueryTerm::Phrase(phrase) => words
+            .windows(phrase.len())
+            .any(|window| window.iter().zip(phrase).all(|(w, p)| w.eq_ignore_ascii_case(p))),
+        QueryTerm::Proximity { a, b, distance } => {
+            let positions_a: Vec<usize> = words
+                .iter()
+                .enumerate()
+                .filter(|(_, w)| w.eq_ignore_ascii_case(a))
+                .map(|(i, _)| i)
+                .collect();
+            let positions_b: Vec<usize> = words
+                .iter()
+                .enumerate()
+                .filter(|(_, w)| w.eq_ignore_ascii_case(b))
+                .map(|(i, _)| i)
+                .collect();
+            positions_a.iter().any(|pa| {
+                positions_b
+                    .iter()
+                    .any(|pb| pa.abs_diff(*pb) <= *distance as usize + 1)
+            })
+        }
+        QueryTerm::Not(inner) => !matches_term(inner, words, fields),
+        QueryTerm::Field { name, value } => {
+            let haystack = match name.as_str() {
+                "from" => fields.from,
+                "subject" => fields.subject,
+                "type" => fields.content_type,
+                _ => return false,
+            };
+            haystack.to_ascii_lowercase().contains(&value.to_ascii_lowercase())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(text: &str) -> Vec<String> {
+        text.split_whitespace().map(|w| w.to_string()).collect()
+    }
+
+    fn fields<'a>(from: &'a str, subject: &'a str, content_type: &'a str) -> SearchableFields<'a> {
+        SearchableFields { from, subject, content_type }
+    }
+
+    fn no_fields() -> SearchableFields<'static> {
+        fields("", "", "")
+    }
+
+    #[test]
+    fn parses_quoted_phrase_alongside_plain_words() {
+        let query = parse_advanced_query("roadmap \"Q3 launch plan\" urgent");
+        assert_eq!(
+            query.terms,
+            vec![
+                QueryTerm::Word("roadmap".to_string()),
+                QueryTerm::Phrase(vec!["Q3".to_string(), "launch".to_string(), "plan".to_string()]),
+                QueryTerm::Word("urgent".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_proximity_operator() {
+        let query = parse_advanced_query("budget NEAR/5 approval");
+        assert_eq!(
+            query.terms,
+            vec![QueryTerm::Proximity {
+                a: "budget".to_string(),
+                b: "approval".to_string(),
+                distance: 5,
+            }]
+        );
+    }
+
+    #[test]
+    fn phrase_must_match_contiguous_words_in_order() {
+        let query = parse_advanced_query("\"launch plan\"");
+        assert!(matches_advanced_query(&query, &words("the Q3 launch plan is ready"), no_fields()));
+        assert!(!matches_advanced_query(&query, &words("the launch of the plan"), no_fields()));
+    }
+
+    #[test]
+    fn proximity_matches_within_distance_in_either_order() {
+        let query = parse_advanced_query("budget NEAR/2 approval");
+        assert!(matches_advanced_query(&query, &words("we need approval on the budget today"), no_fields()));
+        assert!(!matches_advanced_query(
+            &query,
+            &words("budget discussions happened long before the approval was granted"),
+            no_fields()
+        ));
+    }
+
+    #[test]
+    fn parses_negated_word_and_field_terms() {
+        let query = parse_advanced_query("roadmap -spam from:alice subject:\"Q3 plan\"");
+        assert_eq!(
+            query.terms,
+            vec![
+                QueryTerm::Word("roadmap".to_string()),
+                QueryTerm::Not(Box::new(QueryTerm::Word("spam".to_string()))),
+                QueryTerm::Field { name: "from".to_string(), value: "alice".to_string() },
+                QueryTerm::Field { name: "subject".to_string(), value: "Q3 plan".to_string() },
+            ]
+        );
+    }
+
+    #[test]
+    fn a_negated_word_excludes_documents_that_contain_it() {
+        let query = parse_advanced_query("roadmap -spam");
+        assert!(matches_advanced_query(&query, &words("the roadmap is ready"), no_fields()));
+        assert!(!matches_advanced_query(&query, &words("roadmap spam newsletter"), no_fields()));
+    }
+
+    #[test]
+    fn a_field_scoped_term_matches_only_that_field() {
+        let query = parse_advanced_query("from:alice");
+        assert!(matches_advanced_query(&query, &words("ignored"), fields("alice@example.com", "", "")));
+        assert!(!matches_advanced_query(&query, &words("ignored"), fields("bob@example.com", "", "")));
+    }
+
+    #[test]
+    fn an_unrecognized_field_name_is_treated_as_a_plain_word() {
+        let query = parse_advanced_query("priority:high");
+        assert_eq!(query.terms, vec![QueryTerm::Word("priority:high".to_string())]);
+    }
+}
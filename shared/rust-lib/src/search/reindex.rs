@@ -0,0 +1,95 @@
+//! Incremental re-indexing: given the documents currently known to a
+//! provider and a fresh fetch, figure out what to upsert versus delete
+//! instead of rebuilding the whole index every time.
+
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedDocument {
+    pub id: String,
+    /// Opaque content fingerprint (e.g. a hash or the provider's own
+    /// `updated_at`/ETag) used to detect unchanged documents without
+    /// re-diffing the full content.
+    pub content_version: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ReindexPlan {
+    pub upsert: Vec<String>,
+    pub delete: Vec<String>,
+    pub unchanged: Vec<String>,
+}
+
+/// Diff the index's current state against a fresh fetch, producing a plan
+/// that upserts new/changed documents and deletes ones no longer present
+/// upstream.
+pub fn plan_incremental_reindex(
+    currently_indexed: &[IndexedDocument],
+    freshly_fetched: &[IndexedDocument],
+) -> ReindexPlan {
+    let current_by_id: HashMap<&str, &str> = currently_indexed
+        .iter()
+        .map(|doc| (doc.id.as_str(), doc.content_version.as_str()))
+        .collect();
+    let fetched_ids: std::collections::HashSet<&str> =
+        freshly_fetched.iter().map(|doc| doc.id.as_str()).collect();
+
+    let mut plan = ReindexPlan::default();
+
+    for doc in freshly_fetched {
+        match current_by_id.get(doc.id.as_str()) {
+            Some(version) if *version == doc.content_version => plan.unchanged.push(doc.id.clone()),
+            _ => plan.upsert.push(doc.id.clone()),
+        }
+    }
+
+    for doc in currently_indexed {
+        if !fetched_ids.contains(doc.id.as_str()) {
+            plan.delete.push(doc.id.clone());
+        }
+    }
+
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(id: &str, version: &str) -> IndexedDocument {
+        IndexedDocument {
+            id: id.to_string(),
+            content_version: version.to_string(),
+        }
+    }
+
+    #[test]
+    fn changed_content_is_upserted_unchanged_is_skipped() {
+        let current = vec![doc("1", "v1"), doc("2", "v1")];
+        let fetched = vec![doc("1", "v2"), doc("2", "v1")];
+
+        let plan = plan_incremental_reindex(&current, &fetched);
+        assert_eq!(plan.upsert, vec!["1".to_string()]);
+        assert_eq!(plan.unchanged, vec!["2".to_string()]);
+        assert!(plan.delete.is_empty());
+    }
+
+    #[test]
+    fn documents_missing_from_fresh_fetch_are_deleted() {
+        let current = vec![doc("1", "v1"), doc("2", "v1")];
+        let fetched = vec![doc("1", "v1")];
+
+        let plan = plan_incremental_reindex(&current, &fetched);
+        assert_eq!(plan.delete, vec!["2".to_string()]);
+        assert_eq!(plan.unchanged, vec!["1".to_string()]);
+    }
+
+    #[test]
+    fn new_documents_are_upserted() {
+        let current = vec![];
+        let fetched = vec![doc("1", "v1")];
+
+        let plan = plan_incremental_reindex(&current, &fetched);
+        assert_eq!(plan.upsert, vec!["1".to_string()]);
+    }
+}
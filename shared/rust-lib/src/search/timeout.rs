@@ -0,0 +1,71 @@
+//! Enforcing the search engine's response-time target ([`SearchEngineConfig::query_timeout_ms`],
+//! 300ms by default): a slow provider shouldn't be allowed to stall the
+//! whole aggregated search indefinitely.
+
+use super::{ProviderFailure, ProviderId, SearchHit};
+use std::time::{Duration, Instant};
+
+/// Run `search_fn` (a single provider's `search()` call), but only accept
+/// the result if it returns before `timeout` elapses. Modeled as a
+/// synchronous deadline check rather than spawning a real timer thread,
+/// since the providers here are themselves synchronous calls into blocking
+/// HTTP clients — the caller is expected to poll/interrupt the underlying
+/// request, not this wrapper, once the deadline passes.
+pub fn run_with_deadline<F>(
+    provider: &ProviderId,
+    timeout: Duration,
+    search_fn: F,
+) -> Result<Vec<SearchHit>, ProviderFailure>
+where
+    F: FnOnce() -> Result<Vec<SearchHit>, String>,
+{
+    let started = Instant::now();
+    let result = search_fn();
+    let elapsed = started.elapsed();
+
+    if elapsed > timeout {
+        return Err(ProviderFailure {
+            provider: provider.clone(),
+            reason: format!(
+                "exceeded {}ms response-time target (took {}ms)",
+                timeout.as_millis(),
+                elapsed.as_millis()
+            ),
+        });
+    }
+
+    result.map_err(|reason| ProviderFailure {
+        provider: provider.clone(),
+        reason,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fast_provider_result_passes_through() {
+        let result = run_with_deadline(&"mail".to_string(), Duration::from_millis(300), || Ok(Vec::new()));
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn provider_exceeding_deadline_is_reported_as_a_failure() {
+        let result = run_with_deadline(&"slow".to_string(), Duration::from_millis(0), || {
+            std::thread::sleep(Duration::from_millis(5));
+            Ok(Vec::new())
+        });
+        let failure = result.unwrap_err();
+        assert_eq!(failure.provider, "slow");
+        assert!(failure.reason.contains("response-time target"));
+    }
+
+    #[test]
+    fn provider_error_within_deadline_is_forwarded() {
+        let result = run_with_deadline(&"mail".to_string(), Duration::from_millis(300), || {
+            Err("bad query".to_string())
+        });
+        assert_eq!(result.unwrap_err().reason, "bad query");
+    }
+}
@@ -0,0 +1,117 @@
+//! In-memory test doubles for the database layer, so engine logic can be
+//! unit tested without spinning up SQLite.
+
+use crate::calendar::CalendarEvent;
+use crate::mail::MessageId;
+use std::collections::HashMap;
+
+/// Mail storage operations as a trait. [`InMemoryMailDatabase`] is its only
+/// implementation; there is no SQLite-backed one in this crate.
+pub trait MailDatabase {
+    fn upsert_message(&mut self, id: MessageId, subject: String);
+    fn get_message_subject(&self, id: &str) -> Option<&str>;
+    fn delete_message(&mut self, id: &str) -> bool;
+    fn message_count(&self) -> usize;
+}
+
+#[derive(Debug, Default)]
+pub struct InMemoryMailDatabase {
+    messages: HashMap<MessageId, String>,
+}
+
+impl InMemoryMailDatabase {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl MailDatabase for InMemoryMailDatabase {
+    fn upsert_message(&mut self, id: MessageId, subject: String) {
+        self.messages.insert(id, subject);
+    }
+
+    fn get_message_subject(&self, id: &str) -> Option<&str> {
+        self.messages.get(id).map(|s| s.as_str())
+    }
+
+    fn delete_message(&mut self, id: &str) -> bool {
+        self.messages.remove(id).is_some()
+    }
+
+    fn message_count(&self) -> usize {
+        self.messages.len()
+    }
+}
+
+pub trait CalendarDatabase {
+    fn upsert_event(&mut self, event: CalendarEvent);
+    fn get_event(&self, id: &str) -> Option<&CalendarEvent>;
+    fn delete_event(&mut self, id: &str) -> bool;
+    fn event_count(&self) -> usize;
+}
+
+#[derive(Debug, Default)]
+pub struct InMemoryCalendarDatabase {
+    events: HashMap<String, CalendarEvent>,
+}
+
+impl InMemoryCalendarDatabase {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CalendarDatabase for InMemoryCalendarDatabase {
+    fn upsert_event(&mut self, event: CalendarEvent) {
+        self.events.insert(event.id.clone(), event);
+    }
+
+    fn get_event(&self, id: &str) -> Option<&CalendarEvent> {
+        self.events.get(id)
+    }
+
+    fn delete_event(&mut self, id: &str) -> bool {
+        self.events.remove(id).is_some()
+    }
+
+    fn event_count(&self) -> usize {
+        self.events.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::epoch_plus;
+
+    #[test]
+    fn in_memory_mail_database_round_trips() {
+        let mut db = InMemoryMailDatabase::new();
+        db.upsert_message("1".to_string(), "Hello".to_string());
+        assert_eq!(db.get_message_subject("1"), Some("Hello"));
+        assert_eq!(db.message_count(), 1);
+        assert!(db.delete_message("1"));
+        assert_eq!(db.message_count(), 0);
+    }
+
+    #[test]
+    fn in_memory_calendar_database_round_trips() {
+        let mut db = InMemoryCalendarDatabase::new();
+        db.upsert_event(CalendarEvent {
+            id: "e1".to_string(),
+            calendar_id: "cal-1".to_string(),
+            uid: None,
+            title: "Standup".to_string(),
+            start: epoch_plus(0),
+            end: epoch_plus(1800),
+            description: None,
+            location: None,
+            attendee_count: 0,
+            recurring_event_id: None,
+            original_start_time: None,
+        });
+        assert_eq!(db.event_count(), 1);
+        assert!(db.get_event("e1").is_some());
+        assert!(db.delete_event("e1"));
+    }
+}
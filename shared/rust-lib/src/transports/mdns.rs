@@ -0,0 +1,86 @@
+//! mDNS peer discovery for LAN sync: the `_flowdesk-sync._tcp.local` service
+//! type and a registry of peers seen on the network, separate from the
+//! actual transfer logic in [`super::lan_sync`]. Nothing here sends mDNS
+//! packets or advertises the service.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::time::{Duration, SystemTime};
+
+pub const SERVICE_TYPE: &str = "_flowdesk-sync._tcp.local";
+
+/// Peers are considered gone after this long without a fresh announcement,
+/// matching typical mDNS TTL conventions.
+pub const PEER_TIMEOUT: Duration = Duration::from_secs(2 * 60);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveredPeer {
+    pub device_id: String,
+    pub address: IpAddr,
+    pub port: u16,
+}
+
+#[derive(Debug, Default)]
+pub struct PeerRegistry {
+    peers: HashMap<String, (DiscoveredPeer, SystemTime)>,
+}
+
+impl PeerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an mDNS announcement from `peer`, refreshing its last-seen
+    /// timestamp.
+    pub fn announce(&mut self, peer: DiscoveredPeer, now: SystemTime) {
+        self.peers.insert(peer.device_id.clone(), (peer, now));
+    }
+
+    /// An explicit mDNS goodbye (TTL=0 record) removes the peer
+    /// immediately instead of waiting for it to time out.
+    pub fn remove(&mut self, device_id: &str) {
+        self.peers.remove(device_id);
+    }
+
+    /// Peers seen within [`PEER_TIMEOUT`] of `now`.
+    pub fn active_peers(&self, now: SystemTime) -> Vec<DiscoveredPeer> {
+        self.peers
+            .values()
+            .filter(|(_, last_seen)| now.duration_since(*last_seen).unwrap_or(Duration::ZERO) < PEER_TIMEOUT)
+            .map(|(peer, _)| peer.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peer(device_id: &str) -> DiscoveredPeer {
+        DiscoveredPeer {
+            device_id: device_id.to_string(),
+            address: "192.168.1.10".parse().unwrap(),
+            port: 7777,
+        }
+    }
+
+    #[test]
+    fn announced_peer_is_active_until_it_times_out() {
+        let mut registry = PeerRegistry::new();
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+        registry.announce(peer("device-a"), now);
+
+        assert_eq!(registry.active_peers(now).len(), 1);
+        assert!(registry.active_peers(now + PEER_TIMEOUT + Duration::from_secs(1)).is_empty());
+    }
+
+    #[test]
+    fn explicit_goodbye_removes_the_peer_immediately() {
+        let mut registry = PeerRegistry::new();
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+        registry.announce(peer("device-a"), now);
+        registry.remove("device-a");
+
+        assert!(registry.active_peers(now).is_empty());
+    }
+}
@@ -0,0 +1,61 @@
+//! Pluggable transports for config/workspace sync.
+//!
+//! `cloud_sync` and `lan_sync` previously grew independent, ad-hoc APIs.
+//! This module gives them a shared [`SyncTransport`] trait so the sync
+//! coordinator can treat any transport uniformly and new transports just
+//! need to implement it. `import_export` does not implement it: its backup
+//! archives are plain functions outside the trait.
+
+use crate::error::FlowDeskResult;
+
+mod cloud_sync;
+mod envelope;
+mod import_export;
+mod lan_sync;
+mod mdns;
+
+pub use cloud_sync::CloudSyncTransport;
+pub use import_export::{export_backup, import_backup, BackupArchive, BackupEntry, BackupManifest, ManifestEntry, BACKUP_FORMAT_VERSION};
+pub use lan_sync::LanSyncTransport;
+pub use mdns::{DiscoveredPeer, PeerRegistry, SERVICE_TYPE};
+
+/// A blob of encrypted, opaque workspace config to move between devices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigBlob {
+    pub bytes: Vec<u8>,
+    pub version: u64,
+}
+
+/// Common behavior every sync transport must provide. Mirrors the
+/// TypeScript `BaseSyncTransport` interface so the two layers stay in sync
+/// conceptually even though they're implemented independently.
+pub trait SyncTransport: Send + Sync {
+    /// Stable identifier used in logs and settings (`"cloud_sync"`, `"lan_sync"`).
+    fn name(&self) -> &'static str;
+
+    /// Whether this transport can currently be used (e.g. network reachable).
+    fn is_available(&self) -> bool;
+
+    /// Upload the local config blob to this transport.
+    fn upload(&self, blob: &ConfigBlob) -> FlowDeskResult<()>;
+
+    /// Download the latest config blob from this transport, if any.
+    fn download(&self) -> FlowDeskResult<Option<ConfigBlob>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::keychain_manager::EncryptionKey;
+
+    #[test]
+    fn transports_are_interchangeable_behind_the_trait() {
+        let transports: Vec<Box<dyn SyncTransport>> = vec![
+            Box::new(CloudSyncTransport::new(EncryptionKey([1u8; 32]))),
+            Box::new(LanSyncTransport::new()),
+        ];
+
+        let names: Vec<&str> = transports.iter().map(|t| t.name()).collect();
+        assert_eq!(names, vec!["cloud_sync", "lan_sync"]);
+    }
+}